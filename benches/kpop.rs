@@ -0,0 +1,70 @@
+//! Compare ways of popping a window, run with `cargo bench`.
+
+use std::time::{Duration, Instant};
+
+use kstack::KStack;
+
+const ITEMS: usize = 1 << 16;
+const ROUNDS: usize = 50;
+
+fn bench<F: FnMut(&mut KStack<u64, K>) -> u64, const K: usize>(name: &str, mut pop_all: F) {
+    let mut best = Duration::MAX;
+    let mut checksum = 0;
+    for _ in 0..ROUNDS {
+        let mut stack = KStack::<u64, K>::with_capacity(ITEMS);
+        stack.kpush_iter(0..ITEMS as u64);
+
+        let start = Instant::now();
+        checksum = pop_all(&mut stack).wrapping_add(checksum);
+        best = best.min(start.elapsed());
+    }
+    println!(
+        "K = {:>3}  {:<12} {:>8.2} ns/window  (checksum {})",
+        K,
+        name,
+        best.as_nanos() as f64 / (ITEMS / K.max(1)) as f64,
+        checksum
+    );
+}
+
+fn bench_window<const K: usize>() {
+    // what `kpop` used to do, one `pop` per element.
+    bench::<_, K>("pop loop", |stack| {
+        let mut sum = 0;
+        while !stack.is_empty() {
+            let window: [Option<u64>; K] = core::array::from_fn(|_| stack.pop());
+            sum += window[0].unwrap_or(0) ^ window[K - 1].unwrap_or(0);
+        }
+        sum
+    });
+    bench::<_, K>("kpop", |stack| {
+        let mut sum = 0;
+        while !stack.is_empty() {
+            let window = stack.kpop();
+            sum += window[0].unwrap_or(0) ^ window[K - 1].unwrap_or(0);
+        }
+        sum
+    });
+    bench::<_, K>("kpop_exact", |stack| {
+        let mut sum = 0;
+        while let Some(window) = stack.kpop_exact() {
+            sum += window[0] ^ window[K - 1];
+        }
+        sum
+    });
+    bench::<_, K>("kpop_raw", |stack| {
+        let mut sum = 0;
+        while !stack.is_empty() {
+            let window = stack.kpop_raw();
+            let items = window.as_slice();
+            sum += items[0] ^ items[items.len() - 1];
+        }
+        sum
+    });
+}
+
+fn main() {
+    bench_window::<4>();
+    bench_window::<64>();
+    bench_window::<256>();
+}
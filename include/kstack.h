@@ -0,0 +1,46 @@
+#ifndef KSTACK_H
+#define KSTACK_H
+
+/* C API of kstack, enabled by `ffi` feature. */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+/* Opaque handle of a stack of `f64`. */
+typedef struct KStackF64 KStackF64;
+
+/* Opaque handle of a stack of `i64`. */
+typedef struct KStackI64 KStackI64;
+
+/* Opaque handle of a stack of `u8`. */
+typedef struct KStackU8 KStackU8;
+
+KStackU8 *kstack_u8_new(size_t k);
+void kstack_u8_free(KStackU8 *stack);
+size_t kstack_u8_len(const KStackU8 *stack);
+void kstack_u8_push(KStackU8 *stack, uint8_t item);
+bool kstack_u8_pop(KStackU8 *stack, uint8_t *out);
+void kstack_u8_kpush(KStackU8 *stack, const uint8_t *items, size_t len);
+size_t kstack_u8_kpop(KStackU8 *stack, uint8_t *out, size_t cap);
+size_t kstack_u8_kshow(const KStackU8 *stack, uint8_t *out, size_t cap);
+
+KStackI64 *kstack_i64_new(size_t k);
+void kstack_i64_free(KStackI64 *stack);
+size_t kstack_i64_len(const KStackI64 *stack);
+void kstack_i64_push(KStackI64 *stack, int64_t item);
+bool kstack_i64_pop(KStackI64 *stack, int64_t *out);
+void kstack_i64_kpush(KStackI64 *stack, const int64_t *items, size_t len);
+size_t kstack_i64_kpop(KStackI64 *stack, int64_t *out, size_t cap);
+size_t kstack_i64_kshow(const KStackI64 *stack, int64_t *out, size_t cap);
+
+KStackF64 *kstack_f64_new(size_t k);
+void kstack_f64_free(KStackF64 *stack);
+size_t kstack_f64_len(const KStackF64 *stack);
+void kstack_f64_push(KStackF64 *stack, double item);
+bool kstack_f64_pop(KStackF64 *stack, double *out);
+void kstack_f64_kpush(KStackF64 *stack, const double *items, size_t len);
+size_t kstack_f64_kpop(KStackF64 *stack, double *out, size_t cap);
+size_t kstack_f64_kshow(const KStackF64 *stack, double *out, size_t cap);
+
+#endif /* KSTACK_H */
@@ -0,0 +1,332 @@
+use core::mem::MaybeUninit;
+use core::{ptr, slice};
+
+use crate::KStackError;
+
+/// Fixed-capacity [`KStack`](crate::KStack) backed by an inline array.
+///
+/// It never allocates, it holds at most `CAP` element and pushing more
+/// than that returns [`KStackError::Overflow`] instead of growing. like
+/// [`KStack`](crate::KStack), K must be at least 1.
+///
+/// ```compile_fail
+///     use kstack::ArrayKStack;
+///
+///     let stack = ArrayKStack::<i32, 0, 4>::new();
+/// ```
+pub struct ArrayKStack<T, const K: usize, const CAP: usize> {
+    items: [MaybeUninit<T>; CAP],
+    len: usize,
+}
+
+/// [`ArrayKStack`] under the name used for hard real-time code.
+///
+/// # Guarantees
+/// - nothing is allocated, element live inline in the stack itself.
+/// - [`new`](ArrayKStack::new) is a `const fn`, so a stack can be a
+///   `static` or a `const` ready before `main`.
+/// - pushing beyond `CAP` returns [`KStackError::Overflow`] and leaves
+///   stack untouched, it never grows or panics.
+/// - [`push`](ArrayKStack::push) and [`pop`](ArrayKStack::pop) are O(1),
+///   window ops are O(K) and [`kpush`](ArrayKStack::kpush) is O(items).
+///
+/// # Example
+/// ```
+///     use std::alloc::{GlobalAlloc, Layout, System};
+///     use std::sync::atomic::{AtomicUsize, Ordering};
+///     use kstack::{BoundedKStack, KStackError};
+///
+///     struct Counting;
+///     static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+///
+///     unsafe impl GlobalAlloc for Counting {
+///         unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+///             ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+///             System.alloc(layout)
+///         }
+///         unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+///             System.dealloc(ptr, layout)
+///         }
+///     }
+///
+///     #[global_allocator]
+///     static ALLOCATOR: Counting = Counting;
+///
+///     const EMPTY: BoundedKStack<f32, 2, 4> = BoundedKStack::new();
+///
+///     let before = ALLOCATIONS.load(Ordering::SeqCst);
+///     let mut stack = EMPTY;
+///
+///     assert_eq!(Ok(()), stack.kpush(&[0.1, 0.2, 0.3, 0.4]));
+///     assert_eq!(Err(KStackError::Overflow), stack.push(0.5));
+///     assert_eq!(Err(KStackError::Overflow), stack.kpush(&[0.5]));
+///     assert_eq!([Some(0.4), Some(0.3)], stack.kpop());
+///     assert_eq!(Some(0.2), stack.pop());
+///     assert_eq!(&[0.1], stack.kshow_slice());
+///     assert_eq!(4, stack.capacity());
+///
+///     assert_eq!(before, ALLOCATIONS.load(Ordering::SeqCst));
+/// ```
+pub type BoundedKStack<T, const K: usize, const CAP: usize> = ArrayKStack<T, K, CAP>;
+
+impl<T, const K: usize, const CAP: usize> ArrayKStack<T, K, CAP> {
+    const NONZERO_K: () = assert!(K > 0, "window size K of ArrayKStack must be at least 1");
+
+    /// Make a new ArrayKStack, it's a `const fn` so stack can be a
+    /// `static` placed without any allocation.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::ArrayKStack;
+    ///     use std::sync::Mutex;
+    ///
+    ///     static STACK: Mutex<ArrayKStack<u8, 2, 16>> = Mutex::new(ArrayKStack::new());
+    ///
+    ///     STACK.lock().unwrap().push(1).unwrap();
+    ///
+    ///     assert_eq!(Some(1), STACK.lock().unwrap().pop());
+    /// ```
+    pub const fn new() -> Self {
+        let () = Self::NONZERO_K;
+        ArrayKStack {
+            // SAFETY: an array of `MaybeUninit` doesn't need initialization.
+            items: unsafe { MaybeUninit::<[MaybeUninit<T>; CAP]>::uninit().assume_init() },
+            len: 0,
+        }
+    }
+
+    /// Number of element on stack.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether there is no element on stack.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of element stack can hold, which is always `CAP`.
+    pub fn capacity(&self) -> usize {
+        CAP
+    }
+
+    /// Remove and get single element on top of stack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::ArrayKStack;
+    ///
+    ///     let mut stack = ArrayKStack::<i32, 3, 8>::new();
+    ///
+    ///     stack.push(1).unwrap();
+    ///     stack.push(2).unwrap();
+    ///
+    ///     assert_eq!(Some(2), stack.pop());
+    ///     assert_eq!(Some(1), stack.pop());
+    ///     assert_eq!(None, stack.pop());
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        // SAFETY: item at `len` was initialized and is no longer tracked.
+        Some(unsafe { self.items[self.len].as_ptr().read() })
+    }
+
+    /// Push a single element to stack, or an error if stack is full.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::{ArrayKStack, KStackError};
+    ///
+    ///     let mut stack = ArrayKStack::<i32, 3, 2>::new();
+    ///
+    ///     assert_eq!(Ok(()), stack.push(1));
+    ///     assert_eq!(Ok(()), stack.push(2));
+    ///     assert_eq!(Err(KStackError::Overflow), stack.push(3));
+    /// ```
+    pub fn push(&mut self, item: T) -> Result<(), KStackError> {
+        if self.len == CAP {
+            return Err(KStackError::Overflow);
+        }
+
+        self.items[self.len] = MaybeUninit::new(item);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Removes and return K top element on stack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::ArrayKStack;
+    ///
+    ///     let mut stack = ArrayKStack::<i32, 3, 8>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]).unwrap();
+    ///
+    ///     assert_eq!([Some(4), Some(3), Some(2)], stack.kpop());
+    ///     assert_eq!([Some(1), None, None], stack.kpop());
+    /// ```
+    pub fn kpop(&mut self) -> [Option<T>; K] {
+        core::array::from_fn(|_| self.pop())
+    }
+
+    /// return references to K top element on stack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::ArrayKStack;
+    ///
+    ///     let mut stack = ArrayKStack::<i32, 3, 8>::new();
+    ///
+    ///     stack.kpush(&[1, 2]).unwrap();
+    ///
+    ///     assert_eq!([Some(&2), Some(&1), None], stack.kshow_ref());
+    /// ```
+    pub fn kshow_ref(&self) -> [Option<&T>; K] {
+        let mut items = self.as_slice().iter().rev();
+        core::array::from_fn(|_| items.next())
+    }
+
+    /// return K top element on stack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::ArrayKStack;
+    ///
+    ///     let mut stack = ArrayKStack::<i32, 3, 8>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]).unwrap();
+    ///
+    ///     assert_eq!([Some(4), Some(3), Some(2)], stack.kshow());
+    ///     assert_eq!([Some(4), Some(3), Some(2)], stack.kpop());
+    /// ```
+    pub fn kshow(&self) -> [Option<T>; K]
+    where
+        T: Clone,
+    {
+        self.kshow_ref().map(|item| item.cloned())
+    }
+
+    /// return a view of K top element on stack without copying them.
+    ///
+    /// Items are in stack order, so the top element is the last one.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::ArrayKStack;
+    ///
+    ///     let mut stack = ArrayKStack::<i32, 3, 8>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]).unwrap();
+    ///
+    ///     assert_eq!(&[2, 3, 4], stack.kshow_slice());
+    /// ```
+    pub fn kshow_slice(&self) -> &[T] {
+        let start = self.len.saturating_sub(K);
+        &self.as_slice()[start..]
+    }
+
+    /// Push K element to stack, or an error if they don't fit. stack is
+    /// left untouched on error.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::{ArrayKStack, KStackError};
+    ///
+    ///     let mut stack = ArrayKStack::<i32, 3, 4>::new();
+    ///
+    ///     assert_eq!(Ok(()), stack.kpush(&[4, 5, 6]));
+    ///     assert_eq!(Err(KStackError::Overflow), stack.kpush(&[7, 8]));
+    ///     assert_eq!([Some(6), Some(5), Some(4)], stack.kpop());
+    /// ```
+    pub fn kpush(&mut self, items: &[T]) -> Result<(), KStackError>
+    where
+        T: Clone,
+    {
+        if CAP - self.len < items.len() {
+            return Err(KStackError::Overflow);
+        }
+
+        for item in items {
+            self.items[self.len] = MaybeUninit::new(item.clone());
+            self.len += 1;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn as_slice(&self) -> &[T] {
+        // SAFETY: first `len` items are always initialized.
+        unsafe { slice::from_raw_parts(self.items.as_ptr() as *const T, self.len) }
+    }
+}
+
+impl<T, const K: usize, const CAP: usize> Default for ArrayKStack<T, K, CAP> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const K: usize, const CAP: usize> Drop for ArrayKStack<T, K, CAP> {
+    fn drop(&mut self) {
+        let items = ptr::slice_from_raw_parts_mut(self.items.as_mut_ptr() as *mut T, self.len);
+        self.len = 0;
+        // SAFETY: first `len` items were initialized and are dropped once.
+        unsafe { ptr::drop_in_place(items) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_push_pop() {
+        let mut stack = ArrayKStack::<i32, 3, 2>::new();
+        assert_eq!(2, stack.capacity());
+        assert_eq!(Ok(()), stack.push(1));
+        assert_eq!(Ok(()), stack.push(2));
+        assert_eq!(Err(KStackError::Overflow), stack.push(3));
+        assert_eq!(2, stack.len());
+
+        assert_eq!(Some(2), stack.pop());
+        assert_eq!(Some(1), stack.pop());
+        assert_eq!(None, stack.pop());
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn test_kpush_kpop() {
+        let mut stack = ArrayKStack::<i32, 3, 5>::new();
+        assert_eq!(Ok(()), stack.kpush(&[1, 2, 3, 4]));
+        assert_eq!(Err(KStackError::Overflow), stack.kpush(&[5, 6]));
+        assert_eq!(4, stack.len());
+
+        assert_eq!([Some(4), Some(3), Some(2)], stack.kshow());
+        assert_eq!(&[2, 3, 4], stack.kshow_slice());
+        assert_eq!([Some(4), Some(3), Some(2)], stack.kpop());
+        assert_eq!([Some(1), None, None], stack.kshow());
+        assert_eq!([Some(1), None, None], stack.kpop());
+        assert_eq!([None, None, None], stack.kshow());
+    }
+
+    #[test]
+    fn test_drop() {
+        let item = Rc::new(());
+        {
+            let mut stack = ArrayKStack::<Rc<()>, 2, 4>::new();
+            stack
+                .kpush(&[item.clone(), item.clone(), item.clone()])
+                .unwrap();
+            assert_eq!(4, Rc::strong_count(&item));
+
+            drop(stack.pop());
+            assert_eq!(3, Rc::strong_count(&item));
+        }
+        assert_eq!(1, Rc::strong_count(&item));
+    }
+}
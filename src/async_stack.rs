@@ -0,0 +1,293 @@
+//! Stack with futures that wait for a full window.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+
+use crate::poison;
+use crate::KStack;
+
+struct State<T, const K: usize> {
+    stack: KStack<T, K>,
+    waiters: Vec<Waker>,
+}
+
+/// [`KStack`] shared between tasks, where
+/// [`kpop_async`](Self::kpop_async) waits until a full window of K
+/// element is available.
+///
+/// It doesn't depend on any runtime, pushes wake the waiting tasks through
+/// their [`Waker`].
+pub struct AsyncKStack<T, const K: usize>(Mutex<State<T, K>>);
+
+impl<T, const K: usize> AsyncKStack<T, K> {
+    /// Make a new AsyncKStack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::async_stack::AsyncKStack;
+    ///
+    ///     let stack = AsyncKStack::<i32, 3>::new();
+    /// ```
+    pub fn new() -> Self {
+        AsyncKStack(Mutex::new(State {
+            stack: KStack::new(),
+            waiters: Vec::new(),
+        }))
+    }
+
+    /// Number of element on stack.
+    pub fn len(&self) -> usize {
+        self.lock().stack.len()
+    }
+
+    /// Whether there is no element on stack.
+    pub fn is_empty(&self) -> bool {
+        self.lock().stack.is_empty()
+    }
+
+    /// Remove and get single element on top of stack, without waiting.
+    pub fn pop(&self) -> Option<T> {
+        self.lock().stack.pop()
+    }
+
+    /// Push a single element to stack, waking tasks waiting for a window
+    /// if one is complete.
+    pub fn push(&self, item: T) {
+        let mut state = self.lock();
+        state.stack.push(item);
+        Self::wake(&mut state);
+    }
+
+    /// Push K element to stack, waking tasks waiting for a window if one
+    /// is complete.
+    pub fn kpush(&self, items: &[T])
+    where
+        T: Clone,
+    {
+        let mut state = self.lock();
+        state.stack.kpush(items);
+        Self::wake(&mut state);
+    }
+
+    /// Removes and return K top element on stack, only if there are at
+    /// least K element, without waiting.
+    pub fn try_kpop(&self) -> Option<[T; K]> {
+        self.lock().stack.kpop_exact()
+    }
+
+    /// Wait until there are at least K element, then removes and return
+    /// K top element on stack.
+    ///
+    /// # Example
+    /// ```
+    ///     # use std::future::Future;
+    ///     # use std::sync::Arc;
+    ///     # use std::task::{Context, Poll, Wake, Waker};
+    ///     # struct NoopWaker;
+    ///     # impl Wake for NoopWaker {
+    ///     #     fn wake(self: Arc<Self>) {}
+    ///     # }
+    ///     use kstack::async_stack::AsyncKStack;
+    ///
+    ///     let stack = AsyncKStack::<i32, 2>::new();
+    ///     let waker = Waker::from(Arc::new(NoopWaker));
+    ///     let mut cx = Context::from_waker(&waker);
+    ///
+    ///     stack.push(1);
+    ///     let mut window = Box::pin(stack.kpop_async());
+    ///     assert_eq!(Poll::Pending, window.as_mut().poll(&mut cx));
+    ///
+    ///     stack.push(2);
+    ///     assert_eq!(Poll::Ready([2, 1]), window.as_mut().poll(&mut cx));
+    /// ```
+    pub fn kpop_async(&self) -> KPopFuture<'_, T, K> {
+        KPopFuture(self)
+    }
+
+    /// Returns a [`Stream`](futures_core::Stream) of full windows, each
+    /// one removed from stack as soon as K element are available. enabled
+    /// by `stream` feature.
+    ///
+    /// Stream never ends, there can always be more pushes.
+    ///
+    /// # Example
+    /// ```
+    ///     # use std::pin::Pin;
+    ///     # use std::sync::Arc;
+    ///     # use std::task::{Context, Poll, Wake, Waker};
+    ///     # struct NoopWaker;
+    ///     # impl Wake for NoopWaker {
+    ///     #     fn wake(self: Arc<Self>) {}
+    ///     # }
+    ///     use futures_core::Stream;
+    ///     use kstack::async_stack::AsyncKStack;
+    ///
+    ///     let stack = AsyncKStack::<i32, 2>::new();
+    ///     let waker = Waker::from(Arc::new(NoopWaker));
+    ///     let mut cx = Context::from_waker(&waker);
+    ///     let mut windows = stack.kwindows();
+    ///
+    ///     stack.kpush(&[1, 2, 3]);
+    ///     assert_eq!(Poll::Ready(Some([3, 2])), Pin::new(&mut windows).poll_next(&mut cx));
+    ///     assert_eq!(Poll::Pending, Pin::new(&mut windows).poll_next(&mut cx));
+    ///
+    ///     stack.push(4);
+    ///     assert_eq!(Poll::Ready(Some([4, 1])), Pin::new(&mut windows).poll_next(&mut cx));
+    /// ```
+    #[cfg(feature = "stream")]
+    pub fn kwindows(&self) -> KWindowStream<'_, T, K> {
+        KWindowStream(self)
+    }
+
+    fn poll_kpop(&self, cx: &mut Context<'_>) -> Poll<[T; K]> {
+        let mut state = self.lock();
+        if let Some(window) = state.stack.kpop_exact() {
+            return Poll::Ready(window);
+        }
+
+        if !state.waiters.iter().any(|w| w.will_wake(cx.waker())) {
+            state.waiters.push(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+
+    fn wake(state: &mut State<T, K>) {
+        if state.stack.len() >= K {
+            state.waiters.drain(..).for_each(Waker::wake);
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State<T, K>> {
+        poison::lock(&self.0)
+    }
+}
+
+impl<T, const K: usize> Default for AsyncKStack<T, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Future returned by [`AsyncKStack::kpop_async`].
+pub struct KPopFuture<'a, T, const K: usize>(&'a AsyncKStack<T, K>);
+
+impl<T, const K: usize> Future for KPopFuture<'_, T, K> {
+    type Output = [T; K];
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.poll_kpop(cx)
+    }
+}
+
+/// Stream returned by [`AsyncKStack::kwindows`].
+#[cfg(feature = "stream")]
+pub struct KWindowStream<'a, T, const K: usize>(&'a AsyncKStack<T, K>);
+
+#[cfg(feature = "stream")]
+impl<T, const K: usize> futures_core::Stream for KWindowStream<'_, T, K> {
+    type Item = [T; K];
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.0.poll_kpop(cx).map(Some)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::task::Wake;
+    use std::thread::{self, Thread};
+
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = Box::pin(future);
+        let waker = Arc::new(ThreadWaker(thread::current())).into();
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_ready_window() {
+        let stack = AsyncKStack::<i32, 2>::new();
+        stack.kpush(&[1, 2, 3]);
+
+        assert_eq!([3, 2], block_on(stack.kpop_async()));
+        assert_eq!(None, stack.try_kpop());
+        assert_eq!(Some(1), stack.pop());
+    }
+
+    #[test]
+    fn test_wait_for_window() {
+        let stack = Arc::new(AsyncKStack::<usize, 3>::new());
+        let producer = {
+            let stack = Arc::clone(&stack);
+            thread::spawn(move || {
+                for i in 0..30 {
+                    stack.push(i);
+                }
+            })
+        };
+
+        let mut count = 0;
+        while count < 10 {
+            let window = block_on(stack.kpop_async());
+            assert_eq!(3, window.len());
+            count += 1;
+        }
+        producer.join().unwrap();
+        assert!(stack.is_empty());
+    }
+
+    #[cfg(feature = "stream")]
+    #[test]
+    fn test_kwindows() {
+        use futures_core::Stream;
+
+        struct Next<'a, S>(&'a mut S);
+
+        impl<S: Stream + Unpin> Future for Next<'_, S> {
+            type Output = Option<S::Item>;
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                Pin::new(&mut *self.0).poll_next(cx)
+            }
+        }
+
+        let stack = Arc::new(AsyncKStack::<usize, 2>::new());
+        let producer = {
+            let stack = Arc::clone(&stack);
+            thread::spawn(move || {
+                for i in 0..20 {
+                    stack.push(i);
+                }
+            })
+        };
+
+        let mut windows = stack.kwindows();
+        let mut count = 0;
+        while let Some(window) = block_on(Next(&mut windows)) {
+            assert_eq!(2, window.len());
+            count += 1;
+            if count == 10 {
+                break;
+            }
+        }
+        producer.join().unwrap();
+        assert!(stack.is_empty());
+    }
+}
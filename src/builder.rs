@@ -0,0 +1,202 @@
+use alloc::vec::Vec;
+
+use crate::{CappedKStack, KStack, KStackError, OverflowPolicy};
+
+/// Builder configuring a [`KStack`] or [`CappedKStack`] before making it.
+///
+/// [`build`](Self::build) makes a plain [`KStack`]. setting a max depth or
+/// policy turns it into a [`CappedKStackBuilder`], which can only build a
+/// [`CappedKStack`], so a bound is never dropped.
+///
+/// # Example
+/// ```
+///     use kstack::{KStackBuilder, OverflowPolicy};
+///
+///     let stack = KStackBuilder::<i32, 2>::new()
+///         .capacity(16)
+///         .seed([1, 2, 3])
+///         .build();
+///
+///     assert!(stack.capacity() >= 16);
+///     assert_eq!([Some(3), Some(2)], stack.kshow());
+///
+///     let capped = KStackBuilder::<i32, 2>::new()
+///         .max_depth(2)
+///         .policy(OverflowPolicy::Evict)
+///         .seed([1, 2, 3])
+///         .build()
+///         .unwrap();
+///
+///     assert_eq!(&[2, 3], capped.kshow_slice());
+/// ```
+pub struct KStackBuilder<T, const K: usize> {
+    capacity: usize,
+    seed: Vec<T>,
+}
+
+/// Builder for a [`CappedKStack`], made by [`KStackBuilder::max_depth`] or
+/// [`KStackBuilder::policy`].
+pub struct CappedKStackBuilder<T, const K: usize> {
+    capacity: usize,
+    max_depth: Option<usize>,
+    policy: OverflowPolicy,
+    seed: Vec<T>,
+}
+
+impl<T, const K: usize> KStackBuilder<T, K> {
+    /// Make a new KStackBuilder, for an empty stack with no capacity or
+    /// max depth.
+    pub fn new() -> Self {
+        KStackBuilder {
+            capacity: 0,
+            seed: Vec::new(),
+        }
+    }
+
+    /// Make room for at least `capacity` element without reallocating.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Most element the stack can hold, making it a capped one.
+    pub fn max_depth(self, max_depth: usize) -> CappedKStackBuilder<T, K> {
+        self.capped().max_depth(max_depth)
+    }
+
+    /// What the stack does when a push goes beyond its max depth, making
+    /// it a capped one.
+    pub fn policy(self, policy: OverflowPolicy) -> CappedKStackBuilder<T, K> {
+        self.capped().policy(policy)
+    }
+
+    /// Element to push on stack when it's made, in order so the last one
+    /// ends up on top. calling it again adds more element.
+    pub fn seed<I: IntoIterator<Item = T>>(mut self, items: I) -> Self {
+        self.seed.extend(items);
+        self
+    }
+
+    fn capped(self) -> CappedKStackBuilder<T, K> {
+        CappedKStackBuilder {
+            capacity: self.capacity,
+            max_depth: None,
+            policy: OverflowPolicy::default(),
+            seed: self.seed,
+        }
+    }
+
+    /// Make a [`KStack`] with seed element.
+    pub fn build(self) -> KStack<T, K> {
+        let mut stack = KStack::with_capacity(self.capacity.max(self.seed.len()));
+        stack.kpush_iter(self.seed);
+        stack
+    }
+
+    /// Make a [`CappedKStack`] with seed element and unlimited max depth.
+    pub fn build_capped(self) -> CappedKStack<T, K> {
+        CappedKStack::with_stack(self.build(), usize::MAX, OverflowPolicy::default())
+    }
+}
+
+impl<T, const K: usize> CappedKStackBuilder<T, K> {
+    /// Make room for at least `capacity` element without reallocating.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Most element the stack can hold.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// What the stack does when a push goes beyond its max depth.
+    pub fn policy(mut self, policy: OverflowPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Element to push on stack when it's made, in order so the last one
+    /// ends up on top. calling it again adds more element.
+    pub fn seed<I: IntoIterator<Item = T>>(mut self, items: I) -> Self {
+        self.seed.extend(items);
+        self
+    }
+
+    /// Make a [`CappedKStack`] with seed element pushed by its policy, its
+    /// max depth is unlimited if not set.
+    ///
+    /// # Errors
+    /// Returns [`KStackError::Overflow`] if seed element don't fit and
+    /// policy is [`OverflowPolicy::Reject`].
+    ///
+    /// # Panics
+    /// Panics if seed element don't fit and policy is
+    /// [`OverflowPolicy::Panic`].
+    pub fn build(self) -> Result<CappedKStack<T, K>, KStackError> {
+        let max_depth = self.max_depth.unwrap_or(usize::MAX);
+        let stack = KStack::with_capacity(self.capacity.min(max_depth));
+        let mut capped = CappedKStack::with_stack(stack, max_depth, self.policy);
+        for item in self.seed {
+            capped.push(item)?;
+        }
+        Ok(capped)
+    }
+}
+
+impl<T, const K: usize> Default for KStackBuilder<T, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build() {
+        let stack = KStackBuilder::<i32, 3>::new().build();
+        assert!(stack.is_empty());
+
+        let stack = KStackBuilder::<i32, 3>::default()
+            .seed(1..=2)
+            .seed(vec![3, 4])
+            .build();
+        assert_eq!(vec![4, 3, 2, 1], stack.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_build_capped() {
+        let capped = KStackBuilder::<i32, 2>::new().capacity(100).build_capped();
+        assert_eq!(usize::MAX, capped.max_depth());
+        assert!(capped.as_kstack().capacity() >= 100);
+
+        let rejected = KStackBuilder::<i32, 2>::new()
+            .max_depth(2)
+            .seed([1, 2, 3])
+            .build();
+        assert!(matches!(rejected, Err(KStackError::Overflow)));
+
+        let capped = KStackBuilder::<i32, 2>::new()
+            .max_depth(3)
+            .capacity(100)
+            .seed([1, 2])
+            .build()
+            .unwrap();
+        assert_eq!(OverflowPolicy::Reject, capped.policy());
+        assert!(capped.as_kstack().capacity() >= 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "beyond max depth 1")]
+    fn test_build_capped_panic() {
+        let _ = KStackBuilder::<i32, 2>::new()
+            .max_depth(1)
+            .policy(OverflowPolicy::Panic)
+            .seed([1, 2])
+            .build();
+    }
+}
@@ -0,0 +1,306 @@
+use crate::{KStack, KStackError, RingStorage};
+
+/// What a [`CappedKStack`] does when a push goes beyond its max depth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OverflowPolicy {
+    /// Leave stack untouched and return [`KStackError::Overflow`].
+    #[default]
+    Reject,
+    /// Drop the bottom (oldest) element to make room.
+    Evict,
+    /// Panic.
+    Panic,
+}
+
+/// [`KStack`] that never holds more than `max_depth` element.
+///
+/// Pushing beyond `max_depth` is handled by its [`OverflowPolicy`], with
+/// [`OverflowPolicy::Evict`] it keeps the most recent `max_depth` element
+/// like a ring buffer, on a [`RingStorage`] so evicting is amortized O(1).
+pub struct CappedKStack<T, const K: usize> {
+    stack: KStack<T, K, RingStorage<T>>,
+    max_depth: usize,
+    policy: OverflowPolicy,
+}
+
+impl<T, const K: usize> CappedKStack<T, K> {
+    /// Make a new CappedKStack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::{CappedKStack, OverflowPolicy};
+    ///
+    ///     let mut stack = CappedKStack::<i32, 3>::new(8, OverflowPolicy::Evict);
+    /// ```
+    pub fn new(max_depth: usize, policy: OverflowPolicy) -> Self {
+        Self::with_stack(KStack::new(), max_depth, policy)
+    }
+
+    /// Make a CappedKStack on top of an empty `stack`, keeping its
+    /// capacity.
+    pub(crate) fn with_stack(
+        stack: KStack<T, K>,
+        max_depth: usize,
+        policy: OverflowPolicy,
+    ) -> Self {
+        debug_assert!(stack.is_empty());
+        CappedKStack {
+            stack: stack.map_storage(RingStorage::from),
+            max_depth,
+            policy,
+        }
+    }
+
+    /// Most element stack can hold.
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Policy used when a push goes beyond max depth.
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    /// Number of element on stack.
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Whether there is no element on stack.
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    /// Get underlying stack, for all the read-only operations.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::{CappedKStack, OverflowPolicy};
+    ///
+    ///     let mut stack = CappedKStack::<i32, 3>::new(8, OverflowPolicy::Evict);
+    ///
+    ///     stack.kpush(&[1, 2]).unwrap();
+    ///
+    ///     assert_eq!(vec![&2, &1], stack.as_kstack().iter().collect::<Vec<_>>());
+    /// ```
+    pub fn as_kstack(&self) -> &KStack<T, K, RingStorage<T>> {
+        &self.stack
+    }
+
+    /// Consume and return underlying stack, which is no longer bounded.
+    pub fn into_kstack(self) -> KStack<T, K> {
+        self.stack.map_storage(RingStorage::into_vec)
+    }
+
+    /// Remove and get single element on top of stack.
+    pub fn pop(&mut self) -> Option<T> {
+        self.stack.pop()
+    }
+
+    /// Push a single element to stack, handling overflow by policy.
+    ///
+    /// # Panics
+    /// Panics if stack is full and policy is [`OverflowPolicy::Panic`].
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::{CappedKStack, KStackError, OverflowPolicy};
+    ///
+    ///     let mut stack = CappedKStack::<i32, 3>::new(2, OverflowPolicy::Reject);
+    ///
+    ///     assert_eq!(Ok(()), stack.push(1));
+    ///     assert_eq!(Ok(()), stack.push(2));
+    ///     assert_eq!(Err(KStackError::Overflow), stack.push(3));
+    ///
+    ///     let mut stack = CappedKStack::<i32, 3>::new(2, OverflowPolicy::Evict);
+    ///
+    ///     stack.push(1).unwrap();
+    ///     stack.push(2).unwrap();
+    ///     stack.push(3).unwrap();
+    ///
+    ///     assert_eq!([Some(3), Some(2), None], stack.kpop());
+    /// ```
+    pub fn push(&mut self, item: T) -> Result<(), KStackError> {
+        self.make_room(1)?;
+        if self.evict_for(1) == 1 {
+            self.stack.push(item);
+        }
+        Ok(())
+    }
+
+    /// Removes and return K top element on stack.
+    pub fn kpop(&mut self) -> [Option<T>; K] {
+        self.stack.kpop()
+    }
+
+    /// return references to K top element on stack.
+    pub fn kshow_ref(&self) -> [Option<&T>; K] {
+        self.stack.kshow_ref()
+    }
+
+    /// return K top element on stack.
+    pub fn kshow(&self) -> [Option<T>; K]
+    where
+        T: Clone,
+    {
+        self.stack.kshow()
+    }
+
+    /// return a view of K top element on stack without copying them.
+    pub fn kshow_slice(&self) -> &[T] {
+        self.stack.kshow_slice()
+    }
+
+    /// Push K element to stack, handling overflow by policy. with
+    /// [`OverflowPolicy::Reject`] either all or none of element are pushed.
+    ///
+    /// # Panics
+    /// Panics if element don't fit and policy is [`OverflowPolicy::Panic`].
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::{CappedKStack, KStackError, OverflowPolicy};
+    ///
+    ///     let mut stack = CappedKStack::<i32, 3>::new(4, OverflowPolicy::Evict);
+    ///
+    ///     stack.kpush(&[1, 2, 3]).unwrap();
+    ///     stack.kpush(&[4, 5, 6]).unwrap();
+    ///
+    ///     assert_eq!(4, stack.len());
+    ///     assert_eq!([Some(6), Some(5), Some(4)], stack.kpop());
+    ///     assert_eq!([Some(3), None, None], stack.kpop());
+    /// ```
+    pub fn kpush(&mut self, items: &[T]) -> Result<(), KStackError>
+    where
+        T: Clone,
+    {
+        self.make_room(items.len())?;
+        let fit = self.evict_for(items.len());
+        self.stack.kpush(&items[items.len() - fit..]);
+        Ok(())
+    }
+
+    fn make_room(&self, additional: usize) -> Result<(), KStackError> {
+        if self.max_depth - self.stack.len().min(self.max_depth) >= additional {
+            return Ok(());
+        }
+
+        match self.policy {
+            OverflowPolicy::Reject => Err(KStackError::Overflow),
+            OverflowPolicy::Evict => Ok(()),
+            OverflowPolicy::Panic => panic!(
+                "stack overflow: pushing {} element beyond max depth {}",
+                additional, self.max_depth
+            ),
+        }
+    }
+
+    /// Evict from the bottom before pushing `additional` element, so stack
+    /// never holds more than max depth, and return how many of the newest
+    /// ones fit. the rest are counted as pushed and evicted right away.
+    fn evict_for(&mut self, additional: usize) -> usize {
+        let fit = additional.min(self.max_depth);
+        let skipped = additional - fit;
+        if skipped > 0 {
+            self.stack.record_push(skipped);
+            self.stack.record_pop(skipped);
+        }
+
+        let excess = self
+            .stack
+            .len()
+            .saturating_add(fit)
+            .saturating_sub(self.max_depth);
+        if excess > 0 {
+            let evicted = self.stack.0.evict_front(excess);
+            self.stack.record_pop(evicted);
+        }
+        fit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reject() {
+        let mut stack = CappedKStack::<i32, 2>::new(3, OverflowPolicy::Reject);
+        assert_eq!(Ok(()), stack.kpush(&[1, 2]));
+        assert_eq!(Err(KStackError::Overflow), stack.kpush(&[3, 4]));
+        assert_eq!(2, stack.len());
+        assert_eq!(Ok(()), stack.push(3));
+        assert_eq!(Err(KStackError::Overflow), stack.push(4));
+
+        assert_eq!([Some(3), Some(2)], stack.kpop());
+        assert_eq!([Some(1), None], stack.kpop());
+    }
+
+    #[test]
+    fn test_evict() {
+        let mut stack = CappedKStack::<i32, 2>::new(3, OverflowPolicy::Evict);
+        for i in 1..=5 {
+            stack.push(i).unwrap();
+        }
+        assert_eq!(3, stack.len());
+        assert_eq!(&[4, 5], stack.kshow_slice());
+
+        stack.kpush(&[6, 7, 8, 9]).unwrap();
+        assert_eq!(
+            vec![&9, &8, &7],
+            stack.as_kstack().iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_evict_many() {
+        let mut stack = CappedKStack::<u32, 2>::new(4, OverflowPolicy::Evict);
+        for i in 0..1000 {
+            stack.push(i).unwrap();
+        }
+        assert!(stack.as_kstack().capacity() < 16);
+        assert_eq!(&[998, 999], stack.kshow_slice());
+        assert_eq!(vec![999, 998, 997, 996], stack.into_kstack().kpop_n(10));
+    }
+
+    #[test]
+    fn test_evict_large_kpush() {
+        let mut stack = CappedKStack::<u32, 2>::new(4, OverflowPolicy::Evict);
+        stack.kpush(&[1, 2, 3]).unwrap();
+        let items: Vec<u32> = (0..10_000).collect();
+        stack.kpush(&items).unwrap();
+        assert!(stack.as_kstack().capacity() < 16);
+        assert_eq!(vec![9999, 9998, 9997, 9996], stack.into_kstack().kpop_n(10));
+
+        let mut stack = CappedKStack::<u32, 2>::new(0, OverflowPolicy::Evict);
+        stack.push(1).unwrap();
+        stack.kpush(&[2, 3]).unwrap();
+        assert!(stack.is_empty());
+    }
+
+    #[cfg(feature = "stats")]
+    #[test]
+    fn test_evict_counted() {
+        let mut stack = CappedKStack::<u32, 2>::new(2, OverflowPolicy::Evict);
+        stack.kpush(&[1, 2, 3, 4, 5]).unwrap();
+        let stats = stack.as_kstack().stats();
+        assert_eq!(5, stats.pushes);
+        assert_eq!(3, stats.pops);
+        assert_eq!(2, stats.depth);
+    }
+
+    #[test]
+    fn test_evict_zero_depth() {
+        let mut stack = CappedKStack::<i32, 2>::new(0, OverflowPolicy::Evict);
+        stack.push(1).unwrap();
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "beyond max depth 1")]
+    fn test_panic() {
+        let mut stack = CappedKStack::<i32, 2>::new(1, OverflowPolicy::Panic);
+        stack.push(1).unwrap();
+        let _ = stack.push(2);
+    }
+}
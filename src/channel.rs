@@ -0,0 +1,214 @@
+//! LIFO channel, the most recently sent element is received first.
+
+use std::sync::mpsc::{RecvError, SendError};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+use crate::poison::{self, unpoisoned};
+use crate::KStack;
+
+struct State<T, const K: usize> {
+    stack: KStack<T, K>,
+    senders: usize,
+    receiver: bool,
+}
+
+struct Shared<T, const K: usize> {
+    state: Mutex<State<T, K>>,
+    ready: Condvar,
+}
+
+impl<T, const K: usize> Shared<T, K> {
+    fn lock(&self) -> MutexGuard<'_, State<T, K>> {
+        poison::lock(&self.state)
+    }
+}
+
+/// Make a LIFO channel, freshest element are received first and
+/// [`Receiver::recv_k`] receives them K at a time.
+///
+/// # Example
+/// ```
+///     use kstack::channel;
+///     use std::thread;
+///
+///     let (sender, receiver) = channel::channel::<u32, 2>();
+///
+///     thread::spawn(move || {
+///         for i in 1..=3 {
+///             sender.send(i).unwrap();
+///         }
+///     })
+///     .join()
+///     .unwrap();
+///
+///     assert_eq!(Ok([3, 2]), receiver.recv_k());
+///     assert_eq!(Ok(1), receiver.recv());
+///     assert!(receiver.recv().is_err());
+/// ```
+pub fn channel<T, const K: usize>() -> (Sender<T, K>, Receiver<T, K>) {
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            stack: KStack::new(),
+            senders: 1,
+            receiver: true,
+        }),
+        ready: Condvar::new(),
+    });
+    (Sender(Arc::clone(&shared)), Receiver(shared))
+}
+
+/// Sending half of a channel made by [`channel`], it can be cloned to
+/// send from several threads.
+pub struct Sender<T, const K: usize>(Arc<Shared<T, K>>);
+
+impl<T, const K: usize> Sender<T, K> {
+    /// Send an element, or give it back if [`Receiver`] is dropped.
+    pub fn send(&self, item: T) -> Result<(), SendError<T>> {
+        let mut state = self.0.lock();
+        if !state.receiver {
+            return Err(SendError(item));
+        }
+        state.stack.push(item);
+        self.0.ready.notify_all();
+        Ok(())
+    }
+
+    /// Send K element atomically, so they are received together and in
+    /// order, returns an error if [`Receiver`] is dropped.
+    pub fn ksend(&self, items: &[T]) -> Result<(), SendError<()>>
+    where
+        T: Clone,
+    {
+        let mut state = self.0.lock();
+        if !state.receiver {
+            return Err(SendError(()));
+        }
+        state.stack.kpush(items);
+        self.0.ready.notify_all();
+        Ok(())
+    }
+}
+
+impl<T, const K: usize> Clone for Sender<T, K> {
+    fn clone(&self) -> Self {
+        self.0.lock().senders += 1;
+        Sender(Arc::clone(&self.0))
+    }
+}
+
+impl<T, const K: usize> Drop for Sender<T, K> {
+    fn drop(&mut self) {
+        let mut state = self.0.lock();
+        state.senders -= 1;
+        if state.senders == 0 {
+            // wake receiver so it sees there is nothing more to wait for.
+            self.0.ready.notify_all();
+        }
+    }
+}
+
+/// Receiving half of a channel made by [`channel`].
+pub struct Receiver<T, const K: usize>(Arc<Shared<T, K>>);
+
+impl<T, const K: usize> Receiver<T, K> {
+    /// Number of element sent and not received yet.
+    pub fn len(&self) -> usize {
+        self.0.lock().stack.len()
+    }
+
+    /// Whether there is no element waiting to be received.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Wait for an element and receive the most recent one, returns an
+    /// error if channel is empty and all [`Sender`]s are dropped.
+    pub fn recv(&self) -> Result<T, RecvError> {
+        let state = self.0.lock();
+        let state = self
+            .0
+            .ready
+            .wait_while(state, |state| state.stack.is_empty() && state.senders > 0);
+        let mut state = unpoisoned(state);
+        state.stack.pop().ok_or(RecvError)
+    }
+
+    /// Wait for K element and receive the most recent ones, top first.
+    ///
+    /// Returns an error if there are less than K element and all
+    /// [`Sender`]s are dropped, the remaining ones are still available
+    /// through [`recv`](Self::recv).
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::channel;
+    ///
+    ///     let (sender, receiver) = channel::channel::<u32, 2>();
+    ///
+    ///     sender.ksend(&[1, 2, 3]).unwrap();
+    ///     drop(sender);
+    ///
+    ///     assert_eq!(Ok([3, 2]), receiver.recv_k());
+    ///     assert!(receiver.recv_k().is_err());
+    ///     assert_eq!(Ok(1), receiver.recv());
+    /// ```
+    pub fn recv_k(&self) -> Result<[T; K], RecvError> {
+        let state = self.0.lock();
+        let state = self
+            .0
+            .ready
+            .wait_while(state, |state| state.stack.len() < K && state.senders > 0);
+        let mut state = unpoisoned(state);
+        state.stack.kpop_exact().ok_or(RecvError)
+    }
+}
+
+impl<T, const K: usize> Drop for Receiver<T, K> {
+    fn drop(&mut self) {
+        self.0.lock().receiver = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_many_senders() {
+        let (sender, receiver) = channel::<usize, 4>();
+        let senders: Vec<_> = (0..4)
+            .map(|id| {
+                let sender = sender.clone();
+                thread::spawn(move || {
+                    for _ in 0..50 {
+                        sender.ksend(&[id; 4]).unwrap();
+                    }
+                })
+            })
+            .collect();
+        drop(sender);
+
+        let mut windows = 0;
+        while let Ok(window) = receiver.recv_k() {
+            assert!(window.iter().all(|&item| item == window[0]));
+            windows += 1;
+        }
+        for sender in senders {
+            sender.join().unwrap();
+        }
+        assert_eq!(200, windows);
+        assert!(receiver.is_empty());
+    }
+
+    #[test]
+    fn test_receiver_dropped() {
+        let (sender, receiver) = channel::<u8, 2>();
+        sender.send(1).unwrap();
+        assert_eq!(1, receiver.len());
+        drop(receiver);
+
+        assert_eq!(Err(SendError(2)), sender.send(2));
+        assert_eq!(Err(SendError(())), sender.ksend(&[3, 4]));
+    }
+}
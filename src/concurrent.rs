@@ -0,0 +1,299 @@
+//! Lock-free stack.
+
+use core::mem::ManuallyDrop;
+use core::ptr;
+use core::sync::atomic::Ordering::{AcqRel, Acquire, Relaxed, Release};
+
+use crossbeam_epoch::{self as epoch, Atomic, Guard, Owned, Shared};
+
+struct Node<T> {
+    data: ManuallyDrop<T>,
+    next: Atomic<Node<T>>,
+}
+
+/// Lock-free Treiber stack with window operations.
+///
+/// `kpush` links all of its element and `kpop` unlinks up to K element
+/// with a single compare-and-swap on the top of stack, so a window is
+/// never split or interleaved with other threads. memory of popped
+/// element is reclaimed with [`crossbeam_epoch`]. K must be at least 1.
+///
+/// ```compile_fail
+///     use kstack::concurrent::ConcurrentKStack;
+///
+///     let stack = ConcurrentKStack::<i32, 0>::new();
+/// ```
+pub struct ConcurrentKStack<T, const K: usize> {
+    head: Atomic<Node<T>>,
+}
+
+unsafe impl<T: Send, const K: usize> Send for ConcurrentKStack<T, K> {}
+unsafe impl<T: Send, const K: usize> Sync for ConcurrentKStack<T, K> {}
+
+impl<T, const K: usize> ConcurrentKStack<T, K> {
+    const NONZERO_K: () = assert!(
+        K > 0,
+        "window size K of ConcurrentKStack must be at least 1"
+    );
+
+    /// Make a new ConcurrentKStack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::concurrent::ConcurrentKStack;
+    ///
+    ///     let stack = ConcurrentKStack::<i32, 3>::new();
+    /// ```
+    pub fn new() -> Self {
+        let () = Self::NONZERO_K;
+        ConcurrentKStack {
+            head: Atomic::null(),
+        }
+    }
+
+    /// Whether there is no element on stack.
+    pub fn is_empty(&self) -> bool {
+        let guard = epoch::pin();
+        self.head.load(Acquire, &guard).is_null()
+    }
+
+    /// Remove and get single element on top of stack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::concurrent::ConcurrentKStack;
+    ///
+    ///     let stack = ConcurrentKStack::<i32, 3>::new();
+    ///
+    ///     stack.push(1);
+    ///     stack.push(2);
+    ///
+    ///     assert_eq!(Some(2), stack.pop());
+    ///     assert_eq!(Some(1), stack.pop());
+    ///     assert_eq!(None, stack.pop());
+    /// ```
+    pub fn pop(&self) -> Option<T> {
+        let guard = epoch::pin();
+        let [item] = self.pop_batch::<1>(&guard);
+        item
+    }
+
+    /// Push a single element to stack.
+    pub fn push(&self, item: T) {
+        let guard = epoch::pin();
+        let node = Owned::new(Node {
+            data: ManuallyDrop::new(item),
+            next: Atomic::null(),
+        })
+        .into_shared(&guard);
+        self.link(node, node, &guard);
+    }
+
+    /// Removes and return up to K top element on stack in a single atomic
+    /// step.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::concurrent::ConcurrentKStack;
+    ///
+    ///     let stack = ConcurrentKStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]);
+    ///
+    ///     assert_eq!([Some(4), Some(3), Some(2)], stack.kpop());
+    ///     assert_eq!([Some(1), None, None], stack.kpop());
+    /// ```
+    pub fn kpop(&self) -> [Option<T>; K] {
+        let guard = epoch::pin();
+        self.pop_batch::<K>(&guard)
+    }
+
+    /// Push K element to stack in a single atomic step.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::concurrent::ConcurrentKStack;
+    ///
+    ///     let stack = ConcurrentKStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[4, 5, 6]);
+    ///
+    ///     assert_eq!([Some(6), Some(5), Some(4)], stack.kpop());
+    /// ```
+    pub fn kpush(&self, items: &[T])
+    where
+        T: Clone,
+    {
+        let guard = epoch::pin();
+        let mut top = Shared::null();
+        let mut bottom = Shared::null();
+        for item in items {
+            let node = Owned::new(Node {
+                data: ManuallyDrop::new(item.clone()),
+                next: Atomic::from(top),
+            })
+            .into_shared(&guard);
+            if bottom.is_null() {
+                bottom = node;
+            }
+            top = node;
+        }
+
+        if !top.is_null() {
+            self.link(top, bottom, &guard);
+        }
+    }
+
+    /// Link an unpublished chain of nodes from `top` to `bottom` on top of
+    /// stack.
+    fn link<'g>(&self, top: Shared<'g, Node<T>>, bottom: Shared<'g, Node<T>>, guard: &'g Guard) {
+        // SAFETY: `bottom` is not published yet, so only this thread uses it.
+        let bottom = unsafe { bottom.deref() };
+        let mut head = self.head.load(Relaxed, guard);
+        loop {
+            bottom.next.store(head, Relaxed);
+            match self
+                .head
+                .compare_exchange(head, top, Release, Relaxed, guard)
+            {
+                Ok(_) => return,
+                Err(error) => head = error.current,
+            }
+        }
+    }
+
+    /// Unlink up to N nodes from top of stack and take their data.
+    fn pop_batch<const N: usize>(&self, guard: &Guard) -> [Option<T>; N] {
+        let mut nodes = [Shared::null(); N];
+        let mut head = self.head.load(Acquire, guard);
+        let count = loop {
+            let mut count = 0;
+            let mut next = head;
+            while count < N && !next.is_null() {
+                nodes[count] = next;
+                // SAFETY: nodes reachable while pinned are not reclaimed.
+                next = unsafe { next.deref() }.next.load(Acquire, guard);
+                count += 1;
+            }
+
+            if count == 0 {
+                break 0;
+            }
+
+            match self
+                .head
+                .compare_exchange(head, next, AcqRel, Acquire, guard)
+            {
+                Ok(_) => break count,
+                Err(error) => head = error.current,
+            }
+        };
+
+        core::array::from_fn(|i| {
+            if i >= count {
+                return None;
+            }
+
+            // SAFETY: winning the exchange unlinked these nodes, so this
+            // thread is the only one taking their data and destroying them.
+            unsafe {
+                let data = ptr::read(&nodes[i].deref().data);
+                guard.defer_destroy(nodes[i]);
+                Some(ManuallyDrop::into_inner(data))
+            }
+        })
+    }
+}
+
+impl<T, const K: usize> Default for ConcurrentKStack<T, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const K: usize> Drop for ConcurrentKStack<T, K> {
+    fn drop(&mut self) {
+        // SAFETY: `&mut self` means no other thread can access nodes.
+        unsafe {
+            let guard = epoch::unprotected();
+            let mut next = self.head.load(Relaxed, guard);
+            while !next.is_null() {
+                let mut node = next.into_owned();
+                next = node.next.load(Relaxed, guard);
+                ManuallyDrop::drop(&mut node.data);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_push_pop() {
+        let stack = ConcurrentKStack::<i32, 2>::new();
+        assert!(stack.is_empty());
+        stack.push(1);
+        stack.kpush(&[2, 3, 4]);
+        assert!(!stack.is_empty());
+
+        assert_eq!([Some(4), Some(3)], stack.kpop());
+        assert_eq!(Some(2), stack.pop());
+        assert_eq!([Some(1), None], stack.kpop());
+        assert_eq!([None, None], stack.kpop());
+    }
+
+    #[test]
+    fn test_drop() {
+        let item = Rc::new(());
+        {
+            let stack = ConcurrentKStack::<Rc<()>, 2>::new();
+            stack.kpush(&[item.clone(), item.clone(), item.clone()]);
+            drop(stack.pop());
+            assert_eq!(3, Rc::strong_count(&item));
+        }
+        assert_eq!(1, Rc::strong_count(&item));
+    }
+
+    #[test]
+    fn test_concurrent_windows() {
+        const THREADS: usize = 8;
+        const WINDOWS: usize = 500;
+
+        let stack = Arc::new(ConcurrentKStack::<usize, 4>::new());
+        let popped = Arc::new(AtomicUsize::new(0));
+        let threads: Vec<_> = (0..THREADS)
+            .map(|id| {
+                let stack = Arc::clone(&stack);
+                let popped = Arc::clone(&popped);
+                thread::spawn(move || {
+                    for window in 0..WINDOWS {
+                        stack.kpush(&[id * WINDOWS + window; 4]);
+                        if let [Some(item), rest @ ..] = stack.kpop() {
+                            assert!(rest.iter().all(|other| *other == Some(item)));
+                            popped.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        let mut remaining = 0;
+        while let [Some(item), rest @ ..] = stack.kpop() {
+            assert!(rest.iter().all(|other| *other == Some(item)));
+            remaining += 1;
+        }
+        assert_eq!(
+            THREADS * WINDOWS,
+            popped.load(Ordering::Relaxed) + remaining
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use alloc::collections::vec_deque::{self, VecDeque};
+use core::iter::FromIterator;
+
+/// Double-ended sibling of [`KStack`](crate::KStack), with a window of K
+/// element at each end.
+///
+/// Windows are returned from the end inward, so the front window starts
+/// with the front element and the back window starts with the back
+/// element, like the top of a [`KStack`](crate::KStack). K must be at
+/// least 1.
+///
+/// ```compile_fail
+///     use kstack::KDeque;
+///
+///     let stack = KDeque::<i32, 0>::new();
+/// ```
+pub struct KDeque<T, const K: usize>(VecDeque<T>);
+
+impl<T, const K: usize> KDeque<T, K> {
+    const NONZERO_K: () = assert!(K > 0, "window size K of KDeque must be at least 1");
+
+    /// Make a new KDeque.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KDeque;
+    ///
+    ///     let mut deque = KDeque::<i32, 3>::new();
+    /// ```
+    pub fn new() -> Self {
+        let () = Self::NONZERO_K;
+        KDeque(VecDeque::new())
+    }
+
+    /// Make a new KDeque with room for at least `capacity` element.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let () = Self::NONZERO_K;
+        KDeque(VecDeque::with_capacity(capacity))
+    }
+
+    /// Number of element in deque.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether there is no element in deque.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Push a single element to the front of deque.
+    pub fn push_front(&mut self, item: T) {
+        self.0.push_front(item);
+    }
+
+    /// Push a single element to the back of deque.
+    pub fn push_back(&mut self, item: T) {
+        self.0.push_back(item);
+    }
+
+    /// Remove and get the front element of deque.
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.0.pop_front()
+    }
+
+    /// Remove and get the back element of deque.
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.0.pop_back()
+    }
+
+    /// Push element to the back of deque, in order.
+    pub fn kpush_back(&mut self, items: &[T])
+    where
+        T: Clone,
+    {
+        self.0.extend(items.iter().cloned());
+    }
+
+    /// Push element to the front of deque, in order, so the last one ends
+    /// up in front.
+    pub fn kpush_front(&mut self, items: &[T])
+    where
+        T: Clone,
+    {
+        items
+            .iter()
+            .cloned()
+            .for_each(|item| self.0.push_front(item));
+    }
+
+    /// Removes and return K front element of deque, front first.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KDeque;
+    ///
+    ///     let mut deque = KDeque::<i32, 2>::new();
+    ///
+    ///     deque.kpush_back(&[1, 2, 3]);
+    ///
+    ///     assert_eq!([Some(1), Some(2)], deque.kpop_front());
+    ///     assert_eq!([Some(3), None], deque.kpop_front());
+    /// ```
+    pub fn kpop_front(&mut self) -> [Option<T>; K] {
+        core::array::from_fn(|_| self.0.pop_front())
+    }
+
+    /// Removes and return K back element of deque, back first.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KDeque;
+    ///
+    ///     let mut deque = KDeque::<i32, 2>::new();
+    ///
+    ///     deque.kpush_back(&[1, 2, 3]);
+    ///
+    ///     assert_eq!([Some(3), Some(2)], deque.kpop_back());
+    ///     assert_eq!([Some(1), None], deque.kpop_back());
+    /// ```
+    pub fn kpop_back(&mut self) -> [Option<T>; K] {
+        core::array::from_fn(|_| self.0.pop_back())
+    }
+
+    /// return references to K front element of deque, front first.
+    pub fn kshow_front_ref(&self) -> [Option<&T>; K] {
+        let mut items = self.0.iter();
+        core::array::from_fn(|_| items.next())
+    }
+
+    /// return references to K back element of deque, back first.
+    pub fn kshow_back_ref(&self) -> [Option<&T>; K] {
+        let mut items = self.0.iter().rev();
+        core::array::from_fn(|_| items.next())
+    }
+
+    /// return K front element of deque, front first.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KDeque;
+    ///
+    ///     let mut deque = KDeque::<i32, 2>::new();
+    ///
+    ///     deque.kpush_back(&[1, 2, 3]);
+    ///
+    ///     assert_eq!([Some(1), Some(2)], deque.kshow_front());
+    /// ```
+    pub fn kshow_front(&self) -> [Option<T>; K]
+    where
+        T: Clone,
+    {
+        self.kshow_front_ref().map(|item| item.cloned())
+    }
+
+    /// return K back element of deque, back first.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KDeque;
+    ///
+    ///     let mut deque = KDeque::<i32, 2>::new();
+    ///
+    ///     deque.kpush_back(&[1, 2, 3]);
+    ///
+    ///     assert_eq!([Some(3), Some(2)], deque.kshow_back());
+    /// ```
+    pub fn kshow_back(&self) -> [Option<T>; K]
+    where
+        T: Clone,
+    {
+        self.kshow_back_ref().map(|item| item.cloned())
+    }
+
+    /// Iterate over element in deque, front first.
+    pub fn iter(&self) -> vec_deque::Iter<'_, T> {
+        self.0.iter()
+    }
+}
+
+impl<T, const K: usize> Default for KDeque<T, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Items are pushed to the back in iteration order.
+impl<T, const K: usize> FromIterator<T> for KDeque<T, K> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let () = Self::NONZERO_K;
+        KDeque(VecDeque::from_iter(iter))
+    }
+}
+
+/// Items are pushed to the back in iteration order.
+impl<T, const K: usize> Extend<T> for KDeque<T, K> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.0.extend(iter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_both_ends() {
+        let mut deque = KDeque::<i32, 2>::new();
+        deque.kpush_back(&[3, 4]);
+        deque.kpush_front(&[2, 1]);
+        deque.push_back(5);
+        deque.push_front(0);
+        assert_eq!(
+            vec![&0, &1, &2, &3, &4, &5],
+            deque.iter().collect::<Vec<_>>()
+        );
+
+        assert_eq!([Some(&0), Some(&1)], deque.kshow_front_ref());
+        assert_eq!([Some(&5), Some(&4)], deque.kshow_back_ref());
+        assert_eq!([Some(0), Some(1)], deque.kpop_front());
+        assert_eq!([Some(5), Some(4)], deque.kpop_back());
+        assert_eq!(Some(2), deque.pop_front());
+        assert_eq!(Some(3), deque.pop_back());
+        assert_eq!([None, None], deque.kshow_back());
+        assert!(deque.is_empty());
+    }
+
+    #[test]
+    fn test_sliding_window_max() {
+        // classic monotonic deque: back is trimmed while front holds max.
+        let values = [1, 3, -1, -3, 5, 3, 6, 7];
+        let mut deque = KDeque::<usize, 1>::new();
+        let mut maxima = Vec::new();
+        for (index, &value) in values.iter().enumerate() {
+            while matches!(deque.kshow_back(), [Some(back)] if values[back] <= value) {
+                deque.pop_back();
+            }
+            deque.push_back(index);
+            if matches!(deque.kshow_front(), [Some(front)] if front + 3 <= index) {
+                deque.pop_front();
+            }
+            if index >= 2 {
+                maxima.push(values[deque.kshow_front()[0].unwrap()]);
+            }
+        }
+        assert_eq!(vec![3, 3, 5, 5, 6, 7], maxima);
+    }
+}
@@ -0,0 +1,147 @@
+use alloc::vec::Vec;
+
+use crate::{KStack, Storage};
+
+/// Changes to K top element of a stack between two snapshots, returned by
+/// [`WindowTracker::kdiff`]. element are in stack order, the top last.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct WindowDiff<T> {
+    /// Element that are in window now but weren't before.
+    pub entered: Vec<T>,
+    /// Element that were in window before but aren't now.
+    pub left: Vec<T>,
+}
+
+impl<T> WindowDiff<T> {
+    /// Whether window didn't change.
+    pub fn is_empty(&self) -> bool {
+        self.entered.is_empty() && self.left.is_empty()
+    }
+}
+
+/// Remembers last observed window of a stack, to report what changed in it
+/// since with [`kdiff`](Self::kdiff).
+///
+/// Element are told apart by position on stack, so popping an element and
+/// pushing an equal one back is not a change.
+pub struct WindowTracker<T, const K: usize> {
+    /// Length of stack at last snapshot.
+    len: usize,
+    /// Window at last snapshot.
+    window: Vec<T>,
+}
+
+impl<T: Clone + PartialEq, const K: usize> WindowTracker<T, K> {
+    /// Make a new WindowTracker, that has seen an empty stack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::WindowTracker;
+    ///
+    ///     let mut tracker = WindowTracker::<i32, 3>::new();
+    /// ```
+    pub fn new() -> Self {
+        WindowTracker {
+            len: 0,
+            window: Vec::new(),
+        }
+    }
+
+    /// Compare window of `stack` to the last observed one and remember it
+    /// for next call.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::{KStack, WindowTracker};
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new();
+    ///     let mut tracker = WindowTracker::new();
+    ///
+    ///     stack.kpush(&[1, 2]);
+    ///     assert_eq!(vec![1, 2], tracker.kdiff(&stack).entered);
+    ///
+    ///     stack.push(3);
+    ///     let diff = tracker.kdiff(&stack);
+    ///     assert_eq!(vec![3], diff.entered);
+    ///     assert_eq!(vec![1], diff.left);
+    ///
+    ///     assert!(tracker.kdiff(&stack).is_empty());
+    /// ```
+    pub fn kdiff<S: Storage<T>>(&mut self, stack: &KStack<T, K, S>) -> WindowDiff<T> {
+        let window = stack.kshow_slice();
+        let len = stack.len();
+        let old_start = self.len - self.window.len();
+        let new_start = len - window.len();
+
+        // element at the same position in both windows, if unchanged, are
+        // not in the diff.
+        let stayed = |position: usize, old: &T| {
+            position >= new_start && position < len && window[position - new_start] == *old
+        };
+        let left = (old_start..)
+            .zip(&self.window)
+            .filter(|&(position, item)| !stayed(position, item))
+            .map(|(_, item)| item.clone())
+            .collect();
+        let entered = (new_start..)
+            .zip(window)
+            .filter(|&(position, item)| {
+                position < old_start
+                    || position >= self.len
+                    || self.window[position - old_start] != *item
+            })
+            .map(|(_, item)| item.clone())
+            .collect();
+
+        self.len = len;
+        self.window.clear();
+        self.window.extend_from_slice(window);
+        WindowDiff { entered, left }
+    }
+}
+
+impl<T: Clone + PartialEq, const K: usize> Default for WindowTracker<T, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diff(entered: &[i32], left: &[i32]) -> WindowDiff<i32> {
+        WindowDiff {
+            entered: entered.to_vec(),
+            left: left.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_kdiff() {
+        let mut stack = KStack::<i32, 3>::new();
+        let mut tracker = WindowTracker::default();
+        assert!(tracker.kdiff(&stack).is_empty());
+
+        stack.kpush(&[1, 2, 3, 4, 5]);
+        assert_eq!(diff(&[3, 4, 5], &[]), tracker.kdiff(&stack));
+
+        stack.pop();
+        assert_eq!(diff(&[2], &[5]), tracker.kdiff(&stack));
+
+        stack.kpop();
+        assert_eq!(diff(&[1], &[2, 3, 4]), tracker.kdiff(&stack));
+
+        stack.pop();
+        stack.push(9);
+        assert_eq!(diff(&[9], &[1]), tracker.kdiff(&stack));
+
+        stack.pop();
+        stack.push(9);
+        assert!(tracker.kdiff(&stack).is_empty());
+
+        stack[0] = 7;
+        stack.push(8);
+        assert_eq!(diff(&[7, 8], &[9]), tracker.kdiff(&stack));
+    }
+}
@@ -0,0 +1,90 @@
+//! Stack with a window size given at runtime, shared by the language
+//! bindings.
+//!
+//! C, JS and Python have no const generics, so bindings keep a
+//! [`KStack`] and take window size from their constructor. its K is
+//! unused, every window op goes through `k` instead.
+
+use alloc::vec::Vec;
+use core::iter::Rev;
+use core::slice;
+
+use crate::KStack;
+
+/// [`KStack`] with window size `k` chosen when it's made.
+pub(crate) struct DynKStack<T> {
+    stack: KStack<T, 1>,
+    k: usize,
+}
+
+impl<T> DynKStack<T> {
+    /// Make a new stack with window size `k`, or `None` if `k` is 0.
+    pub(crate) fn new(k: usize) -> Option<Self> {
+        if k == 0 {
+            return None;
+        }
+        Some(DynKStack {
+            stack: KStack::new(),
+            k,
+        })
+    }
+
+    /// Window size.
+    #[cfg(any(feature = "python", test))]
+    pub(crate) fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Number of element on stack.
+    pub(crate) fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Push a single element to stack.
+    pub(crate) fn push(&mut self, item: T) {
+        self.stack.push(item);
+    }
+
+    /// Remove and get single element on top of stack.
+    pub(crate) fn pop(&mut self) -> Option<T> {
+        self.stack.pop()
+    }
+
+    /// Push element in order, so the last one ends up on top.
+    pub(crate) fn kpush<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        self.stack.kpush_iter(items);
+    }
+
+    /// Removes and return window, or at most `cap` element of it, top
+    /// first.
+    pub(crate) fn kpop(&mut self, cap: usize) -> Vec<T> {
+        self.stack.kpop_n(self.k.min(cap))
+    }
+
+    /// Window, or at most `cap` element of it, top first.
+    pub(crate) fn kshow(&self, cap: usize) -> Rev<slice::Iter<'_, T>> {
+        self.stack.kshow_n(self.k.min(cap)).iter().rev()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dyn_kstack() {
+        assert!(DynKStack::<i32>::new(0).is_none());
+
+        let mut stack = DynKStack::new(2).unwrap();
+        assert_eq!(2, stack.k());
+        stack.kpush([1, 2, 3]);
+        stack.push(4);
+        assert_eq!(4, stack.len());
+        assert_eq!(vec![&4, &3], stack.kshow(usize::MAX).collect::<Vec<_>>());
+        assert_eq!(vec![&4], stack.kshow(1).collect::<Vec<_>>());
+        assert_eq!(Some(4), stack.pop());
+        assert_eq!(vec![3, 2], stack.kpop(usize::MAX));
+        assert_eq!(vec![1], stack.kpop(usize::MAX));
+        assert!(stack.kpop(usize::MAX).is_empty());
+    }
+}
@@ -0,0 +1,52 @@
+use core::fmt;
+
+/// Error returned by the checked stack operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KStackError {
+    /// There are not enough element on stack for the operation.
+    Underflow {
+        /// Number of element the operation needed.
+        requested: usize,
+        /// Number of element that was on stack.
+        available: usize,
+    },
+    /// Stack can't grow to hold the new element.
+    Overflow,
+}
+
+impl fmt::Display for KStackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KStackError::Underflow {
+                requested,
+                available,
+            } => write!(
+                f,
+                "stack underflow: requested {} element but only {} available",
+                requested, available
+            ),
+            KStackError::Overflow => write!(f, "stack overflow"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for KStackError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let error = KStackError::Underflow {
+            requested: 3,
+            available: 1,
+        };
+        assert_eq!(
+            "stack underflow: requested 3 element but only 1 available",
+            error.to_string()
+        );
+        assert_eq!("stack overflow", KStackError::Overflow.to_string());
+    }
+}
@@ -0,0 +1,163 @@
+//! Small RPN expression evaluator on top of [`KStack`].
+
+use alloc::string::{String, ToString};
+use core::fmt;
+
+use crate::{KStack, KStackError};
+
+/// Error returned when evaluating an RPN expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvalError {
+    /// Token is neither a number nor a known operator.
+    InvalidToken(String),
+    /// Operator didn't have enough operands.
+    Stack(KStackError),
+    /// Expression didn't leave exactly one value, it holds the number of
+    /// values left.
+    Unbalanced(usize),
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalError::InvalidToken(token) => write!(f, "invalid token `{}`", token),
+            EvalError::Stack(error) => write!(f, "{}", error),
+            EvalError::Unbalanced(count) => {
+                write!(f, "expression left {} values instead of one", count)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EvalError {}
+
+impl From<KStackError> for EvalError {
+    fn from(error: KStackError) -> Self {
+        EvalError::Stack(error)
+    }
+}
+
+/// RPN evaluator that can be fed one token at a time.
+///
+/// Numbers are pushed, and `+`, `-`, `*` and `/` pop two operands and push
+/// the result. the K = 2 window of its stack is the pair of operands the
+/// next operator would use.
+#[derive(Default)]
+pub struct Evaluator {
+    stack: KStack<f64, 2>,
+}
+
+impl Evaluator {
+    /// Make a new Evaluator.
+    pub fn new() -> Self {
+        Evaluator {
+            stack: KStack::new(),
+        }
+    }
+
+    /// Evaluate a single token.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::eval::Evaluator;
+    ///
+    ///     let mut evaluator = Evaluator::new();
+    ///
+    ///     evaluator.eval_token("3").unwrap();
+    ///     evaluator.eval_token("4").unwrap();
+    ///     assert_eq!([Some(4.0), Some(3.0)], evaluator.stack().kshow());
+    ///
+    ///     evaluator.eval_token("*").unwrap();
+    ///     assert_eq!(Ok(12.0), evaluator.finish());
+    /// ```
+    pub fn eval_token(&mut self, token: &str) -> Result<(), EvalError> {
+        let op: fn(f64, f64) -> f64 = match token {
+            "+" => |a, b| a + b,
+            "-" => |a, b| a - b,
+            "*" => |a, b| a * b,
+            "/" => |a, b| a / b,
+            _ => {
+                let number = token
+                    .parse()
+                    .map_err(|_| EvalError::InvalidToken(token.to_string()))?;
+                self.stack.push(number);
+                return Ok(());
+            }
+        };
+        self.stack.apply_binop(op)?;
+        Ok(())
+    }
+
+    /// Operand stack of evaluator.
+    pub fn stack(&self) -> &KStack<f64, 2> {
+        &self.stack
+    }
+
+    /// Consume evaluator and return the value of expression.
+    pub fn finish(mut self) -> Result<f64, EvalError> {
+        match self.stack.len() {
+            1 => Ok(self.stack.pop().unwrap()),
+            count => Err(EvalError::Unbalanced(count)),
+        }
+    }
+}
+
+/// Evaluate a whitespace separated RPN expression.
+///
+/// # Example
+/// ```
+///     use kstack::eval::{eval, EvalError};
+///
+///     assert_eq!(Ok(14.0), eval("5 1 2 + 4 * + 3 -"));
+///     assert_eq!(Err(EvalError::Unbalanced(2)), eval("1 2"));
+/// ```
+pub fn eval(expression: &str) -> Result<f64, EvalError> {
+    let mut evaluator = Evaluator::new();
+    for token in expression.split_whitespace() {
+        evaluator.eval_token(token)?;
+    }
+    evaluator.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval() {
+        assert_eq!(Ok(3.0), eval("1 2 +"));
+        assert_eq!(Ok(-1.0), eval("1 2 -"));
+        assert_eq!(Ok(0.5), eval("1 2 /"));
+        assert_eq!(Ok(2.5), eval("  0.5   5 *  "));
+        assert_eq!(Ok(14.0), eval("5 1 2 + 4 * + 3 -"));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(Err(EvalError::Unbalanced(0)), eval(""));
+        assert_eq!(Err(EvalError::Unbalanced(3)), eval("1 2 3"));
+        assert_eq!(Err(EvalError::InvalidToken("x".to_string())), eval("1 x +"));
+        assert_eq!(
+            Err(EvalError::Stack(KStackError::Underflow {
+                requested: 2,
+                available: 1
+            })),
+            eval("1 +")
+        );
+        assert_eq!(
+            "invalid token `x`",
+            EvalError::InvalidToken("x".to_string()).to_string()
+        );
+    }
+
+    #[test]
+    fn test_evaluator_window() {
+        let mut evaluator = Evaluator::new();
+        for token in ["1", "2", "3", "+"] {
+            evaluator.eval_token(token).unwrap();
+        }
+        assert_eq!(&[1.0, 5.0], evaluator.stack().kshow_slice());
+        assert_eq!(Err(EvalError::Unbalanced(2)), evaluator.finish());
+    }
+}
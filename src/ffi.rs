@@ -0,0 +1,221 @@
+//! C API, enabled by `ffi` feature.
+//!
+//! There is an opaque handle and a set of `kstack_<type>_*` functions for
+//! each of `u8`, `i64` and `f64`. window size is given to `new` at
+//! runtime. windows are written to caller buffers top first, like
+//! [`KStack::kpop`](crate::KStack::kpop).
+//!
+//! `include/kstack.h` declares the API, and can be regenerated with
+//! `cbindgen --config cbindgen.toml`. build the crate as a `staticlib`
+//! or `cdylib` to link it, for example with
+//! `cargo rustc --release --features ffi --crate-type staticlib`.
+
+use alloc::boxed::Box;
+use core::{ptr, slice};
+
+use crate::dynamic::DynKStack;
+
+macro_rules! ffi {
+    ($(
+        $t:ty => $handle:ident {
+            new: $new:ident,
+            free: $free:ident,
+            len: $len:ident,
+            push: $push:ident,
+            pop: $pop:ident,
+            kpush: $kpush:ident,
+            kpop: $kpop:ident,
+            kshow: $kshow:ident $(,)?
+        }
+    )*) => {$(
+        #[doc = concat!("Opaque handle of a stack of `", stringify!($t), "`.")]
+        pub struct $handle {
+            stack: DynKStack<$t>,
+        }
+
+        #[doc = concat!("Make a new stack with window size `k`, free it with [`", stringify!($free), "`].")]
+        ///
+        /// return null if `k` is 0.
+        #[no_mangle]
+        pub extern "C" fn $new(k: usize) -> *mut $handle {
+            match DynKStack::new(k) {
+                Some(stack) => Box::into_raw(Box::new($handle { stack })),
+                None => ptr::null_mut(),
+            }
+        }
+
+        /// Free a stack, does nothing if `stack` is null.
+        ///
+        /// # Safety
+        #[doc = concat!("`stack` must be null or returned by [`", stringify!($new), "`] and not freed.")]
+        #[no_mangle]
+        pub unsafe extern "C" fn $free(stack: *mut $handle) {
+            if !stack.is_null() {
+                drop(Box::from_raw(stack));
+            }
+        }
+
+        /// Number of element on stack.
+        ///
+        /// # Safety
+        /// `stack` must be a valid handle.
+        #[no_mangle]
+        pub unsafe extern "C" fn $len(stack: *const $handle) -> usize {
+            (*stack).stack.len()
+        }
+
+        /// Push a single element to stack.
+        ///
+        /// # Safety
+        /// `stack` must be a valid handle.
+        #[no_mangle]
+        pub unsafe extern "C" fn $push(stack: *mut $handle, item: $t) {
+            (*stack).stack.push(item);
+        }
+
+        /// Pop element on top of stack into `out` and return true, or
+        /// return false if stack is empty.
+        ///
+        /// # Safety
+        /// `stack` must be a valid handle and `out` valid for a write.
+        #[no_mangle]
+        pub unsafe extern "C" fn $pop(stack: *mut $handle, out: *mut $t) -> bool {
+            match (*stack).stack.pop() {
+                Some(item) => {
+                    out.write(item);
+                    true
+                }
+                None => false,
+            }
+        }
+
+        /// Push `len` element of `items` to stack in order, so the last one
+        /// ends up on top.
+        ///
+        /// # Safety
+        /// `stack` must be a valid handle and `items` valid for `len`
+        /// reads, it may be null if `len` is zero.
+        #[no_mangle]
+        pub unsafe extern "C" fn $kpush(stack: *mut $handle, items: *const $t, len: usize) {
+            if len > 0 {
+                (*stack).stack.kpush(slice::from_raw_parts(items, len).iter().copied());
+            }
+        }
+
+        /// Pop window, or `cap` element if less, into `out` top first and
+        /// return how many were written.
+        ///
+        /// # Safety
+        /// `stack` must be a valid handle and `out` valid for `cap` writes.
+        #[no_mangle]
+        pub unsafe extern "C" fn $kpop(stack: *mut $handle, out: *mut $t, cap: usize) -> usize {
+            let items = (*stack).stack.kpop(cap);
+            for (i, &item) in items.iter().enumerate() {
+                out.add(i).write(item);
+            }
+            items.len()
+        }
+
+        /// Copy window, or `cap` element if less, into `out` top first and
+        /// return how many were written.
+        ///
+        /// # Safety
+        /// `stack` must be a valid handle and `out` valid for `cap` writes.
+        #[no_mangle]
+        pub unsafe extern "C" fn $kshow(stack: *const $handle, out: *mut $t, cap: usize) -> usize {
+            let window = (*stack).stack.kshow(cap);
+            let count = window.len();
+            for (i, &item) in window.enumerate() {
+                out.add(i).write(item);
+            }
+            count
+        }
+    )*};
+}
+
+ffi! {
+    u8 => KStackU8 {
+        new: kstack_u8_new,
+        free: kstack_u8_free,
+        len: kstack_u8_len,
+        push: kstack_u8_push,
+        pop: kstack_u8_pop,
+        kpush: kstack_u8_kpush,
+        kpop: kstack_u8_kpop,
+        kshow: kstack_u8_kshow,
+    }
+    i64 => KStackI64 {
+        new: kstack_i64_new,
+        free: kstack_i64_free,
+        len: kstack_i64_len,
+        push: kstack_i64_push,
+        pop: kstack_i64_pop,
+        kpush: kstack_i64_kpush,
+        kpop: kstack_i64_kpop,
+        kshow: kstack_i64_kshow,
+    }
+    f64 => KStackF64 {
+        new: kstack_f64_new,
+        free: kstack_f64_free,
+        len: kstack_f64_len,
+        push: kstack_f64_push,
+        pop: kstack_f64_pop,
+        kpush: kstack_f64_kpush,
+        kpop: kstack_f64_kpop,
+        kshow: kstack_f64_kshow,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_i64() {
+        unsafe {
+            let stack = kstack_i64_new(2);
+            let mut out = [0i64; 3];
+            assert!(!kstack_i64_pop(stack, out.as_mut_ptr()));
+            assert_eq!(0, kstack_i64_kshow(stack, out.as_mut_ptr(), 3));
+
+            kstack_i64_kpush(stack, [1, 2, 3].as_ptr(), 3);
+            kstack_i64_kpush(stack, ptr::null(), 0);
+            kstack_i64_push(stack, 4);
+            assert_eq!(4, kstack_i64_len(stack));
+
+            assert_eq!(2, kstack_i64_kshow(stack, out.as_mut_ptr(), 3));
+            assert_eq!([4, 3, 0], out);
+            assert_eq!(1, kstack_i64_kpop(stack, out.as_mut_ptr(), 1));
+            assert_eq!([4, 3, 0], out);
+            assert_eq!(2, kstack_i64_kpop(stack, out.as_mut_ptr(), 3));
+            assert_eq!([3, 2, 0], out);
+
+            assert!(kstack_i64_pop(stack, out.as_mut_ptr()));
+            assert_eq!(1, out[0]);
+            assert_eq!(0, kstack_i64_len(stack));
+            kstack_i64_free(stack);
+            kstack_i64_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_u8_f64() {
+        unsafe {
+            let bytes = kstack_u8_new(3);
+            let mut out = [0u8; 3];
+            kstack_u8_kpush(bytes, b"kstack".as_ptr(), 6);
+            assert_eq!(3, kstack_u8_kpop(bytes, out.as_mut_ptr(), 3));
+            assert_eq!(*b"kca", out);
+            kstack_u8_free(bytes);
+
+            let floats = kstack_f64_new(1);
+            let mut out = 0.0;
+            kstack_f64_push(floats, 1.5);
+            assert_eq!(1, kstack_f64_kshow(floats, &mut out, 1));
+            assert_eq!(1.5, out);
+            kstack_f64_free(floats);
+
+            assert!(kstack_u8_new(0).is_null());
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use alloc::sync::Arc;
+use core::ops::Deref;
+
+use crate::{KStack, Storage};
+
+/// Immutable snapshot of a [`KStack`], returned by [`KStack::freeze`].
+///
+/// It derefs to a [`KStack`] for all the read-only operations, and
+/// cloning it only clones an [`Arc`], so it can be shared with readers on
+/// other threads while the original stack keeps changing.
+pub struct FrozenKStack<T, const K: usize>(Arc<KStack<T, K>>);
+
+impl<T, const K: usize> FrozenKStack<T, K> {
+    /// Copy element of snapshot to a new stack that can be changed.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let frozen = KStack::<i32, 2>::from(vec![1, 2]).freeze();
+    ///     let mut stack = frozen.to_kstack();
+    ///
+    ///     stack.push(3);
+    ///     assert_eq!(&[2, 3], stack.kshow_slice());
+    ///     assert_eq!(&[1, 2], frozen.kshow_slice());
+    /// ```
+    pub fn to_kstack(&self) -> KStack<T, K>
+    where
+        T: Clone,
+    {
+        KStack::from(self.0.as_slice().to_vec())
+    }
+}
+
+impl<T, const K: usize> Clone for FrozenKStack<T, K> {
+    fn clone(&self) -> Self {
+        FrozenKStack(Arc::clone(&self.0))
+    }
+}
+
+impl<T, const K: usize> Deref for FrozenKStack<T, K> {
+    type Target = KStack<T, K>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Frozen snapshots.
+impl<T: Clone, const K: usize, S: Storage<T>> KStack<T, K, S> {
+    /// Make an immutable snapshot of stack, that can be shared between
+    /// threads and doesn't change with the stack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///     use std::thread;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::from(vec![1, 2, 3]);
+    ///     let frozen = stack.freeze();
+    ///
+    ///     stack.kpop();
+    ///     let reader = {
+    ///         let frozen = frozen.clone();
+    ///         thread::spawn(move || frozen.iter().sum::<i32>())
+    ///     };
+    ///
+    ///     assert_eq!(6, reader.join().unwrap());
+    ///     assert_eq!([Some(3), Some(2)], frozen.kshow());
+    ///     assert_eq!(1, frozen[2]);
+    ///     assert_eq!(1, stack.len());
+    /// ```
+    pub fn freeze(&self) -> FrozenKStack<T, K> {
+        FrozenKStack(Arc::new(KStack::from(self.as_slice().to_vec())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_is_independent() {
+        let mut stack = KStack::<String, 2>::new();
+        stack.kpush_iter(["a", "b", "c"].iter().map(|s| s.to_string()));
+
+        let frozen = stack.freeze();
+        let shared = frozen.clone();
+        stack.clear();
+        stack.push("d".to_string());
+
+        assert_eq!(3, shared.len());
+        assert_eq!(["b", "c"], shared.kshow_slice());
+        assert_eq!(
+            vec!["c", "b", "a"],
+            frozen.iter().map(String::as_str).collect::<Vec<_>>()
+        );
+        assert_eq!(stack.freeze().as_slice(), ["d"]);
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{KStack, KWindow};
+
+/// Guard returned by [`KStack::kpop_guard`], holding K top element on
+/// stack until it's committed.
+///
+/// Element stay on stack, hidden behind the exclusive borrow, until
+/// [`commit`](Self::commit) removes them. dropping the guard without
+/// committing, even during a panic or an early return, leaves stack
+/// exactly as it was.
+pub struct KPopGuard<'a, T, const K: usize> {
+    stack: &'a mut KStack<T, K>,
+}
+
+impl<'a, T, const K: usize> KPopGuard<'a, T, K> {
+    pub(crate) fn new(stack: &'a mut KStack<T, K>) -> Self {
+        KPopGuard { stack }
+    }
+
+    /// View of the guarded element.
+    pub fn window(&self) -> KWindow<'_, T, K> {
+        self.stack.kshow_view()
+    }
+
+    /// Number of guarded element.
+    pub fn len(&self) -> usize {
+        self.stack.kshow_slice().len()
+    }
+
+    /// Whether there is no guarded element, which means stack is empty.
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    /// Remove and return the guarded element, in the same order as
+    /// [`KStack::kpop`].
+    pub fn commit(self) -> [Option<T>; K] {
+        self.stack.kpop()
+    }
+
+    /// Give up the guarded element, leaving them on stack. same as
+    /// dropping the guard.
+    pub fn rollback(self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::KStack;
+
+    #[test]
+    fn test_commit() {
+        let mut stack = KStack::<i32, 2>::new();
+        stack.kpush(&[1, 2, 3]);
+
+        let guard = stack.kpop_guard();
+        assert_eq!(2, guard.len());
+        assert_eq!(Some(&3), guard.window().get(0));
+        assert_eq!([Some(3), Some(2)], guard.commit());
+        assert_eq!(1, stack.len());
+    }
+
+    #[test]
+    fn test_rollback() {
+        let mut stack = KStack::<i32, 2>::new();
+        stack.kpush(&[1, 2, 3]);
+
+        stack.kpop_guard().rollback();
+        {
+            let guard = stack.kpop_guard();
+            assert!(!guard.is_empty());
+        }
+        assert_eq!(&[1, 2, 3], stack.kshow_n(3));
+    }
+
+    #[test]
+    fn test_early_return() {
+        fn parse(stack: &mut KStack<char, 2>) -> Option<[Option<char>; 2]> {
+            let guard = stack.kpop_guard();
+            if guard.window().get(1) != Some(&'(') {
+                return None;
+            }
+            Some(guard.commit())
+        }
+
+        let mut stack = KStack::<char, 2>::new();
+        stack.kpush(&['x', 'y']);
+        assert_eq!(None, parse(&mut stack));
+        assert_eq!(&['x', 'y'], stack.kshow_slice());
+
+        stack.kpush(&['(', 'a']);
+        assert_eq!(Some([Some('a'), Some('(')]), parse(&mut stack));
+        assert_eq!(&['x', 'y'], stack.kshow_slice());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_panic() {
+        let mut stack = KStack::<i32, 2>::new();
+        stack.kpush(&[1, 2, 3]);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _guard = stack.kpop_guard();
+            panic!("speculation failed");
+        }));
+        assert!(result.is_err());
+        assert_eq!(&[1, 2, 3], stack.kshow_n(3));
+    }
+}
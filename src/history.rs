@@ -0,0 +1,183 @@
+//! Undo/redo history built on two stacks.
+
+use crate::{KStack, KWindow};
+
+/// Records actions and supports undo and redo, with the K most recent
+/// actions available as a window, for example for a "recent actions" menu.
+///
+/// Recording a new action clears redo history, like most editors do.
+pub struct UndoManager<T, const K: usize> {
+    done: KStack<T, K>,
+    undone: KStack<T, K>,
+}
+
+impl<T, const K: usize> UndoManager<T, K> {
+    /// Make a new UndoManager.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::history::UndoManager;
+    ///
+    ///     let mut history = UndoManager::<&str, 3>::new();
+    /// ```
+    pub fn new() -> Self {
+        UndoManager {
+            done: KStack::new(),
+            undone: KStack::new(),
+        }
+    }
+
+    /// Record a new action, clearing redo history.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::history::UndoManager;
+    ///
+    ///     let mut history = UndoManager::<&str, 3>::new();
+    ///
+    ///     history.record("type a");
+    ///     history.undo();
+    ///     history.record("type b");
+    ///
+    ///     assert!(!history.can_redo());
+    ///     assert_eq!(Some(&"type b"), history.recent().get(0));
+    /// ```
+    pub fn record(&mut self, action: T) {
+        self.done.push(action);
+        self.undone.clear();
+    }
+
+    /// Undo most recent action and return it, so caller can revert it.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::history::UndoManager;
+    ///
+    ///     let mut history = UndoManager::<&str, 3>::new();
+    ///
+    ///     history.record("type a");
+    ///     history.record("type b");
+    ///
+    ///     assert_eq!(Some(&"type b"), history.undo());
+    ///     assert_eq!(Some(&"type a"), history.undo());
+    ///     assert_eq!(None, history.undo());
+    /// ```
+    pub fn undo(&mut self) -> Option<&T> {
+        let action = self.done.pop()?;
+        self.undone.push(action);
+        self.undone.kshow_view().get(0)
+    }
+
+    /// Redo most recently undone action and return it, so caller can
+    /// apply it again.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::history::UndoManager;
+    ///
+    ///     let mut history = UndoManager::<&str, 3>::new();
+    ///
+    ///     history.record("type a");
+    ///     history.undo();
+    ///
+    ///     assert_eq!(Some(&"type a"), history.redo());
+    ///     assert_eq!(None, history.redo());
+    /// ```
+    pub fn redo(&mut self) -> Option<&T> {
+        let action = self.undone.pop()?;
+        self.done.push(action);
+        self.done.kshow_view().get(0)
+    }
+
+    /// Whether there is an action to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.done.is_empty()
+    }
+
+    /// Whether there is an action to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    /// K most recent actions that can be undone, most recent first.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::history::UndoManager;
+    ///
+    ///     let mut history = UndoManager::<&str, 2>::new();
+    ///
+    ///     history.record("a");
+    ///     history.record("b");
+    ///     history.record("c");
+    ///
+    ///     let recent: Vec<_> = history.recent().iter().collect();
+    ///     assert_eq!(vec![&"c", &"b"], recent);
+    /// ```
+    pub fn recent(&self) -> KWindow<'_, T, K> {
+        self.done.kshow_view()
+    }
+
+    /// K most recently undone actions that can be redone, the next one to
+    /// redo first.
+    pub fn recent_undone(&self) -> KWindow<'_, T, K> {
+        self.undone.kshow_view()
+    }
+
+    /// Forget all actions.
+    pub fn clear(&mut self) {
+        self.done.clear();
+        self.undone.clear();
+    }
+}
+
+impl<T, const K: usize> Default for UndoManager<T, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undo_redo() {
+        let mut history = UndoManager::<u32, 2>::new();
+        assert!(!history.can_undo());
+        assert!(!history.can_redo());
+
+        for action in 1..=4 {
+            history.record(action);
+        }
+        assert_eq!(Some(&4), history.undo());
+        assert_eq!(Some(&3), history.undo());
+        assert!(history.can_redo());
+        assert_eq!(vec![&2, &1], history.recent().iter().collect::<Vec<_>>());
+        assert_eq!(
+            vec![&3, &4],
+            history.recent_undone().iter().collect::<Vec<_>>()
+        );
+
+        assert_eq!(Some(&3), history.redo());
+        assert_eq!(vec![&3, &2], history.recent().iter().collect::<Vec<_>>());
+
+        history.record(5);
+        assert!(!history.can_redo());
+        assert_eq!(None, history.redo());
+        assert_eq!(vec![&5, &3], history.recent().iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut history = UndoManager::<u32, 2>::new();
+        history.record(1);
+        history.record(2);
+        history.undo();
+        history.clear();
+
+        assert!(!history.can_undo());
+        assert!(!history.can_redo());
+        assert!(history.recent().is_empty());
+    }
+}
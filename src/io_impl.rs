@@ -0,0 +1,253 @@
+use std::io::{self, BufRead, Read, Write};
+
+use crate::{CappedKStack, KStack, OverflowPolicy, Storage};
+
+/// Bytes are pushed in order, so the last one ends up on top.
+///
+/// # Example
+/// ```
+///     use std::io::{Read, Write};
+///     use kstack::KStack;
+///
+///     let mut stack = KStack::<u8, 4>::new();
+///     let mut buffer = [0; 3];
+///
+///     write!(stack, "abcd").unwrap();
+///
+///     assert_eq!(3, stack.read(&mut buffer).unwrap());
+///     assert_eq!(b"dcb", &buffer);
+/// ```
+impl<const K: usize, S: Storage<u8>> Write for KStack<u8, K, S> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.kpush(buf);
+        Ok(buf.len())
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.kpush(buf);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Bytes are popped top first, so they come out in reverse of how they
+/// were written. reads aren't limited to K bytes.
+impl<const K: usize, S: Storage<u8>> Read for KStack<u8, K, S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let count = buf.len().min(self.len());
+        let top = self.kshow_n(count);
+        for (slot, &byte) in buf.iter_mut().zip(top.iter().rev()) {
+            *slot = byte;
+        }
+        self.truncate_to_depth(self.len() - count);
+        Ok(count)
+    }
+}
+
+/// Loading from readers.
+impl<const K: usize, S: Storage<String>> KStack<String, K, S> {
+    /// Make a stack of lines of `reader`, without line endings, so the last
+    /// line ends up on top.
+    ///
+    /// Every line is kept, so memory grows with the whole input. use
+    /// [`CappedKStack::from_reader`] or [`LogTail`](crate::tail::LogTail)
+    /// to keep only the last lines.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let log = "start\nwarn\nstop\n";
+    ///     let stack = KStack::<String, 2>::from_reader(log.as_bytes()).unwrap();
+    ///
+    ///     assert_eq!(["warn", "stop"], stack.kshow_slice());
+    ///     assert_eq!(3, stack.len());
+    /// ```
+    pub fn from_reader<R: BufRead>(reader: R) -> io::Result<Self>
+    where
+        S: Default,
+    {
+        let mut stack = Self::default();
+        stack.push_lines(reader)?;
+        Ok(stack)
+    }
+
+    /// Push each line of `reader`, without line endings, and return how
+    /// many were pushed. lines read before an error stay on stack. like
+    /// [`from_reader`](Self::from_reader), every line is kept.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<String, 2>::new();
+    ///
+    ///     assert_eq!(3, stack.push_lines("a\r\nb\nc".as_bytes()).unwrap());
+    ///     assert_eq!(["b", "c"], stack.kshow_slice());
+    /// ```
+    pub fn push_lines<R: BufRead>(&mut self, reader: R) -> io::Result<usize> {
+        let mut count = 0;
+        for line in reader.lines() {
+            self.push(line?);
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+/// Loading from readers, keeping at most max depth lines.
+impl<const K: usize> CappedKStack<String, K> {
+    /// Make a stack of the last `max_depth` lines of `reader`, without line
+    /// endings. older lines are evicted as they are read, so memory stays
+    /// bounded however long the input is.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::CappedKStack;
+    ///
+    ///     let log = "start\nwarn\nstop\n";
+    ///     let stack = CappedKStack::<String, 2>::from_reader(log.as_bytes(), 2).unwrap();
+    ///
+    ///     assert_eq!(["warn", "stop"], stack.kshow_slice());
+    ///     assert_eq!(2, stack.len());
+    /// ```
+    pub fn from_reader<R: BufRead>(reader: R, max_depth: usize) -> io::Result<Self> {
+        let mut stack = CappedKStack::new(max_depth, OverflowPolicy::Evict);
+        stack.push_lines(reader)?;
+        Ok(stack)
+    }
+
+    /// Push each line of `reader` by policy, without line endings, and
+    /// return how many were read. lines read before an error stay on stack.
+    ///
+    /// # Errors
+    /// Returns an error of kind [`io::ErrorKind::Other`] wrapping
+    /// [`KStackError::Overflow`](crate::KStackError::Overflow) if a line
+    /// doesn't fit and policy is [`OverflowPolicy::Reject`].
+    ///
+    /// # Panics
+    /// Panics if a line doesn't fit and policy is [`OverflowPolicy::Panic`].
+    pub fn push_lines<R: BufRead>(&mut self, reader: R) -> io::Result<usize> {
+        let mut count = 0;
+        for line in reader.lines() {
+            self.push(line?)
+                .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+/// Loading from readers.
+impl<const N: usize, const K: usize, S: Storage<[u8; N]>> KStack<[u8; N], K, S> {
+    /// Push each `N` byte record of `reader` and return how many were
+    /// pushed. records read before an error stay on stack.
+    ///
+    /// # Errors
+    /// Returns an error of kind [`io::ErrorKind::UnexpectedEof`] if
+    /// `reader` ends in the middle of a record.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<[u8; 2], 2>::new();
+    ///
+    ///     assert_eq!(3, stack.push_records(&b"aabbcc"[..]).unwrap());
+    ///     assert_eq!([*b"bb", *b"cc"], stack.kshow_slice());
+    /// ```
+    pub fn push_records<R: Read>(&mut self, mut reader: R) -> io::Result<usize> {
+        let mut count = 0;
+        loop {
+            let mut record = [0; N];
+            let mut filled = 0;
+            while filled < N {
+                match reader.read(&mut record[filled..]) {
+                    Ok(0) if filled == 0 => return Ok(count),
+                    Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                    Ok(read) => filled += read,
+                    Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                    Err(error) => return Err(error),
+                }
+            }
+            self.push(record);
+            count += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_write() {
+        let mut stack = KStack::<u8, 2>::new();
+        let mut buffer = Vec::new();
+        assert_eq!(0, stack.read_to_end(&mut buffer).unwrap());
+
+        stack.write_all(b"hello").unwrap();
+        assert_eq!(3, stack.write(b" yo").unwrap());
+        stack.flush().unwrap();
+        assert_eq!(b"yo", stack.kshow_slice());
+
+        let mut head = [0; 4];
+        stack.read_exact(&mut head).unwrap();
+        assert_eq!(b"oy o", &head);
+        stack.read_to_end(&mut buffer).unwrap();
+        assert_eq!(b"lleh", &buffer[..]);
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn test_copy() {
+        let mut stack = KStack::<u8, 2>::new();
+        io::copy(&mut &b"abc"[..], &mut stack).unwrap();
+        let mut reversed = Vec::new();
+        io::copy(&mut stack, &mut reversed).unwrap();
+        assert_eq!(b"cba", &reversed[..]);
+    }
+
+    #[test]
+    fn test_push_lines() {
+        let mut stack = KStack::<String, 2>::from_reader(&b""[..]).unwrap();
+        assert!(stack.is_empty());
+
+        assert_eq!(2, stack.push_lines(&b"one\n\n"[..]).unwrap());
+        assert_eq!(1, stack.push_lines(&b"three"[..]).unwrap());
+        assert_eq!(vec!["three", "", "one"], stack.iter().collect::<Vec<_>>());
+
+        let error = stack.push_lines(&b"four\n\xff\n"[..]).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, error.kind());
+        assert_eq!(Some(&"four".to_string()), stack.peek());
+    }
+
+    #[test]
+    fn test_capped_push_lines() {
+        let stack = CappedKStack::<String, 1>::from_reader(&b"a\nb\nc\nd"[..], 2).unwrap();
+        assert_eq!(vec!["d", "c"], stack.as_kstack().iter().collect::<Vec<_>>());
+        assert!(stack.as_kstack().capacity() < 16);
+
+        let mut stack = CappedKStack::<String, 1>::new(1, OverflowPolicy::Reject);
+        let error = stack.push_lines(&b"a\nb\n"[..]).unwrap_err();
+        assert_eq!(io::ErrorKind::Other, error.kind());
+        assert_eq!(["a"], stack.kshow_slice());
+    }
+
+    #[test]
+    fn test_push_records() {
+        let mut stack = KStack::<[u8; 3], 2>::new();
+        assert_eq!(0, stack.push_records(&b""[..]).unwrap());
+
+        let reader = io::Read::chain(&b"ab"[..], &b"cdef"[..]);
+        assert_eq!(2, stack.push_records(reader).unwrap());
+        assert_eq!([*b"abc", *b"def"], stack.kshow_slice());
+
+        let error = stack.push_records(&b"ghij"[..]).unwrap_err();
+        assert_eq!(io::ErrorKind::UnexpectedEof, error.kind());
+        assert_eq!(Some(b"ghi"), stack.peek());
+    }
+}
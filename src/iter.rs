@@ -0,0 +1,166 @@
+use alloc::vec::{self, Vec};
+use core::iter::{FusedIterator, Rev};
+use core::slice;
+
+use crate::{KStack, Storage};
+
+/// Iterator over stack items from top to bottom, returned by
+/// [`KStack::iter`](crate::KStack::iter).
+#[derive(Debug, Clone)]
+pub struct Iter<'a, T>(pub(crate) Rev<slice::Iter<'a, T>>);
+
+/// Mutable iterator over stack items from top to bottom, returned by
+/// [`KStack::iter_mut`](crate::KStack::iter_mut).
+#[derive(Debug)]
+pub struct IterMut<'a, T>(pub(crate) Rev<slice::IterMut<'a, T>>);
+
+/// Owning iterator over stack items from top to bottom, returned by
+/// `KStack::into_iter`.
+#[derive(Debug)]
+pub struct IntoIter<T>(pub(crate) Rev<vec::IntoIter<T>>);
+
+/// Iterator over overlapping windows of K element from top to bottom,
+/// returned by [`KStack::iter_windows`](crate::KStack::iter_windows).
+#[derive(Debug, Clone)]
+pub struct Windows<'a, T>(pub(crate) Rev<slice::Windows<'a, T>>);
+
+/// Iterator over non-overlapping chunks of K element from top to bottom,
+/// returned by [`KStack::kchunks`](crate::KStack::kchunks).
+///
+/// The bottom element that don't fill a whole chunk are never yielded,
+/// they can be retrieved with [`remainder`](Self::remainder).
+#[derive(Debug, Clone)]
+pub struct KChunks<'a, T>(pub(crate) slice::RChunksExact<'a, T>);
+
+impl<'a, T> KChunks<'a, T> {
+    /// Returns the bottom element that don't fill a whole chunk, in stack
+    /// order.
+    pub fn remainder(&self) -> &'a [T] {
+        self.0.remainder()
+    }
+}
+
+/// Owning iterator over non-overlapping chunks of K element from top to
+/// bottom, returned by [`KStack::into_kchunks`](crate::KStack::into_kchunks).
+///
+/// Each chunk is in the same order as [`KStack::kpop`](crate::KStack::kpop).
+/// The bottom element that don't fill a whole chunk are never yielded,
+/// they can be retrieved with [`remainder`](Self::remainder) or
+/// [`into_remainder`](Self::into_remainder).
+#[derive(Debug, Clone)]
+pub struct IntoKChunks<T, const K: usize> {
+    pub(crate) items: Vec<T>,
+    pub(crate) remainder: usize,
+}
+
+impl<T, const K: usize> IntoKChunks<T, K> {
+    pub(crate) fn new(items: Vec<T>) -> Self {
+        let remainder = items.len() % K;
+        IntoKChunks { items, remainder }
+    }
+
+    /// Returns the bottom element that don't fill a whole chunk, in stack
+    /// order.
+    pub fn remainder(&self) -> &[T] {
+        &self.items[..self.remainder]
+    }
+
+    /// Consumes the iterator and returns the bottom element that don't
+    /// fill a whole chunk, in stack order. chunks not yet yielded are
+    /// dropped.
+    pub fn into_remainder(mut self) -> Vec<T> {
+        self.items.truncate(self.remainder);
+        self.items
+    }
+}
+
+impl<T, const K: usize> Iterator for IntoKChunks<T, K> {
+    type Item = [T; K];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = self.items.len();
+        if len < self.remainder + K {
+            return None;
+        }
+
+        let mut items = self.items.drain(len - K..).rev();
+        Some(core::array::from_fn(|_| items.next().unwrap()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = (self.items.len() - self.remainder) / K;
+        (count, Some(count))
+    }
+}
+
+impl<T, const K: usize> ExactSizeIterator for IntoKChunks<T, K> {}
+
+impl<T, const K: usize> FusedIterator for IntoKChunks<T, K> {}
+
+/// Iterator removing top element of a stack and yielding them from top to
+/// bottom, returned by [`KStack::drain_top`](crate::KStack::drain_top).
+///
+/// element not yet yielded are removed when it's dropped.
+pub struct DrainTop<'a, T, const K: usize, S: Storage<T> = Vec<T>> {
+    pub(crate) stack: &'a mut KStack<T, K, S>,
+    pub(crate) remaining: usize,
+}
+
+impl<T, const K: usize, S: Storage<T>> Iterator for DrainTop<'_, T, K, S> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        self.stack.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T, const K: usize, S: Storage<T>> ExactSizeIterator for DrainTop<'_, T, K, S> {}
+
+impl<T, const K: usize, S: Storage<T>> FusedIterator for DrainTop<'_, T, K, S> {}
+
+impl<T, const K: usize, S: Storage<T>> Drop for DrainTop<'_, T, K, S> {
+    fn drop(&mut self) {
+        let len = self.stack.len();
+        self.stack.truncate_to_depth(len - self.remaining);
+    }
+}
+
+macro_rules! impl_iterator {
+    ($name:ident<$($lt:lifetime,)? $t:ident>, $item:ty) => {
+        impl<$($lt,)? $t> Iterator for $name<$($lt,)? $t> {
+            type Item = $item;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                self.0.next()
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.0.size_hint()
+            }
+        }
+
+        impl<$($lt,)? $t> DoubleEndedIterator for $name<$($lt,)? $t> {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                self.0.next_back()
+            }
+        }
+
+        impl<$($lt,)? $t> ExactSizeIterator for $name<$($lt,)? $t> {}
+
+        impl<$($lt,)? $t> FusedIterator for $name<$($lt,)? $t> {}
+    };
+}
+
+impl_iterator!(Iter<'a, T>, &'a T);
+impl_iterator!(IterMut<'a, T>, &'a mut T);
+impl_iterator!(IntoIter<T>, T);
+impl_iterator!(Windows<'a, T>, &'a [T]);
+impl_iterator!(KChunks<'a, T>, &'a [T]);
@@ -0,0 +1,286 @@
+//! Append-only journal of stack operations, enabled by `journal` feature.
+//!
+//! A journal is the magic bytes `KJNL`, a format version byte, K as a
+//! little-endian `u64`, then a record for each operation: a tag byte and,
+//! for pushes, element encoded by `bincode`. [`replay`] reads it back into
+//! the stack it describes.
+
+use std::io::{self, Read, Write};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::KStack;
+
+const MAGIC: [u8; 4] = *b"KJNL";
+const VERSION: u8 = 1;
+
+const PUSH: u8 = 0;
+const POP: u8 = 1;
+const KPUSH: u8 = 2;
+const KPOP: u8 = 3;
+
+/// Operation on a stack, as recorded by [`Journal`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Op<T> {
+    /// [`KStack::push`] of an element.
+    Push(T),
+    /// [`KStack::pop`].
+    Pop,
+    /// [`KStack::kpush`] of element, in order.
+    KPush(Vec<T>),
+    /// [`KStack::kpop`].
+    KPop,
+}
+
+impl<T> Op<T> {
+    /// Do operation on `stack`.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::journal::Op;
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new();
+    ///
+    ///     Op::KPush(vec![1, 2, 3]).apply(&mut stack);
+    ///     Op::Pop.apply(&mut stack);
+    ///
+    ///     assert_eq!(&[1, 2], stack.kshow_slice());
+    /// ```
+    pub fn apply<const K: usize>(self, stack: &mut KStack<T, K>) {
+        match self {
+            Op::Push(item) => stack.push(item),
+            Op::Pop => {
+                stack.pop();
+            }
+            Op::KPush(items) => stack.kpush_iter(items),
+            Op::KPop => {
+                stack.kpop();
+            }
+        }
+    }
+
+    /// Write operation as a journal record.
+    pub fn write_to<W: Write>(&self, writer: W) -> io::Result<()>
+    where
+        T: Serialize,
+    {
+        match self {
+            Op::Push(item) => write_record(writer, PUSH, Some(item)),
+            Op::Pop => write_record::<T, _>(writer, POP, None),
+            Op::KPush(items) => write_record(writer, KPUSH, Some(items)),
+            Op::KPop => write_record::<T, _>(writer, KPOP, None),
+        }
+    }
+
+    /// Read a journal record, or `None` if `reader` is at its end.
+    pub fn read_from<R: Read>(mut reader: R) -> io::Result<Option<Self>>
+    where
+        T: DeserializeOwned,
+    {
+        let mut tag = [0];
+        loop {
+            match reader.read(&mut tag) {
+                Ok(0) => return Ok(None),
+                Ok(_) => break,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return Err(error),
+            }
+        }
+        let op = match tag[0] {
+            PUSH => Op::Push(decode(reader)?),
+            POP => Op::Pop,
+            KPUSH => Op::KPush(decode(reader)?),
+            KPOP => Op::KPop,
+            _ => return Err(invalid_data("unknown journal record")),
+        };
+        Ok(Some(op))
+    }
+}
+
+/// [`KStack`] that records every push and pop to a writer before doing
+/// it, so the stack can be rebuilt with [`replay`].
+pub struct Journal<T, const K: usize, W: Write> {
+    stack: KStack<T, K>,
+    writer: W,
+}
+
+impl<T: Serialize, const K: usize, W: Write> Journal<T, K, W> {
+    /// Make a new empty stack journaling to `writer`, writing journal
+    /// header to it.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::journal::{self, Journal};
+    ///
+    ///     let mut journal = Journal::<i32, 2, _>::new(Vec::new()).unwrap();
+    ///
+    ///     journal.kpush(&[1, 2, 3]).unwrap();
+    ///     journal.pop().unwrap();
+    ///
+    ///     let (stack, log) = journal.into_parts();
+    ///     assert_eq!(stack, journal::replay(&log[..]).unwrap());
+    /// ```
+    pub fn new(mut writer: W) -> io::Result<Self> {
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&[VERSION])?;
+        writer.write_all(&(K as u64).to_le_bytes())?;
+        Ok(Journal {
+            stack: KStack::new(),
+            writer,
+        })
+    }
+
+    /// Get underlying stack, for all the read-only operations.
+    pub fn as_kstack(&self) -> &KStack<T, K> {
+        &self.stack
+    }
+
+    /// Consume and return underlying stack and writer.
+    pub fn into_parts(self) -> (KStack<T, K>, W) {
+        (self.stack, self.writer)
+    }
+
+    /// Flush writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Push a single element to stack.
+    pub fn push(&mut self, item: T) -> io::Result<()> {
+        self.record(PUSH, Some(&item))?;
+        self.stack.push(item);
+        Ok(())
+    }
+
+    /// Remove and get single element on top of stack.
+    pub fn pop(&mut self) -> io::Result<Option<T>> {
+        self.record::<T>(POP, None)?;
+        Ok(self.stack.pop())
+    }
+
+    /// Push K element to stack.
+    pub fn kpush(&mut self, items: &[T]) -> io::Result<()>
+    where
+        T: Clone,
+    {
+        self.record(KPUSH, Some(items))?;
+        self.stack.kpush(items);
+        Ok(())
+    }
+
+    /// Removes and return K top element on stack.
+    pub fn kpop(&mut self) -> io::Result<[Option<T>; K]> {
+        self.record::<T>(KPOP, None)?;
+        Ok(self.stack.kpop())
+    }
+
+    fn record<P: Serialize + ?Sized>(&mut self, tag: u8, payload: Option<&P>) -> io::Result<()> {
+        write_record(&mut self.writer, tag, payload)
+    }
+}
+
+/// Rebuild a stack from a journal written by [`Journal`], reading `reader`
+/// to the end.
+///
+/// # Errors
+/// Returns an error of kind [`io::ErrorKind::InvalidData`] if `reader`
+/// isn't a journal of a stack with this K, or
+/// [`io::ErrorKind::UnexpectedEof`] if its last record is cut short.
+pub fn replay<T: DeserializeOwned, const K: usize, R: Read>(
+    mut reader: R,
+) -> io::Result<KStack<T, K>> {
+    let mut header = [0; 13];
+    reader.read_exact(&mut header)?;
+    let mut k = [0; 8];
+    k.copy_from_slice(&header[5..]);
+    if header[..4] != MAGIC || header[4] != VERSION || u64::from_le_bytes(k) != K as u64 {
+        return Err(invalid_data("not a journal of this kind of stack"));
+    }
+
+    let mut stack = KStack::new();
+    while let Some(op) = Op::read_from(&mut reader)? {
+        op.apply(&mut stack);
+    }
+    Ok(stack)
+}
+
+fn write_record<P: Serialize + ?Sized, W: Write>(
+    mut writer: W,
+    tag: u8,
+    payload: Option<&P>,
+) -> io::Result<()> {
+    writer.write_all(&[tag])?;
+    if let Some(payload) = payload {
+        encode(writer, payload)?;
+    }
+    Ok(())
+}
+
+/// Decode a value encoded by `bincode`.
+pub(crate) fn decode<T: DeserializeOwned, R: Read>(reader: R) -> io::Result<T> {
+    bincode::deserialize_from(reader).map_err(|error| into_io(*error))
+}
+
+/// Encode a value with `bincode`.
+pub(crate) fn encode<T: Serialize + ?Sized, W: Write>(writer: W, value: &T) -> io::Result<()> {
+    bincode::serialize_into(writer, value).map_err(|error| into_io(*error))
+}
+
+pub(crate) fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn into_io(error: bincode::ErrorKind) -> io::Error {
+    match error {
+        bincode::ErrorKind::Io(error) => error,
+        error => io::Error::new(io::ErrorKind::InvalidData, error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replay() {
+        let mut journal = Journal::<String, 2, _>::new(Vec::new()).unwrap();
+        assert_eq!(None, journal.pop().unwrap());
+        journal.push("a".to_string()).unwrap();
+        journal
+            .kpush(&["b".to_string(), "c".to_string(), "d".to_string()])
+            .unwrap();
+        assert_eq!(
+            [Some("d".to_string()), Some("c".to_string())],
+            journal.kpop().unwrap()
+        );
+        journal.push("e".to_string()).unwrap();
+        journal.flush().unwrap();
+        assert_eq!(3, journal.as_kstack().len());
+
+        let (stack, log) = journal.into_parts();
+        let replayed: KStack<String, 2> = replay(&log[..]).unwrap();
+        assert_eq!(stack, replayed);
+
+        let empty = Journal::<String, 2, _>::new(Vec::new()).unwrap();
+        let replayed: KStack<String, 2> = replay(&empty.into_parts().1[..]).unwrap();
+        assert!(replayed.is_empty());
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut journal = Journal::<u32, 2, _>::new(Vec::new()).unwrap();
+        journal.push(7).unwrap();
+        let mut log = journal.into_parts().1;
+
+        let error = replay::<u32, 3, _>(&log[..]).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, error.kind());
+        let error = replay::<u32, 2, _>(&log[..log.len() - 1]).unwrap_err();
+        assert_eq!(io::ErrorKind::UnexpectedEof, error.kind());
+
+        log.push(9);
+        let error = replay::<u32, 2, _>(&log[..]).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, error.kind());
+    }
+}
@@ -1,4 +1,132 @@
-#![feature(const_generics)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+mod array;
+#[cfg(feature = "std")]
+pub mod async_stack;
+mod builder;
+mod capped;
+#[cfg(feature = "std")]
+pub mod channel;
+#[cfg(feature = "concurrent")]
+pub mod concurrent;
+mod deque;
+mod diff;
+#[cfg(any(feature = "ffi", feature = "python", feature = "wasm"))]
+mod dynamic;
+mod error;
+pub mod eval;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod frozen;
+mod guard;
+pub mod history;
+#[cfg(feature = "std")]
+mod io_impl;
+mod iter;
+#[cfg(feature = "journal")]
+pub mod journal;
+mod macros;
+mod minmax;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+mod monotonic;
+mod nonempty;
+#[cfg(feature = "num-traits")]
+mod num;
+mod observer;
+mod pattern;
+pub mod persistent;
+#[cfg(feature = "std")]
+mod poison;
+#[cfg(feature = "pool")]
+pub mod pool;
+mod priority;
+#[cfg(feature = "python")]
+pub mod python;
+mod queue;
+#[cfg(feature = "rayon")]
+mod rayon_impl;
+#[cfg(feature = "registry")]
+pub mod registry;
+#[cfg(feature = "replication")]
+pub mod replication;
+pub mod rollback;
+#[cfg(feature = "num-traits")]
+mod running;
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(feature = "std")]
+pub mod sharded;
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
+#[cfg(feature = "spill")]
+pub mod spill;
+#[cfg(feature = "stats")]
+mod stats;
+#[cfg(feature = "std")]
+pub mod steal;
+mod storage;
+#[cfg(feature = "std")]
+pub mod sync;
+mod tagged;
+pub mod tail;
+#[cfg(feature = "std")]
+mod timed;
+mod top;
+mod transaction;
+pub mod vm;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+mod watermark;
+mod window;
+
+pub use array::{ArrayKStack, BoundedKStack};
+pub use builder::{CappedKStackBuilder, KStackBuilder};
+pub use capped::{CappedKStack, OverflowPolicy};
+pub use deque::KDeque;
+pub use diff::{WindowDiff, WindowTracker};
+pub use error::KStackError;
+pub use frozen::FrozenKStack;
+pub use guard::KPopGuard;
+pub use iter::{DrainTop, IntoIter, IntoKChunks, Iter, IterMut, KChunks, Windows};
+pub use minmax::MinMaxKStack;
+pub use monotonic::{MonotonicKStack, MonotonicOrder};
+pub use nonempty::NonEmptyKStack;
+pub use observer::{ObservedKStack, StackObserver};
+pub use pattern::Pattern;
+pub use priority::PriorityKStack;
+pub use queue::KQueue;
+#[cfg(feature = "num-traits")]
+pub use running::RunningKStack;
+#[cfg(feature = "stats")]
+pub use stats::KStackStats;
+#[cfg(feature = "smallvec")]
+pub use storage::SmallKStack;
+pub use storage::{CowKStack, CowStorage, RingStorage, Storage};
+pub use tagged::TaggedKStack;
+#[cfg(feature = "std")]
+pub use timed::TimedKStack;
+pub use top::KTop;
+pub use transaction::Transaction;
+pub use watermark::{WatermarkEvent, Watermarks};
+pub use window::{KWindow, RawKWindow};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::vec;
+}
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::{self, Write as _};
+use core::hash::{Hash, Hasher};
+use core::iter::FromIterator;
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use core::ops::{Index, IndexMut};
 
 /// Stack with a windows view of K top items.
 /// its like an normal stack and have `push` and `pop`
@@ -7,22 +135,373 @@
 ///
 /// The K defines window size, so you can see, push or pop K
 /// top element on top of stack.
-pub struct KStack<T, const K: usize>(Vec<T>);
+///
+/// `S` is where element are stored, a [`Vec`] by default, see
+/// [`Storage`].
+///
+/// K must be at least 1, making a stack with an empty window fails to
+/// compile.
+///
+/// ```compile_fail
+///     use kstack::KStack;
+///
+///     let stack = KStack::<i32, 0>::new();
+/// ```
+pub struct KStack<T, const K: usize, S = Vec<T>>(
+    S,
+    PhantomData<T>,
+    #[cfg(feature = "stats")] KStackStats,
+);
+
+impl<T, const K: usize> KStack<T, K> {
+    /// Make a new KStack, it's a `const fn` so stack can be a `static`.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///     use std::sync::Mutex;
+    ///
+    ///     static STACK: Mutex<KStack<i32, 3>> = Mutex::new(KStack::new());
+    ///
+    ///     STACK.lock().unwrap().push(1);
+    ///
+    ///     assert_eq!(Some(1), STACK.lock().unwrap().pop());
+    /// ```
+    pub const fn new() -> Self {
+        let () = Self::NONZERO_K;
+        KStack(
+            Vec::new(),
+            PhantomData,
+            #[cfg(feature = "stats")]
+            KStackStats::new(0),
+        )
+    }
+
+    /// Make a new KStack with room for at least `capacity` element
+    /// without reallocating.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let stack = KStack::<i32, 3>::with_capacity(10);
+    ///
+    ///     assert!(stack.capacity() >= 10);
+    ///     assert!(stack.is_empty());
+    /// ```
+    pub fn with_capacity(capacity: usize) -> Self {
+        KStack::with_storage(Vec::with_capacity(capacity))
+    }
+
+    /// Shrink capacity of stack as much as possible.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::with_capacity(10);
+    ///
+    ///     stack.kpush(&[1, 2, 3]);
+    ///     stack.shrink_to_fit();
+    ///
+    ///     assert!(stack.capacity() >= 3);
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        self.0.shrink_to_fit();
+    }
+
+    /// Start a speculative pop of K top element on stack. element are only
+    /// removed when the returned guard is committed, otherwise stack is
+    /// left untouched.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]);
+    ///
+    ///     let guard = stack.kpop_guard();
+    ///     assert_eq!(Some(&4), guard.window().get(0));
+    ///     drop(guard);
+    ///     assert_eq!(4, stack.len());
+    ///
+    ///     let guard = stack.kpop_guard();
+    ///     assert_eq!([Some(4), Some(3), Some(2)], guard.commit());
+    ///     assert_eq!(1, stack.len());
+    /// ```
+    pub fn kpop_guard(&mut self) -> KPopGuard<'_, T, K> {
+        KPopGuard::new(self)
+    }
+
+    /// Start a transaction, changes made through it are undone unless
+    /// it's committed.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3]);
+    ///
+    ///     let mut transaction = stack.checkpoint();
+    ///     transaction.kpop();
+    ///     transaction.push(4);
+    ///     transaction.rollback();
+    ///     assert_eq!(&[1, 2, 3], stack.kshow_slice());
+    ///
+    ///     let mut transaction = stack.checkpoint();
+    ///     transaction.pop();
+    ///     transaction.commit();
+    ///     assert_eq!(&[1, 2], stack.kshow_slice());
+    /// ```
+    pub fn checkpoint(&mut self) -> Transaction<'_, T, K> {
+        Transaction::new(self)
+    }
+
+    /// Push K element to stack, or an error if stack can't grow to hold
+    /// them. stack is left untouched on error.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     assert_eq!(Ok(()), stack.try_kpush(&[4, 5, 6]));
+    ///     assert_eq!(Ok([6, 5, 4]), stack.try_kpop());
+    /// ```
+    pub fn try_kpush(&mut self, items: &[T]) -> Result<(), KStackError>
+    where
+        T: Clone,
+    {
+        self.0
+            .try_reserve(items.len())
+            .map_err(|_| KStackError::Overflow)?;
+        self.0.extend_from_slice(items);
+        self.record_kpush(items.len());
+        Ok(())
+    }
+
+    /// Consumes the stack and returns an iterator over non-overlapping
+    /// chunks of K element, from top to bottom. each chunk is in the same
+    /// order as [`kpop`](Self::kpop), and the bottom element that don't
+    /// fill a whole chunk are available from
+    /// [`IntoKChunks::into_remainder`].
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4, 5]);
+    ///
+    ///     let mut chunks = stack.into_kchunks();
+    ///     assert_eq!(Some([5, 4]), chunks.next());
+    ///     assert_eq!(Some([3, 2]), chunks.next());
+    ///     assert_eq!(None, chunks.next());
+    ///     assert_eq!(vec![1], chunks.into_remainder());
+    /// ```
+    pub fn into_kchunks(self) -> IntoKChunks<T, K> {
+        IntoKChunks::new(self.0)
+    }
+}
+
+impl<T, const K: usize, S: Storage<T>> KStack<T, K, S> {
+    /// Evaluated by every constructor, so an empty window is a compile
+    /// error rather than a stack whose window ops do nothing.
+    const NONZERO_K: () = assert!(K > 0, "window size K of KStack must be at least 1");
+
+    /// Make a new KStack on top of given storage, its element become the
+    /// stack from bottom to top.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let stack = KStack::<i32, 2>::with_storage(vec![1, 2, 3]);
+    ///
+    ///     assert_eq!([Some(3), Some(2)], stack.kshow());
+    /// ```
+    pub fn with_storage(storage: S) -> Self {
+        let () = Self::NONZERO_K;
+        #[cfg(feature = "stats")]
+        let stats = KStackStats::new(storage.len());
+        KStack(
+            storage,
+            PhantomData,
+            #[cfg(feature = "stats")]
+            stats,
+        )
+    }
+
+    /// Underlying storage, for operations of a specific backend.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let stack = KStack::<i32, 2>::with_storage(vec![1, 2, 3]);
+    ///
+    ///     assert_eq!(&vec![1, 2, 3], stack.storage());
+    /// ```
+    pub fn storage(&self) -> &S {
+        &self.0
+    }
+
+    /// Mutable underlying storage, for operations of a specific backend.
+    pub fn storage_mut(&mut self) -> &mut S {
+        &mut self.0
+    }
+
+    /// Consume stack and return its storage, element are from bottom to
+    /// top.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3]);
+    ///
+    ///     assert_eq!(vec![1, 2, 3], stack.into_inner());
+    /// ```
+    pub fn into_inner(self) -> S {
+        self.0
+    }
+
+    /// Convert to a stack with window size M, keeping element and storage
+    /// as they are.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let stack = KStack::<i32, 2>::from(vec![1, 2, 3]);
+    ///     let wide = stack.rewindow::<3>();
+    ///
+    ///     assert_eq!([Some(3), Some(2), Some(1)], wide.kshow());
+    /// ```
+    pub fn rewindow<const M: usize>(self) -> KStack<T, M, S> {
+        let () = KStack::<T, M, S>::NONZERO_K;
+        KStack(
+            self.0,
+            PhantomData,
+            #[cfg(feature = "stats")]
+            self.2,
+        )
+    }
+
+    /// Move element to another storage with `f`, keeping stats.
+    pub(crate) fn map_storage<R: Storage<T>>(self, f: impl FnOnce(S) -> R) -> KStack<T, K, R> {
+        KStack(
+            f(self.0),
+            PhantomData,
+            #[cfg(feature = "stats")]
+            self.2,
+        )
+    }
+
+    /// return all element on stack from bottom to top, so the top element
+    /// is the last one.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3]);
+    ///
+    ///     assert_eq!(&[1, 2, 3], stack.as_slice());
+    /// ```
+    pub fn as_slice(&self) -> &[T] {
+        self.0.as_slice()
+    }
+
+    /// return all element on stack mutably from bottom to top, so the top
+    /// element is the last one.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3]);
+    ///     stack.as_mut_slice().sort_by(|a, b| b.cmp(a));
+    ///
+    ///     assert_eq!(Some(1), stack.pop());
+    /// ```
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.0.as_mut_slice()
+    }
+
+    /// Number of element on stack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2]);
+    ///
+    ///     assert_eq!(2, stack.len());
+    /// ```
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether there is no element on stack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///     assert!(stack.is_empty());
+    ///
+    ///     stack.push(1);
+    ///     assert!(!stack.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Number of element stack can hold without reallocating.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let stack = KStack::<i32, 3>::with_capacity(10);
+    ///
+    ///     assert!(stack.capacity() >= 10);
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
 
-impl<T, const K: usize> KStack<T, K>
-where
-    T: Copy,
-{
-    /// Make a new KStack.
+    /// Reserve room for at least `additional` more element.
     ///
     /// # Example
     /// ```
     ///     use kstack::KStack;
     ///
     ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.push(1);
+    ///     stack.reserve(10);
+    ///
+    ///     assert!(stack.capacity() >= 11);
     /// ```
-    pub fn new() -> Self {
-        KStack(Vec::<T>::new())
+    pub fn reserve(&mut self, additional: usize) {
+        self.0.reserve(additional);
     }
 
     /// Remove and get single element on top of stack.
@@ -40,8 +519,10 @@ where
     ///     assert_eq!(Some(1), stack.pop());
     ///     assert_eq!(None, stack.pop());
     /// ```
-    pub fn pop(self: &mut Self) -> Option<T> {
-        self.0.pop()
+    pub fn pop(&mut self) -> Option<T> {
+        let item = self.0.pop()?;
+        self.record_pop(1);
+        Some(item)
     }
 
     /// Push a single element to stack.
@@ -59,8 +540,9 @@ where
     ///     assert_eq!(Some(1), stack.pop());
     ///     assert_eq!(None, stack.pop());
     /// ```
-    pub fn push(self: &mut Self, item: T) {
+    pub fn push(&mut self, item: T) {
         self.0.push(item);
+        self.record_push(1);
     }
 
     /// Removes and return K top element on stack.
@@ -79,15 +561,13 @@ where
     ///     assert_eq!([Some(4), Some(3), Some(2)], stack.kpop());
     ///     assert_eq!([Some(1), None, None], stack.kpop());
     /// ```
-    pub fn kpop(self: &mut Self) -> [Option<T>; K] {
-        let mut result: [Option<T>; K] = [None; K];
-        for i in 0..=(K - 1) {
-            result[i] = self.0.pop();
-        }
-        result
+    pub fn kpop(&mut self) -> [Option<T>; K] {
+        self.kpop_as::<K>()
     }
 
-    /// return K top element on stack.
+    /// Removes and return M top element on stack, like
+    /// [`kpop`](Self::kpop) but with a different window size for this
+    /// call only.
     ///
     /// # Example
     /// ```
@@ -95,35 +575,34 @@ where
     ///
     ///     let mut stack = KStack::<i32, 3>::new();
     ///
-    ///     stack.push(1);
-    ///     stack.push(2);
-    ///     stack.push(3);
-    ///     stack.push(4);
+    ///     stack.kpush(&[1, 2, 3, 4]);
     ///
-    ///     assert_eq!([Some(4), Some(3), Some(2)], stack.kshow());
-    ///     assert_eq!([Some(4), Some(3), Some(2)], stack.kpop());
+    ///     assert_eq!([Some(4)], stack.kpop_as::<1>());
+    ///     assert_eq!([Some(3), Some(2), Some(1), None], stack.kpop_as::<4>());
     /// ```
-    pub fn kshow(self: &mut Self) -> [Option<T>; K] {
-        let mut result: [Option<T>; K] = [None; K];
+    pub fn kpop_as<const M: usize>(&mut self) -> [Option<T>; M] {
+        let len = self.0.len();
+        let count = len.min(M);
+        if count == 0 {
+            return core::array::from_fn(|_| None);
+        }
+        let top = self.0.as_mut_slice().as_mut_ptr_range().end;
 
-        match self.0.len() {
-            0 => result,
-            x if x < K => {
-                for i in 0..self.0.len() {
-                    result[i] = Some(self.0[self.0.len() - i - 1]);
-                }
-                result
-            }
-            _ => {
-                for i in 0..=(K - 1) {
-                    result[i] = Some(self.0[self.0.len() - i - 1]);
-                }
-                result
-            }
+        // SAFETY: the `count` top element are each moved out once, then
+        // forgotten by storage. nothing in between can panic.
+        unsafe {
+            let items = core::array::from_fn(|i| match i < count {
+                true => Some(top.sub(i + 1).read()),
+                false => None,
+            });
+            self.0.set_len(len - count);
+            self.record_kpop(count);
+            items
         }
     }
 
-    /// Push K element to stack.
+    /// Removes and return K top element on stack, only if there are at
+    /// least K element, otherwise stack is left untouched.
     ///
     /// # Example
     /// ```
@@ -131,53 +610,1844 @@ where
     ///
     ///     let mut stack = KStack::<i32, 3>::new();
     ///
-    ///     stack.kpush(&[4, 5, 6, 7]);
+    ///     stack.kpush(&[1, 2, 3, 4]);
     ///
-    ///     assert_eq!(Some(7), stack.pop());
-    ///     assert_eq!(Some(6), stack.pop());
-    ///     assert_eq!(Some(5), stack.pop());
-    ///     assert_eq!(Some(4), stack.pop());
-    ///     assert_eq!(None, stack.pop());
+    ///     assert_eq!(Some([4, 3, 2]), stack.kpop_exact());
+    ///     assert_eq!(None, stack.kpop_exact());
+    ///     assert_eq!(Some(1), stack.pop());
     /// ```
-    pub fn kpush(self: &mut Self, items: &[T]) {
-        self.0.extend_from_slice(items);
-    }
-}
+    pub fn kpop_exact(&mut self) -> Option<[T; K]> {
+        let len = self.0.len();
+        if len < K {
+            return None;
+        }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let top = self.0.as_mut_slice().as_mut_ptr_range().end;
 
-    #[test]
-    fn test_kpop() {
-        let mut stack = KStack::<i32, 3>::new();
-        stack.push(1);
-        stack.push(2);
-        stack.push(3);
-        stack.push(4);
+        // SAFETY: same as `kpop_as`, with exactly K element.
+        unsafe {
+            let items = core::array::from_fn(|i| top.sub(i + 1).read());
+            self.0.set_len(len - K);
+            self.record_kpop(K);
+            Some(items)
+        }
+    }
 
-        assert_eq!([Some(4), Some(3), Some(2)], stack.kpop());
-        assert_eq!([Some(1), None, None], stack.kpop());
+    /// Removes K top element on stack, without the [`Option`] per element
+    /// of [`kpop`](Self::kpop). only the popped element are initialized,
+    /// so `T` needs no default value.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<u32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]);
+    ///
+    ///     assert_eq!(&[4, 3, 2], stack.kpop_raw().as_slice());
+    ///     assert_eq!(&[1], stack.kpop_raw().as_slice());
+    ///     assert!(stack.kpop_raw().is_empty());
+    /// ```
+    pub fn kpop_raw(&mut self) -> RawKWindow<T, K> {
+        let mut window = RawKWindow::new();
+        let len = self.kpop_into_uninit(window.buffer());
+        // SAFETY: `kpop_into_uninit` initialized the first `len` items.
+        unsafe { window.set_len(len) };
+        window
     }
 
-    #[test]
-    fn test_kshow() {
-        let mut stack = KStack::<i32, 3>::new();
-        stack.push(1);
-        stack.push(2);
-        stack.push(3);
-        stack.push(4);
+    /// Removes K top element on stack into `buffer`, top first, and return
+    /// how many were written. fewer are popped if stack or `buffer` is
+    /// shorter than K, and the rest of `buffer` is left untouched.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///     let mut buffer = [0; 3];
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]);
+    ///
+    ///     assert_eq!(3, stack.kpop_into(&mut buffer));
+    ///     assert_eq!([4, 3, 2], buffer);
+    ///     assert_eq!(1, stack.kpop_into(&mut buffer));
+    ///     assert_eq!([1, 3, 2], buffer);
+    /// ```
+    pub fn kpop_into(&mut self, buffer: &mut [T]) -> usize {
+        let len = self.0.len();
+        let count = len.min(K).min(buffer.len());
+        if count == 0 {
+            return 0;
+        }
+        let top = self.0.as_mut_slice().as_mut_ptr_range().end;
 
-        assert_eq!([Some(4), Some(3), Some(2)], stack.kshow());
-        assert_eq!([Some(4), Some(3), Some(2)], stack.kshow());
+        // SAFETY: the `count` top element are forgotten by storage before
+        // being moved out once, so if dropping an old element of buffer
+        // panics the rest are leaked instead of dropped twice.
+        unsafe {
+            self.0.set_len(len - count);
+            self.record_kpop(count);
+            for (i, slot) in buffer[..count].iter_mut().enumerate() {
+                *slot = top.sub(i + 1).read();
+            }
+        }
+        count
     }
 
-    #[test]
-    fn test_kshow_underflow() {
-        let mut stack = KStack::<i32, 3>::new();
-        stack.push(1);
-        stack.push(2);
-        stack.push(3);
+    /// Like [`kpop_into`](Self::kpop_into), but into a buffer that doesn't
+    /// need initialized element. the first returned number of element of
+    /// `buffer` are initialized.
+    ///
+    /// # Example
+    /// ```
+    ///     use core::mem::MaybeUninit;
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<String, 2>::new();
+    ///     let mut buffer = [MaybeUninit::<String>::uninit(), MaybeUninit::uninit()];
+    ///
+    ///     stack.push("a".to_string());
+    ///
+    ///     assert_eq!(1, stack.kpop_into_uninit(&mut buffer));
+    ///     assert_eq!("a", unsafe { buffer[0].assume_init_read() });
+    /// ```
+    pub fn kpop_into_uninit(&mut self, buffer: &mut [MaybeUninit<T>]) -> usize {
+        let len = self.0.len();
+        let count = len.min(K).min(buffer.len());
+        if count == 0 {
+            return 0;
+        }
+        let top = self.0.as_mut_slice().as_mut_ptr_range().end;
+
+        // SAFETY: same as `kpop_as`.
+        unsafe {
+            for (i, slot) in buffer[..count].iter_mut().enumerate() {
+                slot.write(top.sub(i + 1).read());
+            }
+            self.0.set_len(len - count);
+        }
+        self.record_kpop(count);
+        count
+    }
+
+    /// Remove and get single element on top of stack, or an error if
+    /// stack is empty.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::{KStack, KStackError};
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.push(1);
+    ///
+    ///     assert_eq!(Ok(1), stack.try_pop());
+    ///     assert_eq!(
+    ///         Err(KStackError::Underflow { requested: 1, available: 0 }),
+    ///         stack.try_pop()
+    ///     );
+    /// ```
+    pub fn try_pop(&mut self) -> Result<T, KStackError> {
+        self.pop().ok_or(KStackError::Underflow {
+            requested: 1,
+            available: 0,
+        })
+    }
+
+    /// Removes and return K top element on stack, or an error if there
+    /// are less than K element. stack is left untouched on error.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::{KStack, KStackError};
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]);
+    ///
+    ///     assert_eq!(Ok([4, 3, 2]), stack.try_kpop());
+    ///     assert_eq!(
+    ///         Err(KStackError::Underflow { requested: 3, available: 1 }),
+    ///         stack.try_kpop()
+    ///     );
+    /// ```
+    pub fn try_kpop(&mut self) -> Result<[T; K], KStackError> {
+        let available = self.0.len();
+        self.kpop_exact().ok_or(KStackError::Underflow {
+            requested: K,
+            available,
+        })
+    }
+
+    /// return reference to element on top of stack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     assert_eq!(None, stack.peek());
+    ///     stack.kpush(&[1, 2]);
+    ///     assert_eq!(Some(&2), stack.peek());
+    /// ```
+    pub fn peek(&self) -> Option<&T> {
+        self.0.as_slice().last()
+    }
+
+    /// return mutable reference to element on top of stack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2]);
+    ///     if let Some(top) = stack.peek_mut() {
+    ///         *top = 5;
+    ///     }
+    ///
+    ///     assert_eq!(Some(5), stack.pop());
+    /// ```
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.0.as_mut_slice().last_mut()
+    }
+
+    /// return reference to element `depth` positions below top of stack,
+    /// so depth 0 is the top.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3]);
+    ///
+    ///     assert_eq!(Some(&3), stack.get(0));
+    ///     assert_eq!(Some(&1), stack.get(2));
+    ///     assert_eq!(None, stack.get(3));
+    /// ```
+    pub fn get(&self, depth: usize) -> Option<&T> {
+        let index = self.index_of(depth)?;
+        self.0.as_slice().get(index)
+    }
+
+    /// return mutable reference to element `depth` positions below top of
+    /// stack, so depth 0 is the top.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3]);
+    ///     if let Some(item) = stack.get_mut(2) {
+    ///         *item = 10;
+    ///     }
+    ///
+    ///     assert_eq!(vec![3, 2, 10], stack.kpop_n(3));
+    /// ```
+    pub fn get_mut(&mut self, depth: usize) -> Option<&mut T> {
+        let index = self.index_of(depth)?;
+        self.0.as_mut_slice().get_mut(index)
+    }
+
+    /// Whether `item` is anywhere on stack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let stack = KStack::<i32, 2>::from(vec![1, 2, 3]);
+    ///
+    ///     assert!(stack.contains(&1));
+    ///     assert!(!stack.contains(&4));
+    /// ```
+    pub fn contains(&self, item: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.0.as_slice().contains(item)
+    }
+
+    /// Depth of the topmost element `predicate` holds for, so 0 is the
+    /// top, searching the whole stack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let stack = KStack::<char, 2>::from(vec!['(', 'a', '(', 'b', 'c']);
+    ///
+    ///     assert_eq!(Some(2), stack.position_from_top(|&c| c == '('));
+    ///     assert_eq!(None, stack.position_from_top(|&c| c == ')'));
+    /// ```
+    pub fn position_from_top(&self, predicate: impl FnMut(&T) -> bool) -> Option<usize> {
+        self.iter().position(predicate)
+    }
+
+    /// Topmost of K top element on stack that `predicate` holds for.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let stack = KStack::<i32, 2>::from(vec![4, 1, 3]);
+    ///
+    ///     assert_eq!(Some(&1), stack.find_in_window(|&x| x < 2));
+    ///     assert_eq!(None, stack.find_in_window(|&x| x > 3));
+    /// ```
+    pub fn find_in_window(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<&T> {
+        self.kshow_view().iter().find(|item| predicate(item))
+    }
+
+    /// Sort element of stack from bottom to top, so the largest one ends
+    /// up on top. sort is stable.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::from(vec![3, 1, 2]);
+    ///
+    ///     stack.sort();
+    ///
+    ///     assert_eq!([Some(3), Some(2)], stack.kshow());
+    ///     assert!(stack.is_sorted());
+    /// ```
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.0.as_mut_slice().sort();
+    }
+
+    /// Sort element of stack from bottom to top with `compare`.
+    pub fn sort_by(&mut self, compare: impl FnMut(&T, &T) -> Ordering) {
+        self.0.as_mut_slice().sort_by(compare);
+    }
+
+    /// Sort element of stack from bottom to top by a key.
+    pub fn sort_by_key<U: Ord>(&mut self, key: impl FnMut(&T) -> U) {
+        self.0.as_mut_slice().sort_by_key(key);
+    }
+
+    /// Whether element of stack are sorted from bottom to top, like
+    /// [`sort`](Self::sort) leaves them.
+    pub fn is_sorted(&self) -> bool
+    where
+        T: PartialOrd,
+    {
+        self.0.as_slice().windows(2).all(|pair| pair[0] <= pair[1])
+    }
+
+    /// Binary search a stack sorted from bottom to top for `item`.
+    ///
+    /// Like [`slice::binary_search`], positions are indexes in
+    /// [`as_slice`](Self::as_slice), counted from bottom, `Err` has where
+    /// `item` would go to keep stack sorted.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let stack = KStack::<i32, 2>::from(vec![1, 3, 5]);
+    ///
+    ///     assert_eq!(Ok(1), stack.binary_search(&3));
+    ///     assert_eq!(Err(3), stack.binary_search(&8));
+    /// ```
+    pub fn binary_search(&self, item: &T) -> Result<usize, usize>
+    where
+        T: Ord,
+    {
+        self.0.as_slice().binary_search(item)
+    }
+
+    /// Binary search a stack sorted from bottom to top with `compare`,
+    /// like [`binary_search`](Self::binary_search).
+    pub fn binary_search_by(&self, compare: impl FnMut(&T) -> Ordering) -> Result<usize, usize> {
+        self.0.as_slice().binary_search_by(compare)
+    }
+
+    /// Binary search a stack sorted from bottom to top by a key, like
+    /// [`binary_search`](Self::binary_search).
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<(&str, u32), 2>::from(vec![("b", 2), ("c", 3), ("a", 1)]);
+    ///
+    ///     stack.sort_by_key(|&(_, n)| n);
+    ///
+    ///     assert_eq!(Ok(2), stack.binary_search_by_key(&3, |&(_, n)| n));
+    /// ```
+    pub fn binary_search_by_key<U: Ord>(
+        &self,
+        key: &U,
+        f: impl FnMut(&T) -> U,
+    ) -> Result<usize, usize> {
+        self.0.as_slice().binary_search_by_key(key, f)
+    }
+
+    /// Sort K top element on stack in place, the largest one ends up on
+    /// top. element below the window are untouched.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::from(vec![9, 2, 3, 1]);
+    ///
+    ///     stack.ksort();
+    ///
+    ///     assert_eq!(&[9, 1, 2, 3], stack.as_slice());
+    /// ```
+    pub fn ksort(&mut self)
+    where
+        T: Ord,
+    {
+        self.kshow_slice_mut().sort();
+    }
+
+    /// Sort K top element on stack in place with `compare`.
+    pub fn ksort_by(&mut self, compare: impl FnMut(&T, &T) -> Ordering) {
+        self.kshow_slice_mut().sort_by(compare);
+    }
+
+    /// Remove consecutive repeated element among K top element on stack,
+    /// keeping the lowest of each run. element below the window are
+    /// untouched, but they can move into the window as it shrinks.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 4>::from(vec![1, 1, 2, 2, 3]);
+    ///
+    ///     stack.kdedup();
+    ///
+    ///     assert_eq!(&[1, 1, 2, 3], stack.as_slice());
+    /// ```
+    pub fn kdedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        let len = self.0.len();
+        let start = len.saturating_sub(K);
+        let window = &mut self.0.as_mut_slice()[start..];
+        if window.is_empty() {
+            return;
+        }
+
+        // move the first element of each run to the front of window, so
+        // repeats end up after `kept` and can be dropped.
+        let mut kept = 1;
+        for read in 1..window.len() {
+            if window[read] != window[kept - 1] {
+                window.swap(kept, read);
+                kept += 1;
+            }
+        }
+        self.truncate_to_depth(start + kept);
+    }
+
+    /// return references to K top element on stack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<String, 3>::new();
+    ///
+    ///     stack.push("a".to_string());
+    ///     stack.push("b".to_string());
+    ///
+    ///     assert_eq!(Some(&"b".to_string()), stack.kshow_ref()[0]);
+    ///     assert_eq!(Some(&"a".to_string()), stack.kshow_ref()[1]);
+    ///     assert_eq!(None, stack.kshow_ref()[2]);
+    /// ```
+    pub fn kshow_ref(&self) -> [Option<&T>; K] {
+        self.kshow_ref_as::<K>()
+    }
+
+    fn kshow_ref_as<const M: usize>(&self) -> [Option<&T>; M] {
+        let mut items = self.0.as_slice().iter().rev();
+        core::array::from_fn(|_| items.next())
+    }
+
+    /// return K top element on stack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.push(1);
+    ///     stack.push(2);
+    ///     stack.push(3);
+    ///     stack.push(4);
+    ///
+    ///     assert_eq!([Some(4), Some(3), Some(2)], stack.kshow());
+    ///     assert_eq!([Some(4), Some(3), Some(2)], stack.kpop());
+    /// ```
+    pub fn kshow(&self) -> [Option<T>; K]
+    where
+        T: Clone,
+    {
+        self.kshow_as::<K>()
+    }
+
+    /// return M top element on stack, like [`kshow`](Self::kshow) but
+    /// with a different window size for this call only.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]);
+    ///
+    ///     assert_eq!([Some(4), Some(3), Some(2), Some(1), None], stack.kshow_as::<5>());
+    ///     assert_eq!([Some(4), Some(3), Some(2)], stack.kshow());
+    /// ```
+    pub fn kshow_as<const M: usize>(&self) -> [Option<T>; M]
+    where
+        T: Clone,
+    {
+        self.kshow_ref_as::<M>().map(|item| item.cloned())
+    }
+
+    /// return mutable references to K top element on stack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.push(1);
+    ///     stack.push(2);
+    ///
+    ///     if let [Some(top), _, _] = stack.kshow_mut() {
+    ///         *top = 5;
+    ///     }
+    ///
+    ///     assert_eq!([Some(5), Some(1), None], stack.kshow());
+    /// ```
+    pub fn kshow_mut(&mut self) -> [Option<&mut T>; K] {
+        let mut items = self.0.as_mut_slice().iter_mut().rev();
+        core::array::from_fn(|_| items.next())
+    }
+
+    /// return a view of K top element on stack without copying them.
+    ///
+    /// Items are in stack order, so the top element is the last one.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]);
+    ///
+    ///     assert_eq!(&[2, 3, 4], stack.kshow_slice());
+    /// ```
+    pub fn kshow_slice(&self) -> &[T] {
+        let start = self.0.len().saturating_sub(K);
+        &self.0.as_slice()[start..]
+    }
+
+    /// return a mutable view of K top element on stack.
+    ///
+    /// Items are in stack order, so the top element is the last one.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]);
+    ///     stack.kshow_slice_mut()[2] = 5;
+    ///
+    ///     assert_eq!(&[2, 3, 5], stack.kshow_slice());
+    /// ```
+    pub fn kshow_slice_mut(&mut self) -> &mut [T] {
+        let start = self.0.len().saturating_sub(K);
+        &mut self.0.as_mut_slice()[start..]
+    }
+
+    /// return a borrowed view of K top element on stack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]);
+    ///     let window = stack.kshow_view();
+    ///
+    ///     assert_eq!(3, window.len());
+    ///     assert_eq!(Some(&4), window.get(0));
+    ///     assert!(window.contains(&2));
+    ///     assert!(!window.contains(&1));
+    /// ```
+    pub fn kshow_view(&self) -> KWindow<'_, T, K> {
+        KWindow(self.kshow_slice())
+    }
+
+    /// return a borrowed view of K element starting `offset` element below
+    /// top of stack, so `kshow_at(0)` is [`kshow_view`](Self::kshow_view).
+    /// window is shorter near bottom and empty past it.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4, 5]);
+    ///
+    ///     assert_eq!(&[2, 3], stack.kshow_at(2).as_slice());
+    ///     assert_eq!(&[1], stack.kshow_at(4).as_slice());
+    ///     assert!(stack.kshow_at(5).is_empty());
+    /// ```
+    pub fn kshow_at(&self, offset: usize) -> KWindow<'_, T, K> {
+        let end = self.0.len().saturating_sub(offset);
+        let start = end.saturating_sub(K);
+        KWindow(&self.0.as_slice()[start..end])
+    }
+
+    /// return K bottom element on stack, oldest first.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3]);
+    ///
+    ///     assert_eq!(&[1, 2], stack.kbottom_show());
+    /// ```
+    pub fn kbottom_show(&self) -> &[T] {
+        let end = K.min(self.0.len());
+        &self.0.as_slice()[..end]
+    }
+
+    /// Removes and return K bottom element on stack, oldest first. rest of
+    /// stack is moved down once, in O(n).
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3]);
+    ///
+    ///     assert_eq!([Some(1), Some(2)], stack.kbottom_pop());
+    ///     assert_eq!([Some(3), None], stack.kbottom_pop());
+    ///     assert!(stack.is_empty());
+    /// ```
+    pub fn kbottom_pop(&mut self) -> [Option<T>; K] {
+        let len = self.0.len();
+        let count = K.min(len);
+        let items = self.0.as_mut_slice().as_mut_ptr();
+
+        // SAFETY: `count` bottom element are read once and the rest moved
+        // over them before storage forgets the moved-from tail, no user
+        // code runs in between.
+        unsafe {
+            let popped = core::array::from_fn(|i| match i < count {
+                true => Some(items.add(i).read()),
+                false => None,
+            });
+            core::ptr::copy(items.add(count), items, len - count);
+            self.0.set_len(len - count);
+            self.record_kpop(count);
+            popped
+        }
+    }
+
+    /// Hash of K top element on stack, to find out if window changed by
+    /// comparing a single number. it's the same across runs, unlike
+    /// `std` hashers which are randomly seeded, but can still collide.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3]);
+    ///     let hash = stack.khash();
+    ///
+    ///     stack.push(4);
+    ///     assert_ne!(hash, stack.khash());
+    ///
+    ///     stack.pop();
+    ///     assert_eq!(hash, stack.khash());
+    /// ```
+    pub fn khash(&self) -> u64
+    where
+        T: Hash,
+    {
+        let mut hasher = FnvHasher::default();
+        self.kshow_slice().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// return a view of `n` top element on stack, like
+    /// [`kshow_slice`](Self::kshow_slice) but with window size given at
+    /// runtime.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]);
+    ///
+    ///     assert_eq!(&[3, 4], stack.kshow_n(2));
+    ///     assert_eq!(&[1, 2, 3, 4], stack.kshow_n(10));
+    /// ```
+    pub fn kshow_n(&self, n: usize) -> &[T] {
+        let start = self.0.len().saturating_sub(n);
+        &self.0.as_slice()[start..]
+    }
+
+    /// Removes and return `n` top element on stack, like
+    /// [`kpop`](Self::kpop) but with window size given at runtime. there
+    /// are less than `n` element if stack runs out.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]);
+    ///
+    ///     assert_eq!(vec![4, 3], stack.kpop_n(2));
+    ///     assert_eq!(vec![2, 1], stack.kpop_n(10));
+    /// ```
+    pub fn kpop_n(&mut self, n: usize) -> Vec<T> {
+        let start = self.0.len().saturating_sub(n);
+        let count = self.0.len() - start;
+        let mut items = Vec::with_capacity(count);
+        let window = self.0.as_mut_slice()[start..].as_mut_ptr();
+
+        // SAFETY: same as `kpop_as`, pushes can't panic because room for
+        // all of them is already allocated.
+        unsafe {
+            for i in (0..count).rev() {
+                items.push(window.add(i).read());
+            }
+            self.0.set_len(start);
+        }
+        self.record_kpop(count);
+        items
+    }
+
+    /// Remove all element on stack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3]);
+    ///     stack.clear();
+    ///
+    ///     assert!(stack.is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        self.truncate_to_depth(0);
+    }
+
+    /// Keep only the `n` bottom element on stack, removing the ones above
+    /// them. it does nothing if there are not more than `n` element.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]);
+    ///     stack.truncate_to_depth(1);
+    ///
+    ///     assert_eq!(vec![1], stack.kpop_n(4));
+    /// ```
+    pub fn truncate_to_depth(&mut self, n: usize) {
+        let len = self.0.len();
+        self.0.truncate(n);
+        self.record_pop(len.saturating_sub(n));
+    }
+
+    /// Remove `n` top element on stack, or all of them if there are less,
+    /// and return an iterator yielding them from top to bottom. they are
+    /// removed even if the iterator is dropped before yielding them.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]);
+    ///
+    ///     assert_eq!(vec![4, 3], stack.drain_top(2).collect::<Vec<_>>());
+    ///     stack.drain_top(1);
+    ///     assert_eq!(vec![1], stack.kpop_n(4));
+    /// ```
+    pub fn drain_top(&mut self, n: usize) -> DrainTop<'_, T, K, S> {
+        let remaining = n.min(self.len());
+        DrainTop {
+            stack: self,
+            remaining,
+        }
+    }
+
+    /// Remove element from top of stack while `predicate` holds for them,
+    /// and return an iterator yielding them from top to bottom. the first
+    /// element it doesn't hold for, like a sentinel, stays on stack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<char, 2>::new();
+    ///
+    ///     stack.kpush(&['(', 'a', '[', 'b', 'c']);
+    ///
+    ///     let inner: String = stack.drain_top_while(|&c| c != '[').collect();
+    ///     assert_eq!("cb", inner);
+    ///     assert_eq!(Some(&'['), stack.peek());
+    /// ```
+    pub fn drain_top_while(&mut self, predicate: impl FnMut(&T) -> bool) -> DrainTop<'_, T, K, S> {
+        let count = self.top_run_len(usize::MAX, predicate);
+        self.drain_top(count)
+    }
+
+    /// Removes and return up to K top element on stack while `predicate`
+    /// holds for them, the rest of window is `None`. `predicate` is only
+    /// called on the window, so it's O(K).
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, -2, 3, 4, 5, 6]);
+    ///
+    ///     assert_eq!([Some(6), Some(5), Some(4)], stack.kpop_while(|&x| x > 0));
+    ///     assert_eq!([Some(3), None, None], stack.kpop_while(|&x| x > 0));
+    ///     assert_eq!([None, None, None], stack.kpop_while(|&x| x > 0));
+    ///     assert_eq!(2, stack.len());
+    /// ```
+    pub fn kpop_while(&mut self, predicate: impl FnMut(&T) -> bool) -> [Option<T>; K] {
+        let count = self.top_run_len(K, predicate);
+        let items = core::array::from_fn(|i| match i < count {
+            true => self.0.pop(),
+            false => None,
+        });
+        self.record_kpop(count);
+        items
+    }
+
+    /// Number of element on top of stack that `predicate` holds for,
+    /// checking at most `limit` of them.
+    fn top_run_len(&self, limit: usize, mut predicate: impl FnMut(&T) -> bool) -> usize {
+        self.as_slice()
+            .iter()
+            .rev()
+            .take(limit)
+            .take_while(|item| predicate(item))
+            .count()
+    }
+
+    /// Move all element of `other` on top of stack, keeping their order,
+    /// so top of `other` becomes top of stack. `other` is left empty.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new();
+    ///     let mut other = KStack::<i32, 2>::new();
+    ///
+    ///     stack.kpush(&[1, 2]);
+    ///     other.kpush(&[3, 4]);
+    ///     stack.append(&mut other);
+    ///
+    ///     assert!(other.is_empty());
+    ///     assert_eq!(vec![4, 3, 2, 1], stack.kpop_n(4));
+    /// ```
+    pub fn append(&mut self, other: &mut Self) {
+        let len = other.len();
+        other.move_top(len, &mut self.0);
+        self.record_push(len);
+    }
+
+    /// Split stack in two, returning a new stack with the `depth` top
+    /// element, or all of them if there are less, in the same order.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]);
+    ///     let top = stack.split_off(3);
+    ///
+    ///     assert_eq!(vec![1], stack.kpop_n(4));
+    ///     assert_eq!([Some(4), Some(3)], top.kshow());
+    ///     assert_eq!(3, top.len());
+    /// ```
+    pub fn split_off(&mut self, depth: usize) -> Self
+    where
+        S: Default,
+    {
+        let mut top = S::default();
+        self.move_top(depth, &mut top);
+        KStack::with_storage(top)
+    }
+
+    /// Consume stack and split it in a stack with the K top element, or
+    /// all of them if there are less, and a stack with the rest. both keep
+    /// the order of element.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]);
+    ///     let (window, rest) = stack.split_window();
+    ///
+    ///     assert_eq!(&[3, 4], window.kshow_n(4));
+    ///     assert_eq!(&[1, 2], rest.kshow_n(4));
+    /// ```
+    pub fn split_window(mut self) -> (Self, Self)
+    where
+        S: Default,
+    {
+        let window = self.split_off(K);
+        (window, self)
+    }
+
+    /// Push at most `n` element of an iterator to stack, like
+    /// [`kpush_iter`](Self::kpush_iter) but with window size given at
+    /// runtime. return number of pushed element.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     assert_eq!(2, stack.kpush_n(2, 1..));
+    ///     assert_eq!(1, stack.kpush_n(5, Some(3)));
+    ///
+    ///     assert_eq!(vec![3, 2, 1], stack.kpop_n(3));
+    /// ```
+    pub fn kpush_n<I: IntoIterator<Item = T>>(&mut self, n: usize, items: I) -> usize {
+        let len = self.0.len();
+        self.0.extend(items.into_iter().take(n));
+        let count = self.0.len() - len;
+        self.record_kpush(count);
+        count
+    }
+
+    /// Push K element to stack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[4, 5, 6, 7]);
+    ///
+    ///     assert_eq!(Some(7), stack.pop());
+    ///     assert_eq!(Some(6), stack.pop());
+    ///     assert_eq!(Some(5), stack.pop());
+    ///     assert_eq!(Some(4), stack.pop());
+    ///     assert_eq!(None, stack.pop());
+    /// ```
+    pub fn kpush(&mut self, items: &[T])
+    where
+        T: Clone,
+    {
+        self.0.extend(items.iter().cloned());
+        self.record_kpush(items.len());
+    }
+
+    /// Push all element of an iterator to stack, in iteration order so
+    /// the last one ends up on top.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush_iter((1..=4).map(|i| i * 10));
+    ///
+    ///     assert_eq!([Some(40), Some(30), Some(20)], stack.kpop());
+    ///     assert_eq!([Some(10), None, None], stack.kpop());
+    /// ```
+    pub fn kpush_iter<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        let len = self.0.len();
+        self.0.extend(items);
+        self.record_kpush(self.0.len() - len);
+    }
+
+    /// Push exactly K element to stack, in the same order as
+    /// [`kpush`](Self::kpush) so the last one ends up on top.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush_array([4, 5, 6]);
+    ///
+    ///     assert_eq!(Some([6, 5, 4]), stack.kpop_exact());
+    /// ```
+    pub fn kpush_array(&mut self, items: [T; K]) {
+        self.0.extend(items);
+        self.record_kpush(K);
+    }
+
+    /// Returns an iterator over stack items, from top to bottom.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3]);
+    ///
+    ///     assert_eq!(vec![&3, &2, &1], stack.iter().collect::<Vec<_>>());
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter(self.0.as_slice().iter().rev())
+    }
+
+    /// Returns an iterator that allows modifying stack items, from top
+    /// to bottom.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3]);
+    ///     for item in stack.iter_mut() {
+    ///         *item *= 2;
+    ///     }
+    ///
+    ///     assert_eq!([Some(6), Some(4), Some(2)], stack.kpop());
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut(self.0.as_mut_slice().iter_mut().rev())
+    }
+
+    /// Returns an iterator over every overlapping window of K element,
+    /// from top to bottom. each window is in stack order like
+    /// [`kshow_slice`](Self::kshow_slice), and nothing is yielded when
+    /// there are less than K element.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3]);
+    ///
+    ///     let mut windows = stack.iter_windows();
+    ///     assert_eq!(Some(&[2, 3][..]), windows.next());
+    ///     assert_eq!(Some(&[1, 2][..]), windows.next());
+    ///     assert_eq!(None, windows.next());
+    /// ```
+    pub fn iter_windows(&self) -> Windows<'_, T> {
+        Windows(self.0.as_slice().windows(K).rev())
+    }
+
+    /// Returns an iterator over non-overlapping chunks of K element, from
+    /// top to bottom. each chunk is in stack order like
+    /// [`kshow_slice`](Self::kshow_slice), and the bottom element that
+    /// don't fill a whole chunk are available from
+    /// [`KChunks::remainder`].
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4, 5]);
+    ///
+    ///     let mut chunks = stack.kchunks();
+    ///     assert_eq!(Some(&[4, 5][..]), chunks.next());
+    ///     assert_eq!(Some(&[2, 3][..]), chunks.next());
+    ///     assert_eq!(None, chunks.next());
+    ///     assert_eq!(&[1], chunks.remainder());
+    /// ```
+    pub fn kchunks(&self) -> KChunks<'_, T> {
+        KChunks(self.0.as_slice().rchunks_exact(K))
+    }
+
+    /// Duplicate K top element on stack, or an error if there are less
+    /// than K element.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3]);
+    ///     stack.kdup().unwrap();
+    ///
+    ///     assert_eq!(vec![3, 2, 3, 2, 1], stack.kpop_n(5));
+    /// ```
+    pub fn kdup(&mut self) -> Result<(), KStackError>
+    where
+        T: Clone,
+    {
+        let len = self.require(K)?;
+        for i in len - K..len {
+            let item = self.0.as_slice()[i].clone();
+            self.push(item);
+        }
+        Ok(())
+    }
+
+    /// Swap K top element with K element below them, or an error if there
+    /// are less than 2 * K element.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4, 5]);
+    ///     stack.kswap().unwrap();
+    ///
+    ///     assert_eq!(vec![3, 2, 5, 4, 1], stack.kpop_n(5));
+    /// ```
+    pub fn kswap(&mut self) -> Result<(), KStackError> {
+        let len = self.require(2 * K)?;
+        self.0.as_mut_slice()[len - 2 * K..].rotate_left(K);
+        Ok(())
+    }
+
+    /// Remove K top element on stack without returning them, or an error
+    /// if there are less than K element.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3]);
+    ///     stack.kdrop().unwrap();
+    ///
+    ///     assert_eq!(vec![1], stack.kpop_n(5));
+    ///     assert!(stack.kdrop().is_err());
+    /// ```
+    pub fn kdrop(&mut self) -> Result<(), KStackError> {
+        let len = self.require(K)?;
+        self.truncate_to_depth(len - K);
+        Ok(())
+    }
+
+    /// Copy K element below the top window on top of stack, or an error if
+    /// there are less than 2 * K element.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]);
+    ///     stack.kover().unwrap();
+    ///
+    ///     assert_eq!(vec![2, 1, 4, 3, 2, 1], stack.kpop_n(6));
+    /// ```
+    pub fn kover(&mut self) -> Result<(), KStackError>
+    where
+        T: Clone,
+    {
+        let len = self.require(2 * K)?;
+        for i in len - 2 * K..len - K {
+            let item = self.0.as_slice()[i].clone();
+            self.push(item);
+        }
+        Ok(())
+    }
+
+    /// Rotate K top element on stack by `n` positions in place, each
+    /// element moves `n` positions down and the ones passing the bottom of
+    /// window wrap to the top. with K = 3, `krotate(1)` is Forth `ROT`.
+    /// if there are less than K element, all of them are rotated.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]);
+    ///     stack.krotate(1);
+    ///
+    ///     assert_eq!(&[1, 3, 4, 2], stack.kshow_n(4));
+    /// ```
+    pub fn krotate(&mut self, n: usize) {
+        let window = self.kshow_slice_mut();
+        if !window.is_empty() {
+            let n = n % window.len();
+            window.rotate_left(n);
+        }
+    }
+
+    /// Reverse order of K top element on stack in place. if there are
+    /// less than K element, all of them are reversed.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]);
+    ///     stack.kreverse();
+    ///
+    ///     assert_eq!(&[1, 4, 3, 2], stack.kshow_n(4));
+    /// ```
+    pub fn kreverse(&mut self) {
+        self.kshow_slice_mut().reverse();
+    }
+
+    /// Apply `f` to each of K top element on stack in place, from top to
+    /// bottom.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]);
+    ///     stack.kmap(|item| *item *= 10);
+    ///
+    ///     assert_eq!(&[1, 20, 30, 40], stack.kshow_n(4));
+    /// ```
+    pub fn kmap<F: FnMut(&mut T)>(&mut self, f: F) {
+        self.kshow_slice_mut().iter_mut().rev().for_each(f);
+    }
+
+    /// Remove element of K top element on stack that `f` returns false
+    /// for, keeping order of the rest. element below the window are never
+    /// removed.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]);
+    ///     stack.kretain_window(|item| item % 2 == 0);
+    ///
+    ///     assert_eq!(&[1, 2, 4], stack.kshow_n(4));
+    /// ```
+    pub fn kretain_window<F: FnMut(&T) -> bool>(&mut self, f: F) {
+        let start = self.0.len().saturating_sub(K);
+        self.retain_from(start, f);
+    }
+
+    /// Remove element on stack that `f` returns false for, keeping order
+    /// of the rest. `f` is called from bottom to top.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4, 5, 6]);
+    ///     stack.retain(|item| item % 3 != 0);
+    ///
+    ///     assert_eq!(vec![5, 4, 2, 1], stack.kpop_n(6));
+    /// ```
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+        self.retain_from(0, f);
+    }
+
+    /// Remove element from index `start` up that `f` returns false for,
+    /// by moving the kept ones down in place.
+    fn retain_from<F: FnMut(&T) -> bool>(&mut self, start: usize, mut f: F) {
+        let items = &mut self.0.as_mut_slice()[start..];
+        let mut kept = 0;
+        for i in 0..items.len() {
+            if f(&items[i]) {
+                items.swap(kept, i);
+                kept += 1;
+            }
+        }
+        self.truncate_to_depth(start + kept);
+    }
+
+    /// Move `count` top element, or all of them if there are less, on top
+    /// of `other`, keeping their order.
+    fn move_top<D: Storage<T>>(&mut self, count: usize, other: &mut D) {
+        let len = self.0.len();
+        let count = count.min(len);
+        if count == 0 {
+            return;
+        }
+        other.reserve(count);
+        let items = self.0.as_mut_slice()[len - count..].as_mut_ptr();
+
+        // SAFETY: moved element are forgotten by storage before being
+        // moved out once, so if a push panics the rest are leaked instead
+        // of dropped twice.
+        unsafe {
+            self.0.set_len(len - count);
+            self.record_pop(count);
+            for i in 0..count {
+                other.push(items.add(i).read());
+            }
+        }
+    }
+
+    /// Pop two top element on stack, combine them with `f` and push the
+    /// result, or an error if there are less than two element. `f` gets
+    /// the lower element first, so pushing `a` then `b` computes
+    /// `f(a, b)` like in RPN.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new();
+    ///
+    ///     stack.push(7);
+    ///     stack.push(2);
+    ///     stack.apply_binop(|a, b| a - b).unwrap();
+    ///
+    ///     assert_eq!(Some(5), stack.pop());
+    ///     assert!(stack.apply_binop(|a, b| a - b).is_err());
+    /// ```
+    #[doc(alias = "try_apply2")]
+    pub fn apply_binop<F: FnOnce(T, T) -> T>(&mut self, f: F) -> Result<(), KStackError> {
+        self.require(2)?;
+        let b = self.0.pop().unwrap();
+        let a = self.0.pop().unwrap();
+        self.record_pop(2);
+        self.push(f(a, b));
+        Ok(())
+    }
+
+    /// Pop two top element on stack, combine them with `f` and push the
+    /// result. `f` gets the lower element first, like
+    /// [`apply_binop`](Self::apply_binop).
+    ///
+    /// # Panics
+    /// Panics if there are less than two element, see
+    /// [`apply_binop`](Self::apply_binop) for the checked version.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new();
+    ///
+    ///     stack.kpush(&[2, 3, 4]);
+    ///     stack.apply2(|a, b| a * b);
+    ///     stack.apply2(|a, b| a + b);
+    ///
+    ///     assert_eq!(&[14], stack.as_slice());
+    /// ```
+    pub fn apply2<F: FnOnce(T, T) -> T>(&mut self, f: F) {
+        if let Err(error) = self.apply_binop(f) {
+            panic!("apply2: {}", error);
+        }
+    }
+
+    /// Pop K top element on stack, combine them with `f` and push the
+    /// result, or an error if there are less than K element, leaving stack
+    /// untouched. `f` gets the window top first, like
+    /// [`kpop_exact`](Self::kpop_exact).
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]);
+    ///     stack.kreduce(|[a, b, c]| a + b + c).unwrap();
+    ///
+    ///     assert_eq!(&[1, 9], stack.as_slice());
+    ///     assert!(stack.kreduce(|[a, b, c]| a + b + c).is_err());
+    ///     assert_eq!(2, stack.len());
+    /// ```
+    pub fn kreduce<F: FnOnce([T; K]) -> T>(&mut self, f: F) -> Result<(), KStackError> {
+        self.require(K)?;
+        // checked there are at least K element.
+        let window = self.kpop_exact().unwrap();
+        self.push(f(window));
+        Ok(())
+    }
+
+    /// Graphviz DOT graph of stack, a node for each element from top to
+    /// bottom with K top element filled.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<&str, 1>::new();
+    ///
+    ///     stack.kpush(&["a", "say \"hi\""]);
+    ///
+    ///     let dot = "\
+    ///         digraph kstack {\n\
+    ///         \x20   node [shape=box];\n\
+    ///         \x20   n0 [label=\"say \\\"hi\\\"\", style=filled, fillcolor=lightblue];\n\
+    ///         \x20   n1 [label=\"a\"];\n\
+    ///         \x20   n0 -> n1;\n\
+    ///         }\n";
+    ///     assert_eq!(dot, stack.to_dot());
+    /// ```
+    pub fn to_dot(&self) -> String
+    where
+        T: fmt::Display,
+    {
+        let window = self.kshow_slice().len();
+        let mut dot = String::from("digraph kstack {\n    node [shape=box];\n");
+
+        for (depth, item) in self.iter().enumerate() {
+            let mut label = String::new();
+            for c in item.to_string().chars() {
+                match c {
+                    '"' | '\\' => {
+                        label.push('\\');
+                        label.push(c);
+                    }
+                    '\n' => label.push_str("\\n"),
+                    _ => label.push(c),
+                }
+            }
+            let style = match depth < window {
+                true => ", style=filled, fillcolor=lightblue",
+                false => "",
+            };
+            // writing to a String never fails.
+            let _ = writeln!(dot, "    n{} [label=\"{}\"{}];", depth, label, style);
+        }
+        for depth in 1..self.len() {
+            let _ = writeln!(dot, "    n{} -> n{};", depth - 1, depth);
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Check there are at least `requested` element and return length of
+    /// stack.
+    fn require(&self, requested: usize) -> Result<usize, KStackError> {
+        let available = self.0.len();
+        match available < requested {
+            true => Err(KStackError::Underflow {
+                requested,
+                available,
+            }),
+            false => Ok(available),
+        }
+    }
+
+    /// Count `count` element pushed, for `stats` feature.
+    #[cfg_attr(not(feature = "stats"), allow(unused_variables))]
+    fn record_push(&mut self, count: usize) {
+        #[cfg(feature = "stats")]
+        self.2.pushed(count, self.0.len());
+    }
+
+    /// Count `count` element removed, for `stats` feature.
+    #[cfg_attr(not(feature = "stats"), allow(unused_variables))]
+    fn record_pop(&mut self, count: usize) {
+        #[cfg(feature = "stats")]
+        self.2.popped(count);
+    }
+
+    /// Count a window push of `count` element, for `stats` and `tracing`
+    /// features.
+    fn record_kpush(&mut self, count: usize) {
+        self.record_push(count);
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            pushed = count,
+            depth_before = self.0.len() - count,
+            depth_after = self.0.len(),
+            "kpush"
+        );
+    }
+
+    /// Count a window pop of `count` element, for `stats` and `tracing`
+    /// features.
+    fn record_kpop(&mut self, count: usize) {
+        #[cfg(feature = "stats")]
+        {
+            self.2.kpops += 1;
+        }
+        self.record_pop(count);
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            popped = count,
+            depth_before = self.0.len() + count,
+            depth_after = self.0.len(),
+            "kpop"
+        );
+    }
+
+    /// Index in storage of element `depth` positions below top of stack.
+    fn index_of(&self, depth: usize) -> Option<usize> {
+        self.0.len().checked_sub(depth)?.checked_sub(1)
+    }
+}
+
+impl<T, const K: usize, S: Clone> Clone for KStack<T, K, S> {
+    fn clone(&self) -> Self {
+        KStack(
+            self.0.clone(),
+            PhantomData,
+            #[cfg(feature = "stats")]
+            self.2,
+        )
+    }
+}
+
+impl<T, const K: usize, S: Storage<T> + Default> Default for KStack<T, K, S> {
+    fn default() -> Self {
+        KStack::with_storage(S::default())
+    }
+}
+
+/// Element are listed from top to bottom, like [`KStack::iter`].
+///
+/// # Example
+/// ```
+///     use kstack::KStack;
+///
+///     let mut stack = KStack::<i32, 2>::new();
+///
+///     stack.kpush(&[1, 2, 3]);
+///
+///     assert_eq!("[3, 2, 1]", format!("{:?}", stack));
+/// ```
+impl<T: fmt::Debug, const K: usize, S: Storage<T>> fmt::Debug for KStack<T, K, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Draws stack from top to bottom, with K top element marked.
+///
+/// # Example
+/// ```
+///     use kstack::KStack;
+///
+///     let mut stack = KStack::<i32, 3>::new();
+///
+///     stack.kpush(&[1, 2, 30, 4]);
+///
+///     let diagram = "\
+///         |  4 | \\ K\n\
+///         | 30 | |\n\
+///         |  2 | /\n\
+///         |  1 |\n\
+///         +----+";
+///     assert_eq!(diagram, stack.to_string());
+/// ```
+impl<T: fmt::Display, const K: usize, S: Storage<T>> fmt::Display for KStack<T, K, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cells: Vec<String> = self.iter().map(ToString::to_string).collect();
+        let width = cells
+            .iter()
+            .map(|cell| cell.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let window = self.kshow_slice().len();
+
+        for (depth, cell) in cells.iter().enumerate() {
+            write!(f, "| {:>width$} |", cell, width = width)?;
+            match depth {
+                _ if depth >= window => {}
+                0 if window == 1 => f.write_str(" < K")?,
+                0 => f.write_str(" \\ K")?,
+                _ if depth + 1 == window => f.write_str(" /")?,
+                _ => f.write_str(" |")?,
+            }
+            f.write_str("\n")?;
+        }
+        write!(f, "+{:->width$}+", "", width = width + 2)
+    }
+}
+
+/// Stacks are equal if they have the same element in the same order.
+impl<T: PartialEq, const K: usize, S: Storage<T>> PartialEq for KStack<T, K, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Eq, const K: usize, S: Storage<T>> Eq for KStack<T, K, S> {}
+
+impl<T: Hash, const K: usize, S: Storage<T>> Hash for KStack<T, K, S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state);
+    }
+}
+
+impl<T, const K: usize> IntoIterator for KStack<T, K> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self.0.into_iter().rev())
+    }
+}
+
+impl<'a, T, const K: usize, S: Storage<T>> IntoIterator for &'a KStack<T, K, S> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const K: usize, S: Storage<T>> IntoIterator for &'a mut KStack<T, K, S> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Index by depth from top of stack, so `stack[0]` is the top, see
+/// [`KStack::get`] for a version that doesn't panic.
+///
+/// # Panics
+/// Panics if `depth` is not less than length of stack.
+///
+/// # Example
+/// ```
+///     use kstack::KStack;
+///
+///     let mut stack = KStack::<i32, 2>::new();
+///
+///     stack.kpush(&[1, 2, 3]);
+///     stack[2] = 10;
+///
+///     assert_eq!(3, stack[0]);
+///     assert_eq!(10, stack[2]);
+/// ```
+impl<T, const K: usize, S: Storage<T>> Index<usize> for KStack<T, K, S> {
+    type Output = T;
+
+    fn index(&self, depth: usize) -> &T {
+        let len = self.len();
+        match self.get(depth) {
+            Some(item) => item,
+            None => out_of_range(depth, len),
+        }
+    }
+}
+
+impl<T, const K: usize, S: Storage<T>> IndexMut<usize> for KStack<T, K, S> {
+    fn index_mut(&mut self, depth: usize) -> &mut T {
+        let len = self.len();
+        match self.get_mut(depth) {
+            Some(item) => item,
+            None => out_of_range(depth, len),
+        }
+    }
+}
+
+#[cold]
+fn out_of_range(depth: usize, len: usize) -> ! {
+    panic!(
+        "depth {} is out of range for stack of {} element",
+        depth, len
+    )
+}
+
+/// 64-bit FNV-1a, a seedless hasher for [`KStack::khash`].
+struct FnvHasher(u64);
+
+impl Default for FnvHasher {
+    fn default() -> Self {
+        FnvHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for FnvHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Element of vector become the stack from bottom to top, so its last
+/// element is the top, without copying.
+///
+/// # Example
+/// ```
+///     use kstack::KStack;
+///
+///     let stack = KStack::<i32, 2>::from(vec![1, 2, 3]);
+///
+///     assert_eq!([Some(3), Some(2)], stack.kshow());
+///     assert_eq!(vec![1, 2, 3], Vec::from(stack));
+/// ```
+impl<T, const K: usize> From<Vec<T>> for KStack<T, K> {
+    fn from(items: Vec<T>) -> Self {
+        KStack::with_storage(items)
+    }
+}
+
+/// Element are from bottom to top, so the last one is the top.
+impl<T, const K: usize> From<KStack<T, K>> for Vec<T> {
+    fn from(stack: KStack<T, K>) -> Self {
+        stack.into_inner()
+    }
+}
+
+/// Items are pushed in iteration order, so the last item ends up on top.
+impl<T, const K: usize, S: Storage<T> + Default> FromIterator<T> for KStack<T, K, S> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut stack = Self::default();
+        stack.kpush_iter(iter);
+        stack
+    }
+}
+
+/// Items are pushed in iteration order, so the last item ends up on top.
+impl<T, const K: usize, S: Storage<T>> Extend<T> for KStack<T, K, S> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.kpush_iter(iter);
+    }
+}
+
+/// Items are pushed in iteration order, so the last item ends up on top.
+impl<'a, T: Copy + 'a, const K: usize, S: Storage<T>> Extend<&'a T> for KStack<T, K, S> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.kpush_iter(iter.into_iter().copied());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_len_capacity() {
+        let mut stack = KStack::<i32, 3>::with_capacity(4);
+        assert_eq!(0, stack.len());
+        assert!(stack.is_empty());
+        assert!(stack.capacity() >= 4);
+
+        stack.kpush(&[1, 2, 3, 4, 5]);
+        assert_eq!(5, stack.len());
+        assert!(!stack.is_empty());
+
+        stack.reserve(20);
+        assert!(stack.capacity() >= 25);
+
+        stack.kpop();
+        stack.shrink_to_fit();
+        assert_eq!(2, stack.len());
+        assert!(stack.capacity() >= 2);
+    }
+
+    #[test]
+    fn test_kpop() {
+        let mut stack = KStack::<i32, 3>::new();
+        stack.push(1);
+        stack.push(2);
+        stack.push(3);
+        stack.push(4);
+
+        assert_eq!([Some(4), Some(3), Some(2)], stack.kpop());
+        assert_eq!([Some(1), None, None], stack.kpop());
+    }
+
+    #[test]
+    fn test_kpop_exact() {
+        let mut stack = KStack::<String, 2>::new();
+        stack.push("a".to_string());
+        assert_eq!(None, stack.kpop_exact());
+        assert_eq!([Some(&"a".to_string()), None], stack.kshow_ref());
+
+        stack.push("b".to_string());
+        assert_eq!(Some(["b".to_string(), "a".to_string()]), stack.kpop_exact());
+        assert_eq!(None, stack.pop());
+    }
+
+    #[test]
+    fn test_kshow() {
+        let mut stack = KStack::<i32, 3>::new();
+        stack.push(1);
+        stack.push(2);
+        stack.push(3);
+        stack.push(4);
+
+        assert_eq!([Some(4), Some(3), Some(2)], stack.kshow());
+        assert_eq!([Some(4), Some(3), Some(2)], stack.kshow());
+    }
+
+    #[test]
+    fn test_kshow_underflow() {
+        let mut stack = KStack::<i32, 3>::new();
+        stack.push(1);
+        stack.push(2);
+        stack.push(3);
         stack.push(4);
 
         assert_eq!([Some(4), Some(3), Some(2)], stack.kshow());
@@ -187,6 +2457,45 @@ mod tests {
         assert_eq!([None, None, None], stack.kshow());
     }
 
+    #[test]
+    fn test_kshow_shared() {
+        let mut stack = KStack::<i32, 2>::new();
+        stack.kpush(&[1, 2, 3]);
+
+        let first = &stack;
+        let second = &stack;
+        assert_eq!(first.kshow(), second.kshow());
+    }
+
+    #[test]
+    fn test_kshow_mut() {
+        let mut stack = KStack::<i32, 3>::new();
+        stack.kpush(&[1, 2]);
+
+        for item in stack.kshow_mut().iter_mut().flatten() {
+            **item *= 10;
+        }
+
+        assert_eq!([Some(20), Some(10), None], stack.kshow());
+        assert_eq!(Some(20), stack.pop());
+    }
+
+    #[test]
+    fn test_kshow_slice() {
+        let mut stack = KStack::<i32, 3>::new();
+        assert!(stack.kshow_slice().is_empty());
+
+        stack.kpush(&[1, 2]);
+        assert_eq!(&[1, 2], stack.kshow_slice());
+
+        stack.kpush(&[3, 4]);
+        assert_eq!(&[2, 3, 4], stack.kshow_slice());
+
+        stack.kshow_slice_mut().reverse();
+        assert_eq!([Some(2), Some(3), Some(4)], stack.kpop());
+        assert_eq!(Some(1), stack.pop());
+    }
+
     #[test]
     fn test_kpush() {
         let mut stack = KStack::<i32, 3>::new();
@@ -199,6 +2508,770 @@ mod tests {
         assert_eq!(None, stack.pop());
     }
 
+    #[test]
+    fn test_non_copy() {
+        let mut stack = KStack::<String, 2>::new();
+        stack.kpush(&["a".to_string(), "b".to_string(), "c".to_string()]);
+
+        assert_eq!(
+            [Some("c".to_string()), Some("b".to_string())],
+            stack.kshow()
+        );
+        assert_eq!(
+            [Some(&"c".to_string()), Some(&"b".to_string())],
+            stack.kshow_ref()
+        );
+        assert_eq!([Some("c".to_string()), Some("b".to_string())], stack.kpop());
+        assert_eq!([Some("a".to_string()), None], stack.kpop());
+    }
+
+    #[test]
+    fn test_try_ops() {
+        let mut stack = KStack::<i32, 3>::new();
+        assert_eq!(
+            Err(KStackError::Underflow {
+                requested: 1,
+                available: 0
+            }),
+            stack.try_pop()
+        );
+
+        assert_eq!(Ok(()), stack.try_kpush(&[1, 2]));
+        assert_eq!(
+            Err(KStackError::Underflow {
+                requested: 3,
+                available: 2
+            }),
+            stack.try_kpop()
+        );
+        assert_eq!(Ok(2), stack.try_pop());
+
+        assert_eq!(Ok(()), stack.try_kpush(&[3, 4]));
+        assert_eq!(Ok([4, 3, 1]), stack.try_kpop());
+    }
+
+    #[test]
+    fn test_iter_order() {
+        let mut stack: KStack<i32, 2> = (1..=4).collect();
+        assert_eq!(vec![&4, &3, &2, &1], stack.iter().collect::<Vec<_>>());
+        assert_eq!(vec![&1, &2], stack.iter().rev().take(2).collect::<Vec<_>>());
+        assert_eq!(4, stack.iter().len());
+
+        for item in &mut stack {
+            *item += 10;
+        }
+
+        let mut shared = Vec::new();
+        for item in &stack {
+            shared.push(*item);
+        }
+        assert_eq!(vec![14, 13, 12, 11], shared);
+
+        assert_eq!(vec![14, 13, 12, 11], stack.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_iter_windows() {
+        let mut stack = KStack::<i32, 3>::new();
+        stack.kpush(&[1, 2]);
+        assert_eq!(0, stack.iter_windows().count());
+
+        stack.kpush(&[3, 4, 5]);
+        let windows: Vec<&[i32]> = stack.iter_windows().collect();
+        assert_eq!(vec![&[3, 4, 5][..], &[2, 3, 4], &[1, 2, 3]], windows);
+        assert_eq!(stack.kshow_slice(), windows[0]);
+    }
+
+    #[test]
+    fn test_kchunks() {
+        let stack: KStack<i32, 3> = (1..=7).collect();
+        let mut chunks = stack.kchunks();
+        assert_eq!(2, chunks.len());
+        assert_eq!(&[1], chunks.remainder());
+        assert_eq!(Some(&[5, 6, 7][..]), chunks.next());
+        assert_eq!(Some(&[2, 3, 4][..]), chunks.next());
+        assert_eq!(None, chunks.next());
+
+        let exact: KStack<i32, 3> = (1..=6).collect();
+        assert!(exact.kchunks().remainder().is_empty());
+        assert_eq!(2, exact.kchunks().count());
+    }
+
+    #[test]
+    fn test_into_kchunks() {
+        let stack: KStack<String, 2> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+        let mut chunks = stack.into_kchunks();
+        assert_eq!(1, chunks.len());
+        assert_eq!(&["a".to_string()], chunks.remainder());
+        assert_eq!(Some(["c".to_string(), "b".to_string()]), chunks.next());
+        assert_eq!(None, chunks.next());
+        assert_eq!(vec!["a".to_string()], chunks.into_remainder());
+
+        let empty = KStack::<i32, 2>::new().into_kchunks();
+        assert_eq!(0, empty.len());
+        assert!(empty.into_remainder().is_empty());
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut stack = KStack::<String, 2>::new();
+        stack.extend(vec!["a".to_string(), "b".to_string()]);
+        stack.extend(Some("c".to_string()));
+
+        assert_eq!([Some("c".to_string()), Some("b".to_string())], stack.kpop());
+        assert_eq!([Some("a".to_string()), None], stack.kpop());
+    }
+
+    #[test]
+    fn test_kpush_iter() {
+        let mut stack = KStack::<String, 2>::new();
+        stack.kpush_iter("a b c".split(' ').map(String::from));
+        stack.kpush_iter(None);
+
+        let mut numbers = KStack::<i32, 2>::new();
+        numbers.extend(&[1, 2, 3]);
+        assert_eq!([Some(3), Some(2)], numbers.kpop());
+
+        assert_eq!(3, stack.len());
+        assert_eq!([Some("c".to_string()), Some("b".to_string())], stack.kpop());
+        assert_eq!([Some("a".to_string()), None], stack.kpop());
+    }
+
+    #[test]
+    fn test_kpush_array() {
+        let mut stack = KStack::<String, 2>::new();
+        stack.kpush_array(["a".to_string(), "b".to_string()]);
+        stack.kpush_array(["c".to_string(), "d".to_string()]);
+
+        assert_eq!(4, stack.len());
+        assert_eq!(Some(["d".to_string(), "c".to_string()]), stack.kpop_exact());
+        assert_eq!(Some(["b".to_string(), "a".to_string()]), stack.kpop_exact());
+    }
+
+    #[test]
+    fn test_runtime_window() {
+        let mut stack = KStack::<i32, 2>::new();
+        assert!(stack.kshow_n(3).is_empty());
+        assert!(stack.kpop_n(3).is_empty());
+
+        assert_eq!(5, stack.kpush_n(5, 1..));
+        assert_eq!(&[3, 4, 5], stack.kshow_n(3));
+        assert_eq!(stack.kshow_slice(), stack.kshow_n(2));
+        assert!(stack.kshow_n(0).is_empty());
+
+        assert_eq!(vec![5, 4, 3], stack.kpop_n(3));
+        assert_eq!(vec![2, 1], stack.kpop_n(3));
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn test_window_as() {
+        let mut stack = KStack::<i32, 3>::new();
+        stack.kpush(&[1, 2, 3, 4, 5, 6]);
+
+        assert_eq!(
+            [Some(6), Some(5), Some(4), Some(3), Some(2)],
+            stack.kshow_as::<5>()
+        );
+        assert_eq!([Some(6)], stack.kshow_as::<1>());
+        assert_eq!([None; 0], stack.kshow_as::<0>());
+        assert_eq!(6, stack.len());
+
+        assert_eq!(
+            [Some(6), Some(5), Some(4), Some(3), Some(2)],
+            stack.kpop_as::<5>()
+        );
+        assert_eq!([Some(1), None], stack.kpop_as::<2>());
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn test_window_manipulation() {
+        let mut stack = KStack::<i32, 2>::new();
+        stack.push(1);
+        let underflow = |requested| KStackError::Underflow {
+            requested,
+            available: 1,
+        };
+        assert_eq!(Err(underflow(2)), stack.kdup());
+        assert_eq!(Err(underflow(4)), stack.kswap());
+        assert_eq!(Err(underflow(2)), stack.kdrop());
+        assert_eq!(Err(underflow(4)), stack.kover());
+        assert_eq!(1, stack.len());
+
+        stack.push(2);
+        assert_eq!(Ok(()), stack.kdup());
+        assert_eq!(&[1, 2, 1, 2], stack.kshow_n(4));
+
+        stack.kpush(&[3, 4]);
+        assert_eq!(Ok(()), stack.kswap());
+        assert_eq!(&[1, 2, 3, 4, 1, 2], stack.kshow_n(6));
+
+        assert_eq!(Ok(()), stack.kover());
+        assert_eq!(&[1, 2, 3, 4, 1, 2, 3, 4], stack.kshow_n(8));
+
+        assert_eq!(Ok(()), stack.kdrop());
+        assert_eq!(Ok(()), stack.kdrop());
+        assert_eq!(&[1, 2, 3, 4], stack.kshow_n(8));
+    }
+
+    #[test]
+    fn test_krotate_kreverse() {
+        let mut stack = KStack::<i32, 3>::new();
+        stack.krotate(2);
+        stack.kreverse();
+        assert!(stack.is_empty());
+
+        stack.kpush(&[1, 2]);
+        stack.krotate(3);
+        assert_eq!(&[2, 1], stack.kshow_slice());
+
+        stack.kpush(&[3, 4]);
+        stack.krotate(0);
+        assert_eq!(&[2, 1, 3, 4], stack.kshow_n(4));
+        stack.krotate(2);
+        assert_eq!(&[2, 4, 1, 3], stack.kshow_n(4));
+        stack.krotate(4);
+        assert_eq!(&[2, 1, 3, 4], stack.kshow_n(4));
+
+        stack.kreverse();
+        assert_eq!(&[2, 4, 3, 1], stack.kshow_n(4));
+    }
+
+    #[test]
+    fn test_kmap_kretain_window() {
+        let mut stack = KStack::<String, 2>::new();
+        stack.kpush_iter(["a", "b", "c"].iter().map(|s| s.to_string()));
+
+        let mut visited = Vec::new();
+        stack.kmap(|item| {
+            visited.push(item.clone());
+            item.push('!');
+        });
+        assert_eq!(vec!["c", "b"], visited);
+        assert_eq!(vec!["c!", "b!", "a"], stack.iter().collect::<Vec<_>>());
+
+        stack.kretain_window(|item| item != "c!");
+        assert_eq!(vec!["b!", "a"], stack.iter().collect::<Vec<_>>());
+
+        stack.kretain_window(|_| false);
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn test_apply_binop() {
+        let mut stack = KStack::<String, 2>::new();
+        stack.push("a".to_string());
+        assert_eq!(
+            Err(KStackError::Underflow {
+                requested: 2,
+                available: 1
+            }),
+            stack.apply_binop(|a, b| a + &b)
+        );
+        assert_eq!(1, stack.len());
+
+        stack.push("b".to_string());
+        assert_eq!(Ok(()), stack.apply_binop(|a, b| a + &b));
+        assert_eq!(Some("ab".to_string()), stack.pop());
+        assert!(stack.is_empty());
+    }
+
+    // unsafe window handling, also run under
+    // `cargo +nightly miri test --lib kpop_raw`.
+    #[test]
+    fn test_kpop_raw() {
+        let mut stack = KStack::<f64, 4>::new();
+        stack.kpush(&[0.5, 1.5, 2.5, 3.5, 4.5, 5.5]);
+        assert_eq!(
+            Some([5.5, 4.5, 3.5, 2.5]),
+            stack.kpop_raw().into_array().ok()
+        );
+        let window = stack.kpop_raw();
+        assert_eq!(&[1.5, 0.5], window.as_slice());
+        assert!(!window.is_full());
+        assert!(stack.is_empty());
+
+        let mut stack = KStack::<String, 3>::new();
+        stack.kpush_iter(["a", "b", "c", "d"].iter().map(|s| s.to_string()));
+        let mut window = stack.kpop_raw();
+        window.as_mut_slice()[0].push('!');
+        assert_eq!("[\"d!\", \"c\", \"b\"]", format!("{:?}", window));
+        drop(window);
+        let window = stack.kpop_raw().into_array().unwrap_err();
+        assert_eq!(1, window.len());
+        assert_eq!("a", window.as_slice()[0]);
+        drop(window);
+        assert!(stack.kpop_raw().is_empty());
+    }
+
+    #[test]
+    fn test_pops_move_once() {
+        use std::rc::Rc;
+
+        let item = Rc::new(());
+        let mut stack = KStack::<Rc<()>, 3>::new();
+        stack.kpush_iter((0..10).map(|_| Rc::clone(&item)));
+
+        let window = stack.kpop();
+        assert_eq!(11, Rc::strong_count(&item));
+        drop(window);
+        assert_eq!(Some(3), stack.kpop_exact().map(|window| window.len()));
+        assert_eq!(2, stack.kpop_n(2).len());
+        assert_eq!(2, stack.kpop_as::<5>().iter().flatten().count());
+        assert!(stack.is_empty());
+        assert_eq!(1, Rc::strong_count(&item));
+
+        let mut stack = CowKStack::<Rc<()>, 2>::default();
+        stack.kpush(&[Rc::clone(&item), Rc::clone(&item), Rc::clone(&item)]);
+        let snapshot = stack.clone();
+        assert_eq!([true, true], stack.kpop().map(|item| item.is_some()));
+        assert_eq!(1, stack.len());
+        assert_eq!(3, snapshot.len());
+        drop((stack, snapshot));
+        assert_eq!(1, Rc::strong_count(&item));
+    }
+
+    #[test]
+    fn test_kpop_into() {
+        let mut stack = KStack::<String, 3>::new();
+        stack.kpush_iter((1..=5).map(|i| i.to_string()));
+
+        let mut buffer = vec![String::new(); 2];
+        assert_eq!(2, stack.kpop_into(&mut buffer));
+        assert_eq!(vec!["5", "4"], buffer);
+
+        let mut buffer = vec![String::new(); 4];
+        assert_eq!(3, stack.kpop_into(&mut buffer));
+        assert_eq!(vec!["3", "2", "1", ""], buffer);
+        assert_eq!(0, stack.kpop_into(&mut buffer));
+        assert_eq!(0, stack.kpop_into(&mut []));
+
+        let mut stack = KStack::<u8, 4>::new();
+        let mut buffer = [MaybeUninit::uninit(); 8];
+        stack.kpush(&[1, 2, 3, 4, 5]);
+        assert_eq!(4, stack.kpop_into_uninit(&mut buffer));
+        let written: Vec<u8> = buffer[..4]
+            .iter()
+            .map(|item| unsafe { item.assume_init() })
+            .collect();
+        assert_eq!(vec![5, 4, 3, 2], written);
+        assert_eq!(1, stack.len());
+    }
+
+    #[test]
+    fn test_peek_get() {
+        let mut stack = KStack::<i32, 2>::new();
+        assert_eq!(None, stack.peek_mut());
+        assert_eq!(None, stack.get(0));
+        assert_eq!(None, stack.get(usize::MAX));
+
+        stack.kpush(&[1, 2, 3, 4]);
+        *stack.peek_mut().unwrap() += 10;
+        *stack.get_mut(3).unwrap() += 20;
+        assert_eq!(None, stack.get_mut(4));
+
+        let by_depth: Vec<_> = (0..4).map(|depth| stack.get(depth)).collect();
+        assert_eq!(vec![Some(&14), Some(&3), Some(&2), Some(&21)], by_depth);
+        assert_eq!(stack.peek(), stack.get(0));
+        assert_eq!(stack.iter().nth(2), stack.get(2));
+    }
+
+    #[test]
+    fn test_index_by_depth() {
+        let mut stack = KStack::<i32, 2>::new();
+        stack.kpush(&[1, 2, 3]);
+        stack[0] *= 10;
+        stack[1] += 5;
+        assert_eq!([30, 7, 1], [stack[0], stack[1], stack[2]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "depth 3 is out of range for stack of 3 element")]
+    fn test_index_out_of_range() {
+        let mut stack = KStack::<i32, 2>::new();
+        stack.kpush(&[1, 2, 3]);
+        let _ = stack[3];
+    }
+
+    #[test]
+    #[should_panic(expected = "depth 0 is out of range for stack of 0 element")]
+    fn test_index_mut_empty() {
+        let mut stack = KStack::<i32, 2>::new();
+        stack[0] = 1;
+    }
+
+    #[test]
+    fn test_bulk_removal() {
+        let mut stack: KStack<String, 2> = (1..=6).map(|i| i.to_string()).collect();
+
+        let mut drain = stack.drain_top(3);
+        assert_eq!(3, drain.len());
+        assert_eq!(Some("6".to_string()), drain.next());
+        drop(drain);
+        assert_eq!(3, stack.len());
+        assert_eq!(3, stack.drain_top(10).count());
+        assert!(stack.is_empty());
+
+        stack.kpush_iter((1..=4).map(|i| i.to_string()));
+        stack.truncate_to_depth(10);
+        assert_eq!(4, stack.len());
+        stack.truncate_to_depth(2);
+        assert_eq!(&["1", "2"], stack.kshow_slice());
+        stack.clear();
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut stack = KStack::<(u32, &str), 2>::new();
+        stack.kpush(&[(5, "a"), (1, "b"), (7, "c"), (2, "d"), (9, "e")]);
+
+        let mut seen = Vec::new();
+        stack.retain(|&(time, name)| {
+            seen.push(name);
+            time > 3
+        });
+        assert_eq!(vec!["a", "b", "c", "d", "e"], seen);
+        assert_eq!(vec![(9, "e"), (7, "c"), (5, "a")], stack.kpop_n(5));
+
+        stack.retain(|_| false);
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn test_append_split_off() {
+        let mut stack: KStack<String, 2> = (1..=5).map(|i| i.to_string()).collect();
+        let mut top = stack.split_off(2);
+        assert_eq!(&["1", "2", "3"], stack.kshow_n(5));
+        assert_eq!(&["4", "5"], top.kshow_n(5));
+
+        let mut all = stack.split_off(10);
+        assert!(stack.is_empty());
+        assert!(stack.split_off(1).is_empty());
+        all.append(&mut top);
+        assert!(top.is_empty());
+        assert_eq!(&["1", "2", "3", "4", "5"], all.kshow_n(5));
+
+        let mut empty = KStack::new();
+        all.append(&mut empty);
+        assert_eq!(5, all.len());
+    }
+
+    #[test]
+    fn test_split_window() {
+        let mut stack = KStack::<i32, 3>::new();
+        stack.kpush(&[1, 2]);
+        let (window, rest) = stack.split_window();
+        assert_eq!(&[1, 2], window.kshow_slice());
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn test_conversions() {
+        let items = vec![1, 2, 3];
+        let pointer = items.as_ptr();
+        let mut stack: KStack<i32, 2> = items.into();
+        assert_eq!(pointer, stack.as_slice().as_ptr());
+        assert_eq!(stack.kshow_slice(), &stack.as_slice()[1..]);
+
+        stack.as_mut_slice()[0] = 10;
+        let items: Vec<i32> = stack.into();
+        assert_eq!(vec![10, 2, 3], items);
+        assert_eq!(pointer, items.as_ptr());
+    }
+
+    #[test]
+    fn test_std_traits() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::HashSet;
+
+        fn hash_of(stack: &KStack<i32, 2>) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            stack.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let empty = KStack::<i32, 2>::default();
+        assert_eq!("[]", format!("{:?}", empty));
+
+        let stack = KStack::<i32, 2>::from(vec![1, 2, 3]);
+        let same = stack.clone();
+        assert_eq!(stack, same);
+        assert_eq!(hash_of(&stack), hash_of(&same));
+        assert_ne!(stack, KStack::from(vec![3, 2, 1]));
+        assert_ne!(stack, KStack::from(vec![1, 2]));
+
+        let set: HashSet<_> = vec![stack, same, empty].into_iter().collect();
+        assert_eq!(2, set.len());
+    }
+
+    #[test]
+    fn test_display() {
+        let mut stack = KStack::<&str, 1>::new();
+        assert_eq!("+---+", stack.to_string());
+
+        stack.kpush(&["ab", "c"]);
+        assert_eq!("|  c | < K\n| ab |\n+----+", stack.to_string());
+
+        let stack = KStack::<i32, 4>::from(vec![7, 8]);
+        assert_eq!("| 8 | \\ K\n| 7 | /\n+---+", stack.to_string());
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let stack = KStack::<i32, 2>::new();
+        assert_eq!(
+            "digraph kstack {\n    node [shape=box];\n}\n",
+            stack.to_dot()
+        );
+
+        let stack = KStack::<&str, 2>::from(vec!["a\\b", "c\nd", "e"]);
+        let dot = stack.to_dot();
+        assert!(dot.contains("n0 [label=\"e\", style=filled"));
+        assert!(dot.contains("n1 [label=\"c\\nd\", style=filled"));
+        assert!(dot.contains("n2 [label=\"a\\\\b\"];"));
+        assert!(dot.contains("n0 -> n1;\n    n1 -> n2;\n}"));
+    }
+
+    #[test]
+    fn test_kshow_at() {
+        let stack = KStack::<i32, 3>::from((1..=7).collect::<Vec<_>>());
+        let windows: Vec<_> = (0..)
+            .step_by(3)
+            .map(|offset| stack.kshow_at(offset).as_slice())
+            .take_while(|window| !window.is_empty())
+            .collect();
+        assert_eq!(vec![&[5, 6, 7][..], &[2, 3, 4], &[1]], windows);
+        assert_eq!(stack.kshow_slice(), stack.kshow_at(0).as_slice());
+        assert!(stack.kshow_at(usize::MAX).is_empty());
+    }
+
+    #[test]
+    fn test_kbottom() {
+        let mut stack = KStack::<String, 2>::new();
+        assert!(stack.kbottom_show().is_empty());
+        assert_eq!([None, None], stack.kbottom_pop());
+
+        stack.kpush_iter((1..=5).map(|i| i.to_string()));
+        assert_eq!(["1", "2"], stack.kbottom_show());
+        assert_eq!(
+            [Some("1".to_string()), Some("2".to_string())],
+            stack.kbottom_pop()
+        );
+        assert_eq!(vec!["5", "4", "3"], stack.iter().collect::<Vec<_>>());
+
+        stack.push("6".to_string());
+        assert_eq!(["3", "4"], stack.kbottom_show());
+        assert_eq!(&["5", "6"], stack.kshow_slice());
+    }
+
+    #[test]
+    fn test_khash() {
+        let mut stack = KStack::<u8, 2>::new();
+        let empty = stack.khash();
+        assert_eq!(empty, KStack::<u8, 2>::new().khash());
+
+        stack.kpush(&[1, 2, 3]);
+        assert_ne!(empty, stack.khash());
+        assert_eq!(KStack::<u8, 2>::from(vec![2, 3]).khash(), stack.khash());
+        assert_ne!(KStack::<u8, 2>::from(vec![3, 2]).khash(), stack.khash());
+
+        let mut hasher = FnvHasher::default();
+        hasher.write(b"a");
+        assert_eq!(0xaf63_dc4c_8601_ec8c, hasher.finish());
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_tracing() {
+        use std::sync::{Arc, Mutex};
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+
+        struct Fields(String);
+
+        impl Visit for Fields {
+            fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+                write!(self.0, " {}={:?}", field.name(), value).unwrap();
+            }
+        }
+
+        struct Collector(Arc<Mutex<Vec<String>>>);
+
+        impl Subscriber for Collector {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, _: &Attributes<'_>) -> Id {
+                Id::from_u64(1)
+            }
+
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+            fn event(&self, event: &Event<'_>) {
+                let mut fields = Fields(String::new());
+                event.record(&mut fields);
+                self.0.lock().unwrap().push(fields.0);
+            }
+
+            fn enter(&self, _: &Id) {}
+
+            fn exit(&self, _: &Id) {}
+        }
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let mut stack = KStack::<i32, 2>::new();
+        tracing::subscriber::with_default(Collector(events.clone()), || {
+            stack.kpush(&[1, 2, 3]);
+            stack.push(4);
+            stack.kpop();
+        });
+
+        assert_eq!(
+            vec![
+                " message=kpush pushed=3 depth_before=0 depth_after=3",
+                " message=kpop popped=2 depth_before=4 depth_after=2",
+            ],
+            *events.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rewindow() {
+        let mut stack = KStack::<String, 1>::new();
+        stack.kpush_iter((0..4).map(|i| i.to_string()));
+        let pointer = stack.as_slice().as_ptr();
+
+        let mut wide = stack.rewindow::<3>();
+        assert_eq!(["1", "2", "3"], wide.kshow_slice());
+        assert_eq!(pointer, wide.as_slice().as_ptr());
+
+        wide.kpop();
+        let narrow = wide.rewindow::<1>();
+        assert_eq!(["0"], narrow.kshow_slice());
+        assert_eq!(1, narrow.len());
+    }
+
+    #[test]
+    fn test_drain_top_while() {
+        let mut stack = KStack::<i32, 2>::from(vec![1, 2, 3, 4]);
+        {
+            let mut drained = stack.drain_top_while(|&x| x > 1);
+            assert_eq!(3, drained.len());
+            assert_eq!(Some(4), drained.next());
+        }
+        assert_eq!(&[1], stack.as_slice());
+        assert_eq!(0, stack.drain_top_while(|_| false).count());
+        assert_eq!(vec![1], stack.drain_top_while(|_| true).collect::<Vec<_>>());
+        assert!(stack.drain_top_while(|_| true).next().is_none());
+
+        let mut stack = KStack::<i32, 2>::from(vec![1, 2, 3]);
+        assert_eq!([Some(3), Some(2)], stack.kpop_while(|_| true));
+        assert_eq!([Some(1), None], stack.kpop_while(|_| true));
+        assert!(stack.is_empty());
+
+        let mut stack = KStack::<i32, 2>::from(vec![1, 2, 3, 4, 5]);
+        let mut calls = 0;
+        assert_eq!(
+            [Some(5), Some(4)],
+            stack.kpop_while(|_| {
+                calls += 1;
+                true
+            })
+        );
+        assert_eq!(2, calls);
+    }
+
+    #[test]
+    fn test_search() {
+        let stack = KStack::<i32, 2>::new();
+        assert!(!stack.contains(&0));
+        assert_eq!(None, stack.position_from_top(|_| true));
+        assert_eq!(None, stack.find_in_window(|_| true));
+
+        let stack = KStack::<i32, 2>::from(vec![5, 5, 1, 2]);
+        assert!(stack.contains(&5));
+        assert_eq!(Some(2), stack.position_from_top(|&x| x == 5));
+        assert_eq!(Some(0), stack.position_from_top(|_| true));
+        assert_eq!(Some(&2), stack.find_in_window(|_| true));
+        assert_eq!(None, stack.find_in_window(|&x| x == 5));
+    }
+
+    #[test]
+    fn test_sort() {
+        let mut stack = KStack::<i32, 2>::from(vec![4, -1, 9, 0]);
+        assert!(!stack.is_sorted());
+        stack.sort_by(|a, b| b.cmp(a));
+        assert_eq!(&[9, 4, 0, -1], stack.as_slice());
+        stack.sort_by_key(|x| x.abs());
+        assert_eq!(&[0, -1, 4, 9], stack.as_slice());
+        stack.sort();
+        assert!(stack.is_sorted());
+        assert_eq!(Ok(3), stack.binary_search_by(|x| x.cmp(&9)));
+        assert_eq!(Err(0), stack.binary_search(&-5));
+        assert!(KStack::<i32, 2>::new().is_sorted());
+    }
+
+    #[test]
+    fn test_ksort_kdedup() {
+        let mut stack = KStack::<i32, 4>::new();
+        stack.ksort();
+        stack.kdedup();
+        assert!(stack.is_empty());
+
+        stack.kpush(&[5, 3, 3, 1, 3]);
+        stack.ksort_by(|a, b| b.cmp(a));
+        assert_eq!(&[5, 3, 3, 3, 1], stack.as_slice());
+        stack.kdedup();
+        assert_eq!(&[5, 3, 1], stack.as_slice());
+        stack.ksort();
+        assert_eq!(&[1, 3, 5], stack.as_slice());
+
+        let mut stack = KStack::<i32, 4>::from(vec![7, 7, 7, 7]);
+        stack.kdedup();
+        assert_eq!(&[7], stack.as_slice());
+    }
+
+    #[test]
+    fn test_kreduce() {
+        let mut stack = KStack::<String, 2>::new();
+        stack.kpush_iter(["a", "b", "c"].iter().map(|s| s.to_string()));
+
+        stack.kreduce(|[top, below]| below + &top).unwrap();
+        assert_eq!(&["a", "bc"], stack.as_slice());
+        stack.kreduce(|[top, below]| below + &top).unwrap();
+        assert_eq!(&["abc"], stack.as_slice());
+        assert_eq!(
+            Err(KStackError::Underflow {
+                requested: 2,
+                available: 1
+            }),
+            stack.kreduce(|[top, _]| top)
+        );
+    }
+
+    #[test]
+    fn test_apply2() {
+        let mut stack = KStack::<u32, 2>::from(vec![10, 4, 3]);
+        stack.apply2(|a, b| a - b);
+        assert_eq!(&[10, 1], stack.as_slice());
+        stack.apply2(|a, b| a.pow(b));
+        assert_eq!(&[10], stack.as_slice());
+        assert!(stack.apply_binop(|a, b| a + b).is_err());
+        assert_eq!(&[10], stack.as_slice());
+    }
+
+    #[test]
+    #[should_panic(expected = "apply2: stack underflow: requested 2 element but only 0 available")]
+    fn test_apply2_underflow() {
+        KStack::<u32, 2>::new().apply2(|a, b| a + b);
+    }
+
     #[test]
     fn test_push_pop() {
         let mut stack = KStack::<i32, 3>::new();
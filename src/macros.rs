@@ -0,0 +1,51 @@
+/// Make a [`KStack`](crate::KStack) from a list of element, pushed in
+/// order so the last one ends up on top, like [`vec!`](alloc::vec!).
+///
+/// Window size is given after a `;` as `K = ...`, or inferred from the
+/// type the stack is used as.
+///
+/// # Example
+/// ```
+///     use kstack::{kstack, KStack};
+///
+///     let stack = kstack![1, 2, 3, 4; K = 3];
+///     assert_eq!([Some(4), Some(3), Some(2)], stack.kshow());
+///
+///     let stack: KStack<&str, 2> = kstack!["a", "b", "c"];
+///     assert_eq!([Some("c"), Some("b")], stack.kshow());
+///
+///     let stack: KStack<u8, 2> = kstack![];
+///     assert!(stack.is_empty());
+/// ```
+#[macro_export]
+macro_rules! kstack {
+    ($($item:expr),* $(,)?; K = $k:expr) => {
+        $crate::KStack::<_, { $k }>::from($crate::__private::vec![$($item),*])
+    };
+    ($($item:expr),* $(,)?) => {
+        $crate::KStack::from($crate::__private::vec![$($item),*])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::KStack;
+
+    #[test]
+    fn test_kstack() {
+        let stack = kstack!["a".to_string(), "b".to_string(),; K = 1];
+        assert_eq!([Some("b".to_string())], stack.kshow());
+
+        let mut stack = kstack![; K = 4];
+        assert!(stack.is_empty());
+        stack.push(1u8);
+        assert_eq!([Some(1), None, None, None], stack.kshow());
+
+        let stack: KStack<i32, 2> = kstack![1, 2, 3];
+        assert_eq!(&[2, 3], stack.kshow_slice());
+
+        const WINDOW: usize = 2;
+        let stack = kstack![1, 2, 3; K = WINDOW + 1];
+        assert_eq!([Some(3), Some(2), Some(1)], stack.kshow());
+    }
+}
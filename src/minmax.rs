@@ -0,0 +1,229 @@
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use crate::KStack;
+
+/// [`KStack`] that tracks smallest and largest of K top element, so
+/// [`kmin`](Self::kmin) and [`kmax`](Self::kmax) are O(1).
+///
+/// Extrema of the window ending at each element are recorded when it's
+/// pushed, using monotonic deques of the current window, so pushes are
+/// amortized O(1). popping is O(1) too, but the next push after pops
+/// rebuilds the deques in O(K).
+pub struct MinMaxKStack<T, const K: usize> {
+    stack: KStack<T, K>,
+    /// Index of smallest and largest element of the window ending at each
+    /// element.
+    extrema: Vec<(usize, usize)>,
+    min_deque: VecDeque<usize>,
+    max_deque: VecDeque<usize>,
+    /// Whether deques are out of date because of a pop.
+    dirty: bool,
+}
+
+impl<T: Ord, const K: usize> MinMaxKStack<T, K> {
+    /// Make a new MinMaxKStack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::MinMaxKStack;
+    ///
+    ///     let mut stack = MinMaxKStack::<i32, 3>::new();
+    /// ```
+    pub fn new() -> Self {
+        MinMaxKStack {
+            stack: KStack::new(),
+            extrema: Vec::new(),
+            min_deque: VecDeque::new(),
+            max_deque: VecDeque::new(),
+            dirty: false,
+        }
+    }
+
+    /// Get underlying stack, for all the read-only operations.
+    pub fn as_kstack(&self) -> &KStack<T, K> {
+        &self.stack
+    }
+
+    /// Consume and return underlying stack.
+    pub fn into_kstack(self) -> KStack<T, K> {
+        self.stack
+    }
+
+    /// Number of element on stack.
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Whether there is no element on stack.
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    /// Smallest of K top element on stack, the topmost one if several are
+    /// equal.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::MinMaxKStack;
+    ///
+    ///     let mut stack = MinMaxKStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 3, 2, 4]);
+    ///     assert_eq!(Some(&2), stack.kmin());
+    ///
+    ///     stack.pop();
+    ///     assert_eq!(Some(&1), stack.kmin());
+    /// ```
+    pub fn kmin(&self) -> Option<&T> {
+        let &(min, _) = self.extrema.last()?;
+        Some(&self.stack.0[min])
+    }
+
+    /// Largest of K top element on stack, the topmost one if several are
+    /// equal.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::MinMaxKStack;
+    ///
+    ///     let mut stack = MinMaxKStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[5, 3, 2, 4]);
+    ///     assert_eq!(Some(&4), stack.kmax());
+    ///
+    ///     stack.pop();
+    ///     assert_eq!(Some(&5), stack.kmax());
+    /// ```
+    pub fn kmax(&self) -> Option<&T> {
+        let &(_, max) = self.extrema.last()?;
+        Some(&self.stack.0[max])
+    }
+
+    /// Remove and get single element on top of stack.
+    pub fn pop(&mut self) -> Option<T> {
+        let item = self.stack.pop()?;
+        self.extrema.pop();
+        self.dirty = true;
+        Some(item)
+    }
+
+    /// Push a single element to stack.
+    pub fn push(&mut self, item: T) {
+        let index = self.stack.len();
+        if self.dirty {
+            self.min_deque.clear();
+            self.max_deque.clear();
+            for previous in index.saturating_sub(K)..index {
+                self.track(previous);
+            }
+            self.dirty = false;
+        }
+
+        self.stack.push(item);
+        self.track(index);
+        // deques are never empty right after tracking an element.
+        self.extrema.push((self.min_deque[0], self.max_deque[0]));
+    }
+
+    /// Removes and return K top element on stack.
+    pub fn kpop(&mut self) -> [Option<T>; K] {
+        core::array::from_fn(|_| self.pop())
+    }
+
+    /// return references to K top element on stack.
+    pub fn kshow_ref(&self) -> [Option<&T>; K] {
+        self.stack.kshow_ref()
+    }
+
+    /// Push K element to stack.
+    pub fn kpush(&mut self, items: &[T])
+    where
+        T: Clone,
+    {
+        self.kpush_iter(items.iter().cloned());
+    }
+
+    /// Push all element of an iterator to stack.
+    pub fn kpush_iter<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        items.into_iter().for_each(|item| self.push(item));
+    }
+
+    /// Add element at `index` as the newest element of window in deques.
+    fn track(&mut self, index: usize) {
+        let items = &self.stack.0;
+        while matches!(self.min_deque.back(), Some(&back) if items[back] >= items[index]) {
+            self.min_deque.pop_back();
+        }
+        while matches!(self.max_deque.back(), Some(&back) if items[back] <= items[index]) {
+            self.max_deque.pop_back();
+        }
+        self.min_deque.push_back(index);
+        self.max_deque.push_back(index);
+
+        let start = (index + 1).saturating_sub(K);
+        while matches!(self.min_deque.front(), Some(&front) if front < start) {
+            self.min_deque.pop_front();
+        }
+        while matches!(self.max_deque.front(), Some(&front) if front < start) {
+            self.max_deque.pop_front();
+        }
+    }
+}
+
+impl<T: Ord, const K: usize> Default for MinMaxKStack<T, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive_min<const K: usize>(stack: &MinMaxKStack<u32, K>) -> Option<&u32> {
+        stack.as_kstack().kshow_slice().iter().min()
+    }
+
+    fn naive_max<const K: usize>(stack: &MinMaxKStack<u32, K>) -> Option<&u32> {
+        stack.as_kstack().kshow_slice().iter().max()
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut stack = MinMaxKStack::<i32, 3>::new();
+        assert_eq!(None, stack.kmin());
+        assert_eq!(None, stack.kmax());
+        assert_eq!(None, stack.pop());
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut stack = MinMaxKStack::<u32, 4>::new();
+        let mut seed = 17u32;
+        for step in 0..2000 {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            match seed % 5 {
+                0 | 1 if step % 7 != 0 => {
+                    stack.pop();
+                }
+                _ => stack.push(seed / 7 % 50),
+            }
+            assert_eq!(naive_min(&stack), stack.kmin());
+            assert_eq!(naive_max(&stack), stack.kmax());
+        }
+    }
+
+    #[test]
+    fn test_kpush_kpop() {
+        let mut stack = MinMaxKStack::<u32, 2>::new();
+        stack.kpush(&[9, 1, 5, 7]);
+        assert_eq!(Some(&5), stack.kmin());
+        assert_eq!(Some(&7), stack.kmax());
+
+        assert_eq!([Some(7), Some(5)], stack.kpop());
+        assert_eq!(Some(&1), stack.kmin());
+        assert_eq!(Some(&9), stack.kmax());
+        assert_eq!([Some(&1), Some(&9)], stack.kshow_ref());
+    }
+}
@@ -0,0 +1,317 @@
+//! Memory-mapped file storage, for stacks larger than memory, enabled by
+//! `mmap` feature.
+
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::mem;
+use std::path::Path;
+
+use bytemuck::Pod;
+use memmap2::MmapMut;
+
+use crate::storage::sealed::Sealed;
+use crate::{KStack, Storage};
+
+/// Bytes before the first element, holding number of element and size of
+/// an element. it keeps element aligned for any alignment up to 64.
+const HEADER: usize = 64;
+
+/// Element pushed in a new file before it has to grow.
+const INITIAL_CAPACITY: usize = 64;
+
+/// [`KStack`] stored in a memory-mapped file.
+pub type MmapKStack<T, const K: usize> = KStack<T, K, MmapStorage<T>>;
+
+/// [`Storage`] keeping element in a memory-mapped file, so the OS pages
+/// them in and out as needed.
+///
+/// File grows by doubling when it's full. changes are written back by the
+/// OS eventually, use [`flush`](Self::flush) to make sure they are on
+/// disk.
+///
+/// # Panics
+/// Pushing panics if file can't grow, like [`Vec`] does when out of
+/// memory. use [`reserve_exact`](Self::reserve_exact) to grow it up front
+/// and handle the error.
+pub struct MmapStorage<T> {
+    file: File,
+    map: MmapMut,
+    len: usize,
+    capacity: usize,
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<T: Pod> MmapStorage<T> {
+    /// Create an empty storage at `path`, replacing the file if it exists.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::mmap::{MmapKStack, MmapStorage};
+    ///     use kstack::KStack;
+    ///
+    ///     let path = std::env::temp_dir().join("kstack-doc-create.stack");
+    ///     let mut stack: MmapKStack<u64, 2> = KStack::with_storage(MmapStorage::create(&path)?);
+    ///
+    ///     stack.kpush(&[1, 2, 3]);
+    ///     stack.storage().flush()?;
+    ///
+    ///     assert_eq!([Some(3), Some(2)], stack.kshow());
+    ///     # std::fs::remove_file(&path)?;
+    ///     # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        let mut storage = Self::map(file, 0, 0)?;
+        storage.write_header();
+        storage.reserve_exact(INITIAL_CAPACITY)?;
+        Ok(storage)
+    }
+
+    /// Open a storage created by [`create`](Self::create), with element
+    /// it had when it was last changed.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::mmap::{MmapKStack, MmapStorage};
+    ///     use kstack::KStack;
+    ///
+    ///     let path = std::env::temp_dir().join("kstack-doc-open.stack");
+    ///     {
+    ///         let mut stack: MmapKStack<u32, 2> = KStack::with_storage(MmapStorage::create(&path)?);
+    ///         stack.kpush(&[1, 2, 3]);
+    ///     }
+    ///
+    ///     let mut stack: MmapKStack<u32, 2> = KStack::with_storage(MmapStorage::open(&path)?);
+    ///     assert_eq!([Some(3), Some(2)], stack.kpop());
+    ///     # std::fs::remove_file(&path)?;
+    ///     # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+        let file_len = file.metadata()?.len() as usize;
+        let invalid = |message| io::Error::new(io::ErrorKind::InvalidData, message);
+        if file_len < HEADER {
+            return Err(invalid("file is too short for a stack header"));
+        }
+
+        let storage = Self::map(file, 0, 0)?;
+        let capacity = (file_len - HEADER) / Self::item_size();
+        let len = storage.read_header(0);
+        if storage.read_header(1) != Self::item_size() as u64 {
+            return Err(invalid("stack was created with a different element size"));
+        }
+        if len > capacity as u64 {
+            return Err(invalid("stack length is beyond end of file"));
+        }
+        Ok(MmapStorage {
+            len: len as usize,
+            capacity,
+            ..storage
+        })
+    }
+
+    /// Grow file to hold at least `additional` more element.
+    pub fn reserve_exact(&mut self, additional: usize) -> io::Result<()> {
+        let capacity = self
+            .len
+            .checked_add(additional)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "capacity overflow"))?;
+        if capacity <= self.capacity {
+            return Ok(());
+        }
+
+        let bytes = capacity
+            .checked_mul(Self::item_size())
+            .and_then(|bytes| bytes.checked_add(HEADER))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "capacity overflow"))?;
+        self.map.flush()?;
+        self.file.set_len(bytes as u64)?;
+        // SAFETY: see `map`.
+        self.map = unsafe { MmapMut::map_mut(&self.file)? };
+        self.capacity = capacity;
+        Ok(())
+    }
+
+    /// Write changes to disk, waiting until it's done.
+    pub fn flush(&self) -> io::Result<()> {
+        self.map.flush()
+    }
+
+    /// Start writing changes to disk, without waiting for it.
+    pub fn flush_async(&self) -> io::Result<()> {
+        self.map.flush_async()
+    }
+
+    fn map(file: File, len: usize, capacity: usize) -> io::Result<Self> {
+        if Self::item_size() == 0 || mem::align_of::<T>() > HEADER {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "element must be non zero-sized and aligned to at most {} bytes",
+                    HEADER
+                ),
+            ));
+        }
+        if (file.metadata()?.len() as usize) < HEADER {
+            file.set_len(HEADER as u64)?;
+        }
+
+        // SAFETY: mapping is only unsound if the file is changed by some
+        // other process while mapped, like any memory-mapped file user has
+        // to make sure that doesn't happen. any content is a valid `T`,
+        // because it's `Pod`.
+        let map = unsafe { MmapMut::map_mut(&file)? };
+        Ok(MmapStorage {
+            file,
+            map,
+            len,
+            capacity,
+            _marker: std::marker::PhantomData,
+        })
+    }
+
+    fn item_size() -> usize {
+        mem::size_of::<T>()
+    }
+
+    fn read_header(&self, field: usize) -> u64 {
+        let start = field * 8;
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(&self.map[start..start + 8]);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn write_header(&mut self) {
+        self.map[..8].copy_from_slice(&(self.len as u64).to_le_bytes());
+        self.map[8..16].copy_from_slice(&(Self::item_size() as u64).to_le_bytes());
+    }
+
+    fn set_len(&mut self, len: usize) {
+        self.len = len;
+        self.write_header();
+    }
+}
+
+impl<T> Sealed for MmapStorage<T> {}
+
+impl<T: Pod> Storage<T> for MmapStorage<T> {
+    fn as_slice(&self) -> &[T] {
+        bytemuck::cast_slice(&self.map[HEADER..HEADER + self.len * Self::item_size()])
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        let end = HEADER + self.len * Self::item_size();
+        bytemuck::cast_slice_mut(&mut self.map[HEADER..end])
+    }
+
+    fn push(&mut self, item: T) {
+        if self.len == self.capacity {
+            self.reserve(self.capacity.max(1));
+        }
+        let start = HEADER + self.len * Self::item_size();
+        self.map[start..start + Self::item_size()].copy_from_slice(bytemuck::bytes_of(&item));
+        self.set_len(self.len + 1);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let item = *self.as_slice().last()?;
+        self.set_len(self.len - 1);
+        Some(item)
+    }
+
+    fn truncate(&mut self, len: usize) {
+        if len < self.len {
+            self.set_len(len);
+        }
+    }
+
+    unsafe fn set_len(&mut self, len: usize) {
+        MmapStorage::set_len(self, len);
+    }
+
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        if let Err(error) = self.reserve_exact(additional) {
+            panic!("can't grow memory-mapped stack: {}", error);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("kstack-{}-{}.stack", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_grow_and_reopen() {
+        let path = temp_path("grow");
+        {
+            let mut stack: MmapKStack<u64, 3> =
+                KStack::with_storage(MmapStorage::create(&path).unwrap());
+            stack.kpush_iter(0..1000);
+            assert!(stack.capacity() >= 1000);
+            assert_eq!(Some([999, 998, 997]), stack.kpop_exact());
+            stack.kswap().unwrap();
+            stack.storage().flush().unwrap();
+        }
+
+        let mut stack: MmapKStack<u64, 3> = KStack::with_storage(MmapStorage::open(&path).unwrap());
+        assert_eq!(997, stack.len());
+        assert_eq!(vec![993, 992, 991, 996, 995, 994], stack.kpop_n(6));
+        drop(stack);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[derive(Clone, Copy)]
+    #[repr(C, align(128))]
+    struct Aligned([u8; 128]);
+
+    // SAFETY: plain bytes with no padding.
+    unsafe impl bytemuck::Zeroable for Aligned {}
+    unsafe impl Pod for Aligned {}
+
+    #[test]
+    fn test_create_errors() {
+        let path = temp_path("create-errors");
+        let error = MmapStorage::<()>::create(&path).err().unwrap();
+        assert_eq!(io::ErrorKind::InvalidInput, error.kind());
+        let error = MmapStorage::<Aligned>::create(&path).err().unwrap();
+        assert_eq!(io::ErrorKind::InvalidInput, error.kind());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_open_errors() {
+        let path = temp_path("errors");
+        assert!(MmapStorage::<u32>::open(&path).is_err());
+
+        drop(MmapStorage::<u32>::create(&path).unwrap());
+        let error = MmapStorage::<u64>::open(&path).err().unwrap();
+        assert_eq!(io::ErrorKind::InvalidData, error.kind());
+
+        let error = MmapStorage::<()>::open(&path).err().unwrap();
+        assert_eq!(io::ErrorKind::InvalidInput, error.kind());
+        let error = MmapStorage::<Aligned>::open(&path).err().unwrap();
+        assert_eq!(io::ErrorKind::InvalidInput, error.kind());
+
+        std::fs::write(&path, [0; 3]).unwrap();
+        assert!(MmapStorage::<u32>::open(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{KStack, KWindow};
+
+/// Order kept by a [`MonotonicKStack`], from bottom to top.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MonotonicOrder {
+    /// Each element is not less than the one below it.
+    Increasing,
+    /// Each element is not greater than the one below it.
+    Decreasing,
+}
+
+/// [`KStack`] that keeps its element in order, by popping element that
+/// break the order before each push.
+///
+/// This is the building block of next-greater-element style algorithms,
+/// a [`MonotonicOrder::Decreasing`] stack pops each element when the next
+/// greater one is pushed.
+pub struct MonotonicKStack<T, const K: usize> {
+    stack: KStack<T, K>,
+    order: MonotonicOrder,
+}
+
+impl<T: Ord, const K: usize> MonotonicKStack<T, K> {
+    /// Make a new MonotonicKStack keeping `order`.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::{MonotonicKStack, MonotonicOrder};
+    ///
+    ///     let mut stack = MonotonicKStack::<i32, 3>::new(MonotonicOrder::Increasing);
+    /// ```
+    pub fn new(order: MonotonicOrder) -> Self {
+        MonotonicKStack {
+            stack: KStack::new(),
+            order,
+        }
+    }
+
+    /// Order kept by stack.
+    pub fn order(&self) -> MonotonicOrder {
+        self.order
+    }
+
+    /// Get underlying stack, for all the read-only operations.
+    pub fn as_kstack(&self) -> &KStack<T, K> {
+        &self.stack
+    }
+
+    /// Consume and return underlying stack.
+    pub fn into_kstack(self) -> KStack<T, K> {
+        self.stack
+    }
+
+    /// Number of element on stack.
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Whether there is no element on stack.
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    /// Pop element that would break the order with `item`, giving each to
+    /// `on_pop` along with `item`, then push `item`. element equal to
+    /// `item` are kept.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::{MonotonicKStack, MonotonicOrder};
+    ///
+    ///     // next greater element of each value.
+    ///     let values = [2, 1, 5, 3, 4];
+    ///     let mut next = [None; 5];
+    ///     let mut stack = MonotonicKStack::<(i32, usize), 2>::new(MonotonicOrder::Decreasing);
+    ///
+    ///     for (i, &value) in values.iter().enumerate() {
+    ///         stack.push((value, i), |(_, popped), &(greater, _)| next[popped] = Some(greater));
+    ///     }
+    ///
+    ///     assert_eq!([Some(5), Some(5), None, Some(4), None], next);
+    /// ```
+    pub fn push<F: FnMut(T, &T)>(&mut self, item: T, mut on_pop: F) {
+        while let Some(top) = self.stack.peek() {
+            let breaks = match self.order {
+                MonotonicOrder::Increasing => *top > item,
+                MonotonicOrder::Decreasing => *top < item,
+            };
+            if !breaks {
+                break;
+            }
+            // peeked element is there.
+            on_pop(self.stack.pop().unwrap(), &item);
+        }
+        self.stack.push(item);
+    }
+
+    /// Remove and get single element on top of stack.
+    pub fn pop(&mut self) -> Option<T> {
+        self.stack.pop()
+    }
+
+    /// Removes and return K top element on stack.
+    pub fn kpop(&mut self) -> [Option<T>; K] {
+        self.stack.kpop()
+    }
+
+    /// return references to K top element on stack.
+    pub fn kshow_ref(&self) -> [Option<&T>; K] {
+        self.stack.kshow_ref()
+    }
+
+    /// return a borrowed view of K top element on stack.
+    pub fn kshow_view(&self) -> KWindow<'_, T, K> {
+        self.stack.kshow_view()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_increasing() {
+        let mut stack = MonotonicKStack::<i32, 2>::new(MonotonicOrder::Increasing);
+        let mut popped = Vec::new();
+        for item in [3, 5, 5, 4, 6, 1] {
+            stack.push(item, |old, new| popped.push((old, *new)));
+        }
+        assert_eq!(vec![(5, 4), (5, 4), (6, 1), (4, 1), (3, 1)], popped);
+        assert_eq!([Some(&1), None], stack.kshow_ref());
+        assert_eq!(MonotonicOrder::Increasing, stack.order());
+    }
+
+    #[test]
+    fn test_decreasing() {
+        let mut stack = MonotonicKStack::<i32, 2>::new(MonotonicOrder::Decreasing);
+        for item in [9, 4, 4, 7, 2] {
+            stack.push(item, |_, _| {});
+        }
+        assert_eq!(&[9, 7, 2], stack.as_kstack().kshow_n(3));
+        assert_eq!([Some(2), Some(7)], stack.kpop());
+        assert_eq!(Some(9), stack.pop());
+        assert!(stack.is_empty());
+        assert!(stack.kshow_view().is_empty());
+        assert!(stack.into_kstack().is_empty());
+    }
+}
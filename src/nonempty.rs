@@ -0,0 +1,193 @@
+use crate::{KStack, KWindow};
+
+/// [`KStack`] that always has at least one element, so [`peek`] and
+/// [`pop`] don't return an `Option`.
+///
+/// Made by [`KStack::push_nonempty`] or [`KStack::try_into_nonempty`],
+/// [`pop`] returns the possibly empty stack that's left.
+///
+/// [`peek`]: Self::peek
+/// [`pop`]: Self::pop
+pub struct NonEmptyKStack<T, const K: usize>(KStack<T, K>);
+
+impl<T, const K: usize> NonEmptyKStack<T, K> {
+    /// Make a new NonEmptyKStack with a single element.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::NonEmptyKStack;
+    ///
+    ///     let stack = NonEmptyKStack::<i32, 2>::new(1);
+    ///
+    ///     assert_eq!(&1, stack.peek());
+    /// ```
+    pub fn new(item: T) -> Self {
+        let mut stack = KStack::new();
+        stack.push(item);
+        NonEmptyKStack(stack)
+    }
+
+    /// Get underlying stack, for all the read-only operations.
+    pub fn as_kstack(&self) -> &KStack<T, K> {
+        &self.0
+    }
+
+    /// Consume and return underlying stack.
+    pub fn into_kstack(self) -> KStack<T, K> {
+        self.0
+    }
+
+    /// Number of element on stack, at least one.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// return reference to element on top of stack.
+    pub fn peek(&self) -> &T {
+        // there is always an element.
+        self.0.peek().unwrap()
+    }
+
+    /// return mutable reference to element on top of stack.
+    pub fn peek_mut(&mut self) -> &mut T {
+        // there is always an element.
+        self.0.peek_mut().unwrap()
+    }
+
+    /// Push a single element to stack.
+    pub fn push(&mut self, item: T) {
+        self.0.push(item);
+    }
+
+    /// Remove single element on top of stack and return it with the rest of
+    /// stack, which may be empty.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let stack = KStack::<i32, 2>::new().push_nonempty(1).push_nonempty(2);
+    ///     let (top, rest) = stack.pop();
+    ///
+    ///     assert_eq!(2, top);
+    ///     assert_eq!(&[1], rest.kshow_slice());
+    /// ```
+    pub fn pop(mut self) -> (T, KStack<T, K>) {
+        // there is always an element.
+        let item = self.0.pop().unwrap();
+        (item, self.0)
+    }
+
+    /// Remove and get single element on top of stack if it's not the last
+    /// one.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::NonEmptyKStack;
+    ///
+    ///     let mut stack = NonEmptyKStack::<i32, 2>::new(1);
+    ///
+    ///     stack.push(2);
+    ///
+    ///     assert_eq!(Some(2), stack.pop_unless_last());
+    ///     assert_eq!(None, stack.pop_unless_last());
+    ///     assert_eq!(&1, stack.peek());
+    /// ```
+    pub fn pop_unless_last(&mut self) -> Option<T> {
+        match self.0.len() > 1 {
+            true => self.0.pop(),
+            false => None,
+        }
+    }
+
+    /// Push a single element, keeping stack non-empty.
+    pub fn push_nonempty(mut self, item: T) -> Self {
+        self.push(item);
+        self
+    }
+
+    /// return a borrowed view of K top element on stack.
+    pub fn kshow_view(&self) -> KWindow<'_, T, K> {
+        self.0.kshow_view()
+    }
+}
+
+/// Non-empty stacks.
+impl<T, const K: usize> KStack<T, K> {
+    /// Push a single element and return stack as a [`NonEmptyKStack`].
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new().push_nonempty(1);
+    ///
+    ///     *stack.peek_mut() += 1;
+    ///     assert_eq!(&2, stack.peek());
+    /// ```
+    pub fn push_nonempty(mut self, item: T) -> NonEmptyKStack<T, K> {
+        self.push(item);
+        NonEmptyKStack(self)
+    }
+
+    /// Return stack as a [`NonEmptyKStack`], or give it back if it's
+    /// empty.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let empty = KStack::<i32, 2>::new();
+    ///     let stack = KStack::<i32, 2>::from(vec![1, 2]);
+    ///
+    ///     assert!(empty.try_into_nonempty().is_err());
+    ///     assert_eq!(&2, stack.try_into_nonempty().unwrap().peek());
+    /// ```
+    pub fn try_into_nonempty(self) -> Result<NonEmptyKStack<T, K>, Self> {
+        match self.is_empty() {
+            true => Err(self),
+            false => Ok(NonEmptyKStack(self)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drain() {
+        let mut stack = KStack::<i32, 2>::from(vec![1, 2, 3]);
+        let mut popped = Vec::new();
+        loop {
+            match stack.try_into_nonempty() {
+                Ok(nonempty) => {
+                    let (top, rest) = nonempty.pop();
+                    popped.push(top);
+                    stack = rest;
+                }
+                Err(empty) => {
+                    stack = empty;
+                    break;
+                }
+            }
+        }
+        assert_eq!(vec![3, 2, 1], popped);
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn test_nonempty() {
+        let mut stack = NonEmptyKStack::<i32, 2>::new(1).push_nonempty(2);
+        stack.push(3);
+        assert_eq!(3, stack.len());
+        assert_eq!(&[2, 3], stack.kshow_view().as_slice());
+        assert_eq!(Some(3), stack.pop_unless_last());
+        assert_eq!(&[1, 2], stack.as_kstack().kshow_slice());
+        assert_eq!(
+            vec![2, 1],
+            stack.into_kstack().into_iter().collect::<Vec<_>>()
+        );
+    }
+}
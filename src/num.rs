@@ -0,0 +1,148 @@
+use num_traits::{Num, ToPrimitive};
+
+use crate::{KStack, Storage};
+
+/// Aggregates over K top element on stack, enabled by `num-traits`
+/// feature.
+impl<T, const K: usize, S: Storage<T>> KStack<T, K, S> {
+    /// Sum of K top element on stack, zero if stack is empty.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]);
+    ///
+    ///     assert_eq!(9, stack.ksum());
+    /// ```
+    pub fn ksum(&self) -> T
+    where
+        T: Num + Copy,
+    {
+        self.kshow_slice()
+            .iter()
+            .fold(T::zero(), |sum, &item| sum + item)
+    }
+
+    /// Smallest of K top element on stack, the topmost one if several are
+    /// equal. element that don't compare, like NaN, are skipped unless
+    /// they are on top.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 3, 2, 4]);
+    ///
+    ///     assert_eq!(Some(&2), stack.kmin());
+    /// ```
+    pub fn kmin(&self) -> Option<&T>
+    where
+        T: PartialOrd,
+    {
+        self.kshow_view().iter().fold(None, |min, item| match min {
+            Some(min) if item < min => Some(item),
+            None => Some(item),
+            min => min,
+        })
+    }
+
+    /// Largest of K top element on stack, the topmost one if several are
+    /// equal. element that don't compare, like NaN, are skipped unless
+    /// they are on top.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[5, 3, 2, 4]);
+    ///
+    ///     assert_eq!(Some(&4), stack.kmax());
+    /// ```
+    pub fn kmax(&self) -> Option<&T>
+    where
+        T: PartialOrd,
+    {
+        self.kshow_view().iter().fold(None, |max, item| match max {
+            Some(max) if item > max => Some(item),
+            None => Some(item),
+            max => max,
+        })
+    }
+
+    /// Mean of K top element on stack, `None` if stack is empty or an
+    /// element can't be represented as `f64`.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 5]);
+    ///
+    ///     assert_eq!(Some(10.0 / 3.0), stack.kmean());
+    /// ```
+    pub fn kmean(&self) -> Option<f64>
+    where
+        T: ToPrimitive,
+    {
+        let window = self.kshow_slice();
+        if window.is_empty() {
+            return None;
+        }
+
+        let sum = window
+            .iter()
+            .try_fold(0.0, |sum, item| Some(sum + item.to_f64()?))?;
+        Some(sum / window.len() as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::KStack;
+
+    #[test]
+    fn test_empty() {
+        let stack = KStack::<i32, 3>::new();
+        assert_eq!(0, stack.ksum());
+        assert_eq!(None, stack.kmin());
+        assert_eq!(None, stack.kmax());
+        assert_eq!(None, stack.kmean());
+    }
+
+    #[test]
+    fn test_partial_window() {
+        let mut stack = KStack::<f64, 3>::new();
+        stack.kpush(&[1.5, 2.5]);
+        assert_eq!(4.0, stack.ksum());
+        assert_eq!(Some(&1.5), stack.kmin());
+        assert_eq!(Some(&2.5), stack.kmax());
+        assert_eq!(Some(2.0), stack.kmean());
+    }
+
+    #[test]
+    fn test_only_window() {
+        let mut stack = KStack::<i64, 2>::new();
+        stack.kpush(&[-100, 100, 3, 4]);
+        assert_eq!(7, stack.ksum());
+        assert_eq!(Some(&3), stack.kmin());
+        assert_eq!(Some(&4), stack.kmax());
+        assert_eq!(Some(3.5), stack.kmean());
+    }
+
+    #[test]
+    fn test_nan() {
+        let mut stack = KStack::<f64, 3>::new();
+        stack.kpush(&[1.0, f64::NAN, 2.0]);
+        assert_eq!(Some(&2.0), stack.kmax());
+        assert_eq!(Some(&1.0), stack.kmin());
+    }
+}
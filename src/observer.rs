@@ -0,0 +1,173 @@
+use crate::KStack;
+
+/// Gets told about every change made through an [`ObservedKStack`], for
+/// example to mirror stack into a UI model. methods do nothing by default.
+pub trait StackObserver<T> {
+    /// Called with `item` right before it is pushed on stack.
+    fn on_push(&mut self, item: &T) {
+        let _ = item;
+    }
+
+    /// Called after `item` is popped from stack.
+    fn on_pop(&mut self, item: &T) {
+        let _ = item;
+    }
+}
+
+/// [`KStack`] that calls a [`StackObserver`] for each element pushed or
+/// popped, `kpush` and `kpop` call it once per element in the order they
+/// are pushed or popped.
+pub struct ObservedKStack<T, const K: usize, O> {
+    stack: KStack<T, K>,
+    observer: O,
+}
+
+impl<T, const K: usize, O: StackObserver<T>> ObservedKStack<T, K, O> {
+    /// Make a new empty ObservedKStack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::{ObservedKStack, StackObserver};
+    ///
+    ///     #[derive(Default)]
+    ///     struct Counter(usize);
+    ///
+    ///     impl StackObserver<i32> for Counter {
+    ///         fn on_push(&mut self, _: &i32) {
+    ///             self.0 += 1;
+    ///         }
+    ///     }
+    ///
+    ///     let mut stack = ObservedKStack::<i32, 3, _>::new(Counter::default());
+    ///
+    ///     stack.kpush(&[1, 2, 3]);
+    ///     stack.pop();
+    ///
+    ///     assert_eq!(3, stack.observer().0);
+    /// ```
+    pub fn new(observer: O) -> Self {
+        ObservedKStack {
+            stack: KStack::new(),
+            observer,
+        }
+    }
+
+    /// Get observer.
+    pub fn observer(&self) -> &O {
+        &self.observer
+    }
+
+    /// Get observer mutably.
+    pub fn observer_mut(&mut self) -> &mut O {
+        &mut self.observer
+    }
+
+    /// Get underlying stack, for all the read-only operations.
+    pub fn as_kstack(&self) -> &KStack<T, K> {
+        &self.stack
+    }
+
+    /// Consume and return underlying stack and observer.
+    pub fn into_parts(self) -> (KStack<T, K>, O) {
+        (self.stack, self.observer)
+    }
+
+    /// Number of element on stack.
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Whether there is no element on stack.
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    /// Push a single element to stack.
+    pub fn push(&mut self, item: T) {
+        self.observer.on_push(&item);
+        self.stack.push(item);
+    }
+
+    /// Remove and get single element on top of stack.
+    pub fn pop(&mut self) -> Option<T> {
+        let item = self.stack.pop()?;
+        self.observer.on_pop(&item);
+        Some(item)
+    }
+
+    /// Push K element to stack.
+    pub fn kpush(&mut self, items: &[T])
+    where
+        T: Clone,
+    {
+        self.kpush_iter(items.iter().cloned());
+    }
+
+    /// Push all element of an iterator to stack.
+    pub fn kpush_iter<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        items.into_iter().for_each(|item| self.push(item));
+    }
+
+    /// Removes and return K top element on stack.
+    pub fn kpop(&mut self) -> [Option<T>; K] {
+        core::array::from_fn(|_| self.pop())
+    }
+
+    /// return references to K top element on stack.
+    pub fn kshow_ref(&self) -> [Option<&T>; K] {
+        self.stack.kshow_ref()
+    }
+
+    /// Pop all element on stack, from top to bottom.
+    pub fn clear(&mut self) {
+        while self.pop().is_some() {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Log(Vec<String>);
+
+    impl StackObserver<i32> for Log {
+        fn on_push(&mut self, item: &i32) {
+            self.0.push(format!("push {}", item));
+        }
+
+        fn on_pop(&mut self, item: &i32) {
+            self.0.push(format!("pop {}", item));
+        }
+    }
+
+    struct Silent;
+
+    impl StackObserver<i32> for Silent {}
+
+    #[test]
+    fn test_observer() {
+        let mut stack = ObservedKStack::<i32, 2, _>::new(Log::default());
+        stack.kpush(&[1, 2, 3]);
+        assert_eq!([Some(3), Some(2)], stack.kpop());
+        stack.push(4);
+        stack.clear();
+        assert_eq!(None, stack.pop());
+
+        let (stack, log) = stack.into_parts();
+        assert!(stack.is_empty());
+        assert_eq!(
+            vec!["push 1", "push 2", "push 3", "pop 3", "pop 2", "push 4", "pop 4", "pop 1"],
+            log.0
+        );
+    }
+
+    #[test]
+    fn test_default_methods() {
+        let mut stack = ObservedKStack::<i32, 2, _>::new(Silent);
+        stack.kpush_iter(0..3);
+        assert_eq!(Some(2), stack.pop());
+        assert_eq!([Some(&1), Some(&0)], stack.kshow_ref());
+        assert_eq!(2, stack.as_kstack().len());
+    }
+}
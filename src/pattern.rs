@@ -0,0 +1,136 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{KStack, Storage};
+
+/// What a single element must be to match, for [`KStack::kmatches`].
+pub enum Pattern<'a, T> {
+    /// Matches any element.
+    Any,
+    /// Matches an element equal to the value.
+    Eq(T),
+    /// Matches an element the function returns true for.
+    Pred(&'a dyn Fn(&T) -> bool),
+}
+
+impl<T: PartialEq> Pattern<'_, T> {
+    /// Whether `item` matches.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::Pattern;
+    ///
+    ///     assert!(Pattern::Any.matches(&1));
+    ///     assert!(Pattern::Eq(1).matches(&1));
+    ///     assert!(!Pattern::Pred(&|&x: &i32| x > 1).matches(&1));
+    /// ```
+    pub fn matches(&self, item: &T) -> bool {
+        match self {
+            Pattern::Any => true,
+            Pattern::Eq(value) => value == item,
+            Pattern::Pred(f) => f(item),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Pattern<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Pattern::Any => f.write_str("Any"),
+            Pattern::Eq(value) => f.debug_tuple("Eq").field(value).finish(),
+            Pattern::Pred(_) => f.write_str("Pred(..)"),
+        }
+    }
+}
+
+/// Matching top element against patterns.
+impl<T: PartialEq, const K: usize, S: Storage<T>> KStack<T, K, S> {
+    /// Whether top element on stack match `patterns`, first pattern for the
+    /// top. there must be at least as many element as patterns, and no
+    /// more patterns than K.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::{KStack, Pattern};
+    ///
+    ///     let mut stack = KStack::<&str, 3>::new();
+    ///
+    ///     stack.kpush(&["push 1", "push 2", "add"]);
+    ///
+    ///     let is_push = |op: &&str| op.starts_with("push");
+    ///     let pattern = [Pattern::Eq("add"), Pattern::Pred(&is_push), Pattern::Any];
+    ///
+    ///     assert!(stack.kmatches(&pattern));
+    ///     assert!(stack.kmatches(&pattern[..1]));
+    ///     assert!(!stack.kmatches(&[Pattern::Pred(&is_push)]));
+    /// ```
+    pub fn kmatches(&self, patterns: &[Pattern<'_, T>]) -> bool {
+        patterns.len() <= K
+            && patterns.len() <= self.len()
+            && self
+                .iter()
+                .zip(patterns)
+                .all(|(item, pattern)| pattern.matches(item))
+    }
+
+    /// Like [`kmatches`](Self::kmatches), but return element matched by
+    /// [`Pattern::Any`] and [`Pattern::Pred`] in pattern order if they
+    /// match.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::{KStack, Pattern};
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[0, 4, 0]);
+    ///
+    ///     let pattern = [Pattern::Eq(0), Pattern::Any, Pattern::Eq(0)];
+    ///     assert_eq!(Some(vec![&4]), stack.kmatch(&pattern));
+    ///     assert_eq!(None, stack.kmatch(&[Pattern::Eq(4)]));
+    /// ```
+    pub fn kmatch(&self, patterns: &[Pattern<'_, T>]) -> Option<Vec<&T>> {
+        if !self.kmatches(patterns) {
+            return None;
+        }
+        let captures = self
+            .iter()
+            .zip(patterns)
+            .filter(|(_, pattern)| !matches!(pattern, Pattern::Eq(_)))
+            .map(|(item, _)| item)
+            .collect();
+        Some(captures)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kmatches() {
+        let mut stack = KStack::<i32, 2>::new();
+        assert!(stack.kmatches(&[]));
+        assert!(!stack.kmatches(&[Pattern::Any]));
+
+        stack.kpush(&[1, 2, 3]);
+        assert!(stack.kmatches(&[Pattern::Eq(3), Pattern::Eq(2)]));
+        assert!(!stack.kmatches(&[Pattern::Eq(2), Pattern::Eq(3)]));
+        assert!(!stack.kmatches(&[Pattern::Any, Pattern::Any, Pattern::Any]));
+
+        let even = |x: &i32| x % 2 == 0;
+        assert_eq!(
+            Some(vec![&3, &2]),
+            stack.kmatch(&[Pattern::Any, Pattern::Pred(&even)])
+        );
+        assert_eq!(Some(vec![]), stack.kmatch(&[]));
+        assert_eq!(None, stack.kmatch(&[Pattern::Pred(&even)]));
+    }
+
+    #[test]
+    fn test_debug() {
+        let even = |x: &i32| x % 2 == 0;
+        let pattern = [Pattern::Any, Pattern::Eq(1), Pattern::Pred(&even)];
+        assert_eq!("[Any, Eq(1), Pred(..)]", format!("{:?}", pattern));
+    }
+}
@@ -0,0 +1,304 @@
+//! Immutable stack sharing structure between versions.
+
+use alloc::sync::Arc;
+
+struct Node<T> {
+    item: T,
+    next: Option<Arc<Node<T>>>,
+}
+
+/// Immutable [`KStack`](crate::KStack), where [`push`](Self::push) and
+/// [`pop`](Self::pop) return a new stack and leave the old one readable.
+///
+/// It's a linked list shared behind [`Arc`], so a new version only
+/// allocates the element it pushes, and cloning is O(1). like
+/// [`KStack`](crate::KStack), K must be at least 1.
+///
+/// ```compile_fail
+///     use kstack::persistent::PersistentKStack;
+///
+///     let stack = PersistentKStack::<i32, 0>::new();
+/// ```
+pub struct PersistentKStack<T, const K: usize> {
+    head: Option<Arc<Node<T>>>,
+    len: usize,
+}
+
+impl<T, const K: usize> PersistentKStack<T, K> {
+    const NONZERO_K: () = assert!(
+        K > 0,
+        "window size K of PersistentKStack must be at least 1"
+    );
+
+    /// Make a new, empty PersistentKStack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::persistent::PersistentKStack;
+    ///
+    ///     let stack = PersistentKStack::<i32, 3>::new();
+    /// ```
+    pub fn new() -> Self {
+        let () = Self::NONZERO_K;
+        PersistentKStack { head: None, len: 0 }
+    }
+
+    /// Number of element on stack.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether there is no element on stack.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// return a new stack with `item` pushed on top of this one.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::persistent::PersistentKStack;
+    ///
+    ///     let old = PersistentKStack::<i32, 3>::new().push(1);
+    ///     let new = old.push(2);
+    ///
+    ///     assert_eq!(Some(&1), old.top());
+    ///     assert_eq!(Some(&2), new.top());
+    /// ```
+    pub fn push(&self, item: T) -> Self {
+        PersistentKStack {
+            head: Some(Arc::new(Node {
+                item,
+                next: self.head.clone(),
+            })),
+            len: self.len + 1,
+        }
+    }
+
+    /// Element on top of stack.
+    pub fn top(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.item)
+    }
+
+    /// Get element on top of stack and a new stack without it, `None` if
+    /// stack is empty.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::persistent::PersistentKStack;
+    ///
+    ///     let stack = PersistentKStack::<i32, 3>::new().push(1).push(2);
+    ///     let (top, rest) = stack.pop().unwrap();
+    ///
+    ///     assert_eq!(&2, top);
+    ///     assert_eq!(1, rest.len());
+    ///     assert_eq!(2, stack.len());
+    /// ```
+    pub fn pop(&self) -> Option<(&T, Self)> {
+        let node = self.head.as_ref()?;
+        let rest = PersistentKStack {
+            head: node.next.clone(),
+            len: self.len - 1,
+        };
+        Some((&node.item, rest))
+    }
+
+    /// return a new stack with all `items` pushed on top of this one, so
+    /// the last one ends up on top.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::persistent::PersistentKStack;
+    ///
+    ///     let stack = PersistentKStack::<i32, 2>::new().kpush(&[1, 2, 3]);
+    ///
+    ///     assert_eq!([Some(&3), Some(&2)], stack.kshow_ref());
+    /// ```
+    pub fn kpush(&self, items: &[T]) -> Self
+    where
+        T: Clone,
+    {
+        items
+            .iter()
+            .fold(self.clone(), |stack, item| stack.push(item.clone()))
+    }
+
+    /// Get K top element on stack and a new stack without them.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::persistent::PersistentKStack;
+    ///
+    ///     let stack = PersistentKStack::<i32, 2>::new().kpush(&[1, 2, 3]);
+    ///     let (window, rest) = stack.kpop();
+    ///
+    ///     assert_eq!([Some(&3), Some(&2)], window);
+    ///     assert_eq!([Some(&1), None], rest.kshow_ref());
+    /// ```
+    pub fn kpop(&self) -> ([Option<&T>; K], Self) {
+        let mut node = self.head.as_ref();
+        let mut popped = 0;
+        let window = core::array::from_fn(|_| {
+            let current = node?;
+            node = current.next.as_ref();
+            popped += 1;
+            Some(&current.item)
+        });
+        let rest = PersistentKStack {
+            head: node.cloned(),
+            len: self.len - popped,
+        };
+        (window, rest)
+    }
+
+    /// return references to K top element on stack.
+    pub fn kshow_ref(&self) -> [Option<&T>; K] {
+        let mut items = self.iter();
+        core::array::from_fn(|_| items.next())
+    }
+
+    /// return K top element on stack.
+    pub fn kshow(&self) -> [Option<T>; K]
+    where
+        T: Clone,
+    {
+        self.kshow_ref().map(|item| item.cloned())
+    }
+
+    /// Returns an iterator over stack items, from top to bottom.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::persistent::PersistentKStack;
+    ///
+    ///     let stack = PersistentKStack::<i32, 2>::new().kpush(&[1, 2, 3]);
+    ///
+    ///     assert_eq!(vec![&3, &2, &1], stack.iter().collect::<Vec<_>>());
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            node: self.head.as_deref(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T, const K: usize> Clone for PersistentKStack<T, K> {
+    fn clone(&self) -> Self {
+        PersistentKStack {
+            head: self.head.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T, const K: usize> Default for PersistentKStack<T, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const K: usize> Drop for PersistentKStack<T, K> {
+    fn drop(&mut self) {
+        // drop nodes one by one, dropping a long list recursively would
+        // overflow the call stack. stop at the first node shared with
+        // another version.
+        let mut head = self.head.take();
+        while let Some(node) = head {
+            head = match Arc::try_unwrap(node) {
+                Ok(mut node) => node.next.take(),
+                Err(_) => None,
+            };
+        }
+    }
+}
+
+impl<'a, T, const K: usize> IntoIterator for &'a PersistentKStack<T, K> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over element of a [`PersistentKStack`], from top to bottom.
+pub struct Iter<'a, T> {
+    node: Option<&'a Node<T>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.node?;
+        self.node = node.next.as_deref();
+        self.len -= 1;
+        Some(&node.item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter {
+            node: self.node,
+            len: self.len,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versions() {
+        let empty = PersistentKStack::<i32, 2>::new();
+        let a = empty.kpush(&[1, 2, 3]);
+        let (window, b) = a.kpop();
+        let c = b.push(10);
+
+        assert!(empty.is_empty());
+        assert_eq!([Some(&3), Some(&2)], window);
+        assert_eq!(vec![&3, &2, &1], a.iter().collect::<Vec<_>>());
+        assert_eq!(vec![&1], b.iter().collect::<Vec<_>>());
+        assert_eq!([Some(10), Some(1)], c.kshow());
+        assert_eq!(2, c.iter().len());
+
+        let (_, rest) = b.kpop();
+        assert!(rest.is_empty());
+        assert!(empty.pop().is_none());
+    }
+
+    #[test]
+    fn test_structure_shared() {
+        let base = PersistentKStack::<i32, 1>::new().push(1);
+        let left = base.push(2);
+        let right = base.push(3);
+        let shared = |stack: &PersistentKStack<i32, 1>| {
+            stack.head.as_ref().unwrap().next.as_ref().map(Arc::as_ptr)
+        };
+        assert_eq!(shared(&left), shared(&right));
+        drop(base);
+        assert_eq!(vec![&2, &1], left.iter().collect::<Vec<_>>());
+        assert_eq!(vec![&3, &1], right.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_long_drop() {
+        let mut stack = PersistentKStack::<u32, 1>::new();
+        for i in 0..200_000 {
+            stack = stack.push(i);
+        }
+        let (_, half) = stack.kpop();
+        drop(stack);
+        assert_eq!(199_999, half.len());
+    }
+}
@@ -0,0 +1,26 @@
+//! Lock handling shared by the thread-safe types.
+
+use std::sync::{LockResult, Mutex, MutexGuard};
+
+/// Lock `mutex`, see [`unpoisoned`].
+pub(crate) fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    unpoisoned(mutex.lock())
+}
+
+/// Value of a lock operation, panicking if another thread panicked while
+/// holding the lock, like `lock().unwrap()` does.
+///
+/// A panic in the middle of a closure given to `with`, or in `T::clone`
+/// during `kpush`, can leave a stack half changed, so it's never used
+/// silently after one.
+pub(crate) fn unpoisoned<G>(result: LockResult<G>) -> G {
+    match result {
+        Ok(guard) => guard,
+        Err(_) => poisoned(),
+    }
+}
+
+#[cold]
+fn poisoned() -> ! {
+    panic!("stack lock poisoned by a panic in another thread")
+}
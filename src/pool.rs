@@ -0,0 +1,176 @@
+//! Thread-local pool of stack buffers, enabled by `pool` feature.
+//!
+//! [`take`] hands out an empty stack reusing a buffer freed earlier on the
+//! same thread, and dropping the stack gives its buffer back, so short
+//! lived stacks like one per request don't allocate every time.
+
+use std::any::{Any, TypeId};
+use std::boxed::Box;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::mem;
+use std::ops::{Deref, DerefMut};
+use std::vec::Vec;
+
+use crate::KStack;
+
+/// Most buffers kept per element type on each thread, the rest are freed.
+pub const MAX_POOLED: usize = 16;
+
+thread_local! {
+    // free buffers by element type, each one is a `Vec<T>`.
+    static POOL: RefCell<HashMap<TypeId, Vec<Box<dyn Any>>>> = RefCell::new(HashMap::new());
+}
+
+/// Empty stack, reusing a pooled buffer of this thread if there is one.
+///
+/// # Example
+/// ```
+///     use kstack::pool;
+///
+///     let mut stack = pool::take::<i32, 2>();
+///     stack.reserve(100);
+///     drop(stack);
+///
+///     // buffer of the dropped stack is reused.
+///     let stack = pool::take::<i32, 3>();
+///     assert!(stack.capacity() >= 100);
+/// ```
+pub fn take<T: 'static, const K: usize>() -> PooledKStack<T, K> {
+    let buffer = POOL
+        .try_with(|pool| {
+            let buffer = pool.borrow_mut().get_mut(&TypeId::of::<T>())?.pop()?;
+            buffer.downcast::<Vec<T>>().ok()
+        })
+        .ok()
+        .flatten();
+
+    PooledKStack {
+        stack: KStack::with_storage(buffer.map_or_else(Vec::new, |buffer| *buffer)),
+    }
+}
+
+/// Number of buffers for element of type `T` pooled on this thread.
+pub fn pooled<T: 'static>() -> usize {
+    POOL.try_with(|pool| {
+        pool.borrow()
+            .get(&TypeId::of::<T>())
+            .map_or(0, |buffers| buffers.len())
+    })
+    .unwrap_or(0)
+}
+
+fn give_back<T: 'static>(buffer: Vec<T>) {
+    if buffer.capacity() == 0 {
+        return;
+    }
+    // thread-local is gone while thread exits, buffer is just freed then.
+    let _ = POOL.try_with(|pool| {
+        let mut pool = pool.borrow_mut();
+        let buffers = pool.entry(TypeId::of::<T>()).or_insert_with(Vec::new);
+        if buffers.len() < MAX_POOLED {
+            buffers.push(Box::new(buffer));
+        }
+    });
+}
+
+/// [`KStack`] from [`take`], its buffer goes back to the pool of the
+/// dropping thread.
+///
+/// It derefs to the underlying [`KStack`] for all stack operations.
+pub struct PooledKStack<T: 'static, const K: usize> {
+    stack: KStack<T, K>,
+}
+
+impl<T: 'static, const K: usize> PooledKStack<T, K> {
+    /// Consume and return underlying stack, its buffer is not given back.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::pool;
+    ///
+    ///     let mut stack = pool::take::<u8, 2>();
+    ///     stack.kpush(&[1, 2, 3]);
+    ///
+    ///     assert_eq!(&[2, 3], stack.into_kstack().kshow_slice());
+    /// ```
+    pub fn into_kstack(mut self) -> KStack<T, K> {
+        mem::take(&mut self.stack)
+    }
+}
+
+impl<T: 'static, const K: usize> Deref for PooledKStack<T, K> {
+    type Target = KStack<T, K>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.stack
+    }
+}
+
+impl<T: 'static, const K: usize> DerefMut for PooledKStack<T, K> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.stack
+    }
+}
+
+impl<T: 'static, const K: usize> Drop for PooledKStack<T, K> {
+    fn drop(&mut self) {
+        // element are dropped before pool is borrowed, they may be pooled
+        // stacks too.
+        self.stack.clear();
+        give_back(mem::take(self.stack.storage_mut()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reuse() {
+        struct Item;
+
+        let mut stack = take::<Item, 2>();
+        stack.push(Item);
+        let pointer = stack.as_slice().as_ptr();
+        drop(stack);
+        assert_eq!(1, pooled::<Item>());
+
+        let mut stack = take::<Item, 2>();
+        assert!(stack.is_empty());
+        assert_eq!(0, pooled::<Item>());
+        stack.push(Item);
+        assert_eq!(pointer, stack.as_slice().as_ptr());
+    }
+
+    #[test]
+    fn test_limit() {
+        struct Item;
+
+        let stacks: Vec<_> = (0..MAX_POOLED + 2)
+            .map(|_| {
+                let mut stack = take::<Item, 1>();
+                stack.push(Item);
+                stack
+            })
+            .collect();
+        drop(stacks);
+        assert_eq!(MAX_POOLED, pooled::<Item>());
+
+        let empty = take::<Item, 1>();
+        assert_eq!(MAX_POOLED - 1, pooled::<Item>());
+        drop(empty);
+        assert_eq!(MAX_POOLED, pooled::<Item>());
+    }
+
+    #[test]
+    fn test_nested() {
+        let mut outer = take::<PooledKStack<u16, 1>, 1>();
+        let mut inner = take::<u16, 1>();
+        inner.push(1);
+        outer.push(inner);
+        drop(outer);
+        assert_eq!(1, pooled::<u16>());
+        assert_eq!(1, pooled::<PooledKStack<u16, 1>>());
+    }
+}
@@ -0,0 +1,156 @@
+use alloc::collections::BTreeSet;
+
+use crate::KStack;
+
+/// [`KStack`] of element with a priority, that can also show the K
+/// element of highest priority with [`kshow_by_priority`].
+///
+/// An index of priorities is kept next to stack, so pushes and pops are
+/// O(log n) and [`kshow_by_priority`] is O(K log n).
+///
+/// [`kshow_by_priority`]: Self::kshow_by_priority
+pub struct PriorityKStack<P, T, const K: usize> {
+    stack: KStack<(P, T), K>,
+    /// Priority and position on stack of each element.
+    index: BTreeSet<(P, usize)>,
+}
+
+impl<P: Ord + Clone, T, const K: usize> PriorityKStack<P, T, K> {
+    /// Make a new PriorityKStack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::PriorityKStack;
+    ///
+    ///     let mut stack = PriorityKStack::<u8, &str, 3>::new();
+    /// ```
+    pub fn new() -> Self {
+        PriorityKStack {
+            stack: KStack::new(),
+            index: BTreeSet::new(),
+        }
+    }
+
+    /// Get underlying stack of priorities and element, for all the
+    /// read-only operations.
+    pub fn as_kstack(&self) -> &KStack<(P, T), K> {
+        &self.stack
+    }
+
+    /// Number of element on stack.
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Whether there is no element on stack.
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    /// Push a single element to stack with its priority.
+    pub fn push(&mut self, priority: P, item: T) {
+        self.index.insert((priority.clone(), self.stack.len()));
+        self.stack.push((priority, item));
+    }
+
+    /// Remove and get single element on top of stack with its priority.
+    pub fn pop(&mut self) -> Option<(P, T)> {
+        let (priority, item) = self.stack.pop()?;
+        let key = (priority, self.stack.len());
+        self.index.remove(&key);
+        Some((key.0, item))
+    }
+
+    /// Removes and return K top element on stack with their priority.
+    pub fn kpop(&mut self) -> [Option<(P, T)>; K] {
+        core::array::from_fn(|_| self.pop())
+    }
+
+    /// return references to K top element on stack.
+    pub fn kshow_ref(&self) -> [Option<&T>; K] {
+        self.stack
+            .kshow_ref()
+            .map(|entry| entry.map(|(_, item)| item))
+    }
+
+    /// return references to K element of highest priority with their
+    /// priority, highest first. element of equal priority are shown most
+    /// recent first.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::PriorityKStack;
+    ///
+    ///     let mut stack = PriorityKStack::<u8, &str, 2>::new();
+    ///
+    ///     stack.push(3, "disk full");
+    ///     stack.push(1, "login");
+    ///     stack.push(3, "disk failing");
+    ///     stack.push(2, "slow query");
+    ///
+    ///     assert_eq!(
+    ///         [Some((&3, &"disk failing")), Some((&3, &"disk full"))],
+    ///         stack.kshow_by_priority()
+    ///     );
+    ///     assert_eq!([Some(&"slow query"), Some(&"disk failing")], stack.kshow_ref());
+    /// ```
+    pub fn kshow_by_priority(&self) -> [Option<(&P, &T)>; K] {
+        let items = self.stack.as_slice();
+        let mut keys = self.index.iter().rev();
+        core::array::from_fn(|_| {
+            let &(_, position) = keys.next()?;
+            let (priority, item) = &items[position];
+            Some((priority, item))
+        })
+    }
+}
+
+impl<P: Ord + Clone, T, const K: usize> Default for PriorityKStack<P, T, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_against_sort() {
+        let mut stack = PriorityKStack::<u32, u32, 3>::default();
+        let mut seed = 3u32;
+        for step in 0..1000 {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            match seed % 3 {
+                0 => {
+                    stack.pop();
+                }
+                _ => stack.push(seed / 7 % 10, step),
+            }
+
+            let mut sorted: Vec<_> = stack.as_kstack().as_slice().iter().collect();
+            sorted.sort_by(|a, b| b.cmp(a));
+            let expected: Vec<_> = sorted.iter().take(3).map(|(p, t)| (p, t)).collect();
+            let shown: Vec<_> = stack
+                .kshow_by_priority()
+                .iter()
+                .flatten()
+                .copied()
+                .collect();
+            assert_eq!(expected, shown);
+        }
+    }
+
+    #[test]
+    fn test_kpop() {
+        let mut stack = PriorityKStack::<u8, char, 2>::new();
+        stack.push(1, 'a');
+        stack.push(9, 'b');
+        stack.push(5, 'c');
+        assert_eq!([Some((5, 'c')), Some((9, 'b'))], stack.kpop());
+        assert_eq!([Some((&1, &'a')), None], stack.kshow_by_priority());
+        assert_eq!(1, stack.len());
+        assert_eq!([Some((1, 'a')), None], stack.kpop());
+        assert!(stack.is_empty());
+    }
+}
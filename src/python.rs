@@ -0,0 +1,109 @@
+//! Python bindings, enabled by `python` feature.
+//!
+//! [`PyKStack`] is exported as `kstack.KStack` and holds any Python
+//! object. window size is given to the constructor at runtime, and a
+//! window size of 0 raises `ValueError`. windows are lists, top first,
+//! like [`KStack::kpop`](crate::KStack::kpop).
+
+use alloc::vec::Vec;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::dynamic::DynKStack;
+
+/// Stack of Python objects.
+#[pyclass(name = "KStack")]
+pub struct PyKStack {
+    stack: DynKStack<PyObject>,
+}
+
+#[pymethods]
+impl PyKStack {
+    /// Make a new stack with window size `k`, raises `ValueError` if `k`
+    /// is 0.
+    #[new]
+    pub fn new(k: usize) -> PyResult<Self> {
+        match DynKStack::new(k) {
+            Some(stack) => Ok(PyKStack { stack }),
+            None => Err(PyValueError::new_err("window size must be at least 1")),
+        }
+    }
+
+    /// Window size.
+    #[getter]
+    pub fn k(&self) -> usize {
+        self.stack.k()
+    }
+
+    /// Number of element on stack.
+    pub fn __len__(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Push a single element to stack.
+    pub fn push(&mut self, item: PyObject) {
+        self.stack.push(item);
+    }
+
+    /// Remove and get single element on top of stack.
+    pub fn pop(&mut self) -> Option<PyObject> {
+        self.stack.pop()
+    }
+
+    /// Push element in order, so the last one ends up on top.
+    pub fn kpush(&mut self, items: Vec<PyObject>) {
+        self.stack.kpush(items);
+    }
+
+    /// Removes and return window, top first.
+    pub fn kpop(&mut self) -> Vec<PyObject> {
+        self.stack.kpop(usize::MAX)
+    }
+
+    /// return window, top first.
+    pub fn kshow(&self, py: Python<'_>) -> Vec<PyObject> {
+        let window = self.stack.kshow(usize::MAX);
+        window.map(|item| item.clone_ref(py)).collect()
+    }
+}
+
+/// `kstack` Python module.
+#[pymodule]
+fn kstack(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyKStack>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::types::PyDict;
+
+    #[test]
+    fn test_py_kstack() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let module = PyModule::new_bound(py, "kstack").unwrap();
+            kstack(&module).unwrap();
+            let locals = PyDict::new_bound(py);
+            locals.set_item("kstack", module).unwrap();
+
+            let code = "\
+stack = kstack.KStack(2)
+stack.kpush([1, 'two', 3.0])
+stack.push(None)
+assert len(stack) == 4 and stack.k == 2
+assert stack.kshow() == [None, 3.0]
+assert stack.pop() is None
+assert stack.kpop() == [3.0, 'two']
+assert stack.kpop() == [1]
+assert stack.pop() is None and len(stack) == 0
+try:
+    kstack.KStack(0)
+    assert False
+except ValueError:
+    pass
+";
+            py.run_bound(code, None, Some(&locals)).unwrap();
+        });
+    }
+}
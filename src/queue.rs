@@ -0,0 +1,227 @@
+use alloc::collections::vec_deque::{self, VecDeque};
+use core::iter::FromIterator;
+
+/// FIFO sibling of [`KStack`](crate::KStack), where the window is the K
+/// oldest element instead of the K newest.
+///
+/// Windows are returned oldest first, so [`kpop`](Self::kpop) gives
+/// element in the order they were pushed. K must be at least 1.
+///
+/// ```compile_fail
+///     use kstack::KQueue;
+///
+///     let stack = KQueue::<i32, 0>::new();
+/// ```
+pub struct KQueue<T, const K: usize>(VecDeque<T>);
+
+impl<T, const K: usize> KQueue<T, K> {
+    const NONZERO_K: () = assert!(K > 0, "window size K of KQueue must be at least 1");
+
+    /// Make a new KQueue.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KQueue;
+    ///
+    ///     let mut queue = KQueue::<i32, 3>::new();
+    /// ```
+    pub fn new() -> Self {
+        let () = Self::NONZERO_K;
+        KQueue(VecDeque::new())
+    }
+
+    /// Make a new KQueue with room for at least `capacity` element.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let () = Self::NONZERO_K;
+        KQueue(VecDeque::with_capacity(capacity))
+    }
+
+    /// Number of element in queue.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether there is no element in queue.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Remove and get the oldest element in queue.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KQueue;
+    ///
+    ///     let mut queue = KQueue::<i32, 3>::new();
+    ///
+    ///     queue.push(1);
+    ///     queue.push(2);
+    ///
+    ///     assert_eq!(Some(1), queue.pop());
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        self.0.pop_front()
+    }
+
+    /// Push a single element to the back of queue.
+    pub fn push(&mut self, item: T) {
+        self.0.push_back(item);
+    }
+
+    /// Removes and return K oldest element in queue.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KQueue;
+    ///
+    ///     let mut queue = KQueue::<i32, 3>::new();
+    ///
+    ///     queue.kpush(&[1, 2, 3, 4]);
+    ///
+    ///     assert_eq!([Some(1), Some(2), Some(3)], queue.kpop());
+    ///     assert_eq!([Some(4), None, None], queue.kpop());
+    /// ```
+    pub fn kpop(&mut self) -> [Option<T>; K] {
+        core::array::from_fn(|_| self.0.pop_front())
+    }
+
+    /// Removes and return K oldest element in queue, only if there are at
+    /// least K element, otherwise queue is left untouched.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KQueue;
+    ///
+    ///     let mut queue = KQueue::<i32, 3>::new();
+    ///
+    ///     queue.kpush(&[1, 2, 3, 4]);
+    ///
+    ///     assert_eq!(Some([1, 2, 3]), queue.kpop_exact());
+    ///     assert_eq!(None, queue.kpop_exact());
+    ///     assert_eq!(Some(4), queue.pop());
+    /// ```
+    pub fn kpop_exact(&mut self) -> Option<[T; K]> {
+        if self.0.len() < K {
+            return None;
+        }
+
+        let mut items = self.0.drain(..K);
+        Some(core::array::from_fn(|_| items.next().unwrap()))
+    }
+
+    /// return references to K oldest element in queue.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KQueue;
+    ///
+    ///     let mut queue = KQueue::<i32, 3>::new();
+    ///
+    ///     queue.kpush(&[1, 2]);
+    ///
+    ///     assert_eq!([Some(&1), Some(&2), None], queue.kshow_ref());
+    /// ```
+    pub fn kshow_ref(&self) -> [Option<&T>; K] {
+        let mut items = self.0.iter();
+        core::array::from_fn(|_| items.next())
+    }
+
+    /// return K oldest element in queue.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KQueue;
+    ///
+    ///     let mut queue = KQueue::<i32, 2>::new();
+    ///
+    ///     queue.kpush(&[1, 2, 3]);
+    ///
+    ///     assert_eq!([Some(1), Some(2)], queue.kshow());
+    ///     assert_eq!(3, queue.len());
+    /// ```
+    pub fn kshow(&self) -> [Option<T>; K]
+    where
+        T: Clone,
+    {
+        self.kshow_ref().map(|item| item.cloned())
+    }
+
+    /// Push K element to the back of queue, in order.
+    pub fn kpush(&mut self, items: &[T])
+    where
+        T: Clone,
+    {
+        self.0.extend(items.iter().cloned());
+    }
+
+    /// Push all element of an iterator to the back of queue.
+    pub fn kpush_iter<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        self.0.extend(items);
+    }
+
+    /// Iterate over element in queue, oldest first.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KQueue;
+    ///
+    ///     let queue: KQueue<i32, 2> = (1..=3).collect();
+    ///
+    ///     assert_eq!(vec![&1, &2, &3], queue.iter().collect::<Vec<_>>());
+    /// ```
+    pub fn iter(&self) -> vec_deque::Iter<'_, T> {
+        self.0.iter()
+    }
+}
+
+impl<T, const K: usize> Default for KQueue<T, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Items are pushed in iteration order, so the first item is the oldest.
+impl<T, const K: usize> FromIterator<T> for KQueue<T, K> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let () = Self::NONZERO_K;
+        KQueue(VecDeque::from_iter(iter))
+    }
+}
+
+impl<T, const K: usize> Extend<T> for KQueue<T, K> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.kpush_iter(iter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fifo_windows() {
+        let mut queue = KQueue::<i32, 2>::new();
+        queue.kpush(&[1, 2, 3]);
+        queue.push(4);
+        queue.extend(5..=6);
+
+        assert_eq!([Some(&1), Some(&2)], queue.kshow_ref());
+        assert_eq!([Some(1), Some(2)], queue.kpop());
+        assert_eq!(Some([3, 4]), queue.kpop_exact());
+        assert_eq!(Some(5), queue.pop());
+        assert_eq!(None, queue.kpop_exact());
+        assert_eq!([Some(6), None], queue.kpop());
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_non_clone() {
+        struct Job(u32);
+
+        let mut queue: KQueue<Job, 2> = (0..3).map(Job).collect();
+        let [first, second] = queue.kpop();
+        assert_eq!(0, first.unwrap().0);
+        assert_eq!(1, second.unwrap().0);
+        assert_eq!(1, queue.len());
+    }
+}
@@ -0,0 +1,118 @@
+use rayon::iter::{IntoParallelIterator, Rev};
+use rayon::prelude::*;
+use rayon::slice::{Iter, IterMut, RChunksExact};
+
+use crate::{KStack, Storage};
+
+/// Parallel iteration, enabled by `rayon` feature.
+impl<T, const K: usize, S: Storage<T>> KStack<T, K, S> {
+    /// Returns a parallel iterator over stack items, from top to bottom
+    /// like [`iter`](Self::iter).
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///     use rayon::prelude::*;
+    ///
+    ///     let stack: KStack<u64, 2> = (1..=1000).collect();
+    ///
+    ///     assert_eq!(500500, stack.par_iter().sum::<u64>());
+    ///     assert_eq!(Some(&1000), stack.par_iter().find_first(|_| true));
+    /// ```
+    pub fn par_iter(&self) -> Rev<Iter<'_, T>>
+    where
+        T: Sync,
+    {
+        self.as_slice().par_iter().rev()
+    }
+
+    /// Returns a parallel iterator that allows modifying stack items,
+    /// from top to bottom.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///     use rayon::prelude::*;
+    ///
+    ///     let mut stack: KStack<u64, 2> = (1..=3).collect();
+    ///
+    ///     stack.par_iter_mut().for_each(|item| *item *= 2);
+    ///
+    ///     assert_eq!([Some(6), Some(4)], stack.kshow());
+    /// ```
+    pub fn par_iter_mut(&mut self) -> Rev<IterMut<'_, T>>
+    where
+        T: Send,
+    {
+        self.as_mut_slice().par_iter_mut().rev()
+    }
+
+    /// Returns a parallel iterator over K element chunks of stack, from
+    /// top to bottom like [`kchunks`](Self::kchunks). bottom element that
+    /// don't fill a whole chunk are available from
+    /// [`RChunksExact::remainder`].
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///     use rayon::prelude::*;
+    ///
+    ///     let stack: KStack<u64, 2> = (1..=5).collect();
+    ///     let chunks = stack.par_kchunks();
+    ///
+    ///     assert_eq!(&[1], chunks.remainder());
+    ///
+    ///     let sums: Vec<u64> = chunks.map(|chunk| chunk.iter().sum()).collect();
+    ///     assert_eq!(vec![9, 5], sums);
+    /// ```
+    pub fn par_kchunks(&self) -> RChunksExact<'_, T>
+    where
+        T: Sync,
+    {
+        self.as_slice().par_rchunks_exact(K)
+    }
+}
+
+impl<'a, T: Sync + 'a, const K: usize, S: Storage<T>> IntoParallelIterator for &'a KStack<T, K, S> {
+    type Iter = Rev<Iter<'a, T>>;
+    type Item = &'a T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.par_iter()
+    }
+}
+
+impl<'a, T: Send + 'a, const K: usize, S: Storage<T>> IntoParallelIterator
+    for &'a mut KStack<T, K, S>
+{
+    type Iter = Rev<IterMut<'a, T>>;
+    type Item = &'a mut T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.par_iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_par_iter_order() {
+        let stack: KStack<u32, 3> = (0..10_000).collect();
+        let items: Vec<_> = stack.par_iter().copied().collect();
+        assert_eq!(stack.iter().copied().collect::<Vec<_>>(), items);
+        assert_eq!(items, (&stack).into_par_iter().copied().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_par_kchunks() {
+        let mut stack: KStack<u32, 3> = (0..10).collect();
+        (&mut stack).into_par_iter().for_each(|item| *item += 1);
+
+        let chunks: Vec<_> = stack.par_kchunks().collect();
+        let expected: Vec<_> = stack.kchunks().collect();
+        assert_eq!(expected, chunks);
+        assert_eq!(&[1], stack.par_kchunks().remainder());
+    }
+}
@@ -0,0 +1,153 @@
+//! Process-wide registry of named stacks, enabled by `registry` feature.
+//!
+//! Stacks are registered under a name and looked up by name and type, so
+//! components that don't know each other can share a well-known stack.
+//!
+//! # Example
+//! ```
+//!     use kstack::registry;
+//!
+//!     let ops = registry::get_or_default::<i32, 3>("ops").unwrap();
+//!     ops.push(1);
+//!
+//!     // somewhere else in the process.
+//!     let ops = registry::get::<i32, 3>("ops").unwrap();
+//!     assert_eq!(Some(1), ops.pop());
+//! ```
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::string::String;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::poison;
+use crate::sync::SyncKStack;
+use crate::KStack;
+
+type Stacks = HashMap<String, Arc<dyn Any + Send + Sync>>;
+
+static REGISTRY: Mutex<Option<Stacks>> = Mutex::new(None);
+
+fn stacks() -> MutexGuard<'static, Option<Stacks>> {
+    poison::lock(&REGISTRY)
+}
+
+/// Register `stack` under `name` and return a shared handle to it, or give
+/// it back if `name` is already taken.
+///
+/// # Example
+/// ```
+///     use kstack::{registry, KStack};
+///
+///     let stack = KStack::<i32, 2>::from(vec![1, 2]);
+///
+///     assert!(registry::register("register", stack).is_ok());
+///     assert!(registry::register("register", KStack::<i32, 2>::new()).is_err());
+/// ```
+pub fn register<T, const K: usize>(
+    name: &str,
+    stack: KStack<T, K>,
+) -> Result<Arc<SyncKStack<T, K>>, KStack<T, K>>
+where
+    T: Send + 'static,
+{
+    let mut stacks = stacks();
+    let stacks = stacks.get_or_insert_with(HashMap::new);
+    if stacks.contains_key(name) {
+        return Err(stack);
+    }
+
+    let stack = Arc::new(SyncKStack::from(stack));
+    stacks.insert(name.into(), stack.clone());
+    Ok(stack)
+}
+
+/// Stack registered under `name`, `None` if there is none or it's a stack
+/// of another type or window size.
+///
+/// # Example
+/// ```
+///     use kstack::{registry, KStack};
+///
+///     registry::register("get", KStack::<i32, 2>::from(vec![1])).unwrap();
+///
+///     assert!(registry::get::<i32, 2>("get").is_some());
+///     assert!(registry::get::<i32, 3>("get").is_none());
+///     assert!(registry::get::<u8, 2>("get").is_none());
+/// ```
+pub fn get<T, const K: usize>(name: &str) -> Option<Arc<SyncKStack<T, K>>>
+where
+    T: Send + 'static,
+{
+    let stack = stacks().as_ref()?.get(name)?.clone();
+    stack.downcast().ok()
+}
+
+/// Stack registered under `name`, registering an empty one if there is
+/// none. `None` if it's a stack of another type or window size.
+pub fn get_or_default<T, const K: usize>(name: &str) -> Option<Arc<SyncKStack<T, K>>>
+where
+    T: Send + 'static,
+{
+    let mut stacks = stacks();
+    let stack = stacks
+        .get_or_insert_with(HashMap::new)
+        .entry(name.into())
+        .or_insert_with(|| Arc::new(SyncKStack::<T, K>::new()))
+        .clone();
+    stack.downcast().ok()
+}
+
+/// Remove stack registered under `name` from registry, handles already
+/// given out keep working. return whether there was one.
+///
+/// # Example
+/// ```
+///     use kstack::{registry, KStack};
+///
+///     registry::register("unregister", KStack::<i32, 2>::new()).unwrap();
+///
+///     assert!(registry::unregister("unregister"));
+///     assert!(!registry::unregister("unregister"));
+///     assert!(registry::get::<i32, 2>("unregister").is_none());
+/// ```
+pub fn unregister(name: &str) -> bool {
+    match stacks().as_mut() {
+        Some(stacks) => stacks.remove(name).is_some(),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_shared_between_threads() {
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                thread::spawn(move || {
+                    let stack = get_or_default::<u32, 2>("test_shared").unwrap();
+                    stack.push(i);
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let stack = get::<u32, 2>("test_shared").unwrap();
+        let mut items = stack.with(|stack| stack.kpop_n(10));
+        items.sort_unstable();
+        assert_eq!(vec![0, 1, 2, 3], items);
+    }
+
+    #[test]
+    fn test_type_mismatch() {
+        register("test_mismatch", KStack::<String, 2>::new()).unwrap();
+        assert!(get_or_default::<u32, 2>("test_mismatch").is_none());
+        assert!(get::<String, 2>("test_mismatch").is_some());
+        assert!(get::<String, 2>("test_missing").is_none());
+    }
+}
@@ -0,0 +1,385 @@
+//! Mirroring a stack across processes with snapshots and deltas, enabled
+//! by `replication` feature.
+//!
+//! A [`Primary`] numbers every operation done on it. a [`Replica`] starts
+//! from a [`Snapshot`] of the primary and then applies [`Delta`]s holding
+//! operations since, which are checked to follow what the replica already
+//! has. both can be written to bytes to send them to another process.
+
+use std::fmt;
+use std::io::{self, Read, Write};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::journal::{self, Op};
+use crate::KStack;
+
+/// Whole state of a [`Primary`] stack after operation `seq`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Snapshot<T> {
+    /// Number of operations done on primary.
+    pub seq: u64,
+    /// Element from bottom to top.
+    pub items: Vec<T>,
+}
+
+impl<T> Snapshot<T> {
+    /// Write snapshot as `seq` as a little-endian `u64` then element
+    /// encoded by `bincode`.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()>
+    where
+        T: Serialize,
+    {
+        writer.write_all(&self.seq.to_le_bytes())?;
+        journal::encode(writer, &self.items)
+    }
+
+    /// Read a snapshot written by [`write_to`](Self::write_to).
+    pub fn read_from<R: Read>(mut reader: R) -> io::Result<Self>
+    where
+        T: DeserializeOwned,
+    {
+        let seq = read_u64(&mut reader)?;
+        let items = journal::decode(reader)?;
+        Ok(Snapshot { seq, items })
+    }
+}
+
+/// Operations of a [`Primary`] after operation `base`, returned by
+/// [`Primary::take_delta`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Delta<T> {
+    /// Number of operations done on primary before this delta.
+    pub base: u64,
+    /// Number of element on primary stack after this delta, to find out if
+    /// a replica diverged.
+    pub len: u64,
+    /// Operations in the order they were done.
+    pub ops: Vec<Op<T>>,
+}
+
+impl<T> Delta<T> {
+    /// Number of operations done on primary after this delta.
+    pub fn seq(&self) -> u64 {
+        self.base + self.ops.len() as u64
+    }
+
+    /// Write delta as `base`, `len` and number of operations as
+    /// little-endian `u64`s, then each operation as a
+    /// [journal](crate::journal) record.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()>
+    where
+        T: Serialize,
+    {
+        writer.write_all(&self.base.to_le_bytes())?;
+        writer.write_all(&self.len.to_le_bytes())?;
+        writer.write_all(&(self.ops.len() as u64).to_le_bytes())?;
+        self.ops.iter().try_for_each(|op| op.write_to(&mut writer))
+    }
+
+    /// Read a delta written by [`write_to`](Self::write_to).
+    pub fn read_from<R: Read>(mut reader: R) -> io::Result<Self>
+    where
+        T: DeserializeOwned,
+    {
+        let base = read_u64(&mut reader)?;
+        let len = read_u64(&mut reader)?;
+        let count = read_u64(&mut reader)?;
+        let ops = (0..count)
+            .map(|_| {
+                Op::read_from(&mut reader)?
+                    .ok_or_else(|| io::Error::from(io::ErrorKind::UnexpectedEof))
+            })
+            .collect::<io::Result<_>>()?;
+        Ok(Delta { base, len, ops })
+    }
+}
+
+/// Error returned by [`Replica::apply`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReplicationError {
+    /// Delta starts after the last operation replica has, so operations
+    /// in between are missing. nothing was applied.
+    Gap {
+        /// Sequence number of replica.
+        expected: u64,
+        /// Base of delta.
+        found: u64,
+    },
+    /// Replica has a different number of element than primary after
+    /// applying delta, so it should be rebuilt from a new snapshot.
+    Diverged {
+        /// Number of element on primary.
+        expected: u64,
+        /// Number of element on replica.
+        found: u64,
+    },
+}
+
+impl fmt::Display for ReplicationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplicationError::Gap { expected, found } => write!(
+                f,
+                "delta starts at operation {} but replica is at {}",
+                found, expected
+            ),
+            ReplicationError::Diverged { expected, found } => write!(
+                f,
+                "replica has {} element but primary has {}",
+                found, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ReplicationError {}
+
+/// [`KStack`] that keeps operations done on it to send them to replicas.
+pub struct Primary<T, const K: usize> {
+    stack: KStack<T, K>,
+    seq: u64,
+    ops: Vec<Op<T>>,
+}
+
+impl<T, const K: usize> Primary<T, K> {
+    /// Make a new empty Primary.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::replication::{Primary, Replica};
+    ///
+    ///     let mut primary = Primary::<i32, 2>::new();
+    ///     primary.kpush(&[1, 2]);
+    ///
+    ///     let mut replica = Replica::from_snapshot(primary.snapshot());
+    ///
+    ///     primary.push(3);
+    ///     primary.pop();
+    ///     primary.push(4);
+    ///     replica.apply(primary.take_delta()).unwrap();
+    ///
+    ///     assert_eq!(primary.as_kstack(), replica.as_kstack());
+    /// ```
+    pub fn new() -> Self {
+        Primary {
+            stack: KStack::new(),
+            seq: 0,
+            ops: Vec::new(),
+        }
+    }
+
+    /// Get underlying stack, for all the read-only operations.
+    pub fn as_kstack(&self) -> &KStack<T, K> {
+        &self.stack
+    }
+
+    /// Number of operations done on stack.
+    pub fn seq(&self) -> u64 {
+        self.seq
+    }
+
+    /// Push a single element to stack.
+    pub fn push(&mut self, item: T)
+    where
+        T: Clone,
+    {
+        self.record(Op::Push(item.clone()));
+        self.stack.push(item);
+    }
+
+    /// Remove and get single element on top of stack.
+    pub fn pop(&mut self) -> Option<T> {
+        self.record(Op::Pop);
+        self.stack.pop()
+    }
+
+    /// Push K element to stack.
+    pub fn kpush(&mut self, items: &[T])
+    where
+        T: Clone,
+    {
+        self.record(Op::KPush(items.to_vec()));
+        self.stack.kpush(items);
+    }
+
+    /// Removes and return K top element on stack.
+    pub fn kpop(&mut self) -> [Option<T>; K] {
+        self.record(Op::KPop);
+        self.stack.kpop()
+    }
+
+    /// Copy current state, to start a replica.
+    pub fn snapshot(&self) -> Snapshot<T>
+    where
+        T: Clone,
+    {
+        Snapshot {
+            seq: self.seq,
+            items: self.stack.as_slice().to_vec(),
+        }
+    }
+
+    /// Take operations done since last delta was taken.
+    pub fn take_delta(&mut self) -> Delta<T> {
+        let ops = core::mem::take(&mut self.ops);
+        Delta {
+            base: self.seq - ops.len() as u64,
+            len: self.stack.len() as u64,
+            ops,
+        }
+    }
+
+    fn record(&mut self, op: Op<T>) {
+        self.seq += 1;
+        self.ops.push(op);
+    }
+}
+
+impl<T, const K: usize> Default for Primary<T, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Read-only copy of a [`Primary`] stack, kept up to date with deltas.
+pub struct Replica<T, const K: usize> {
+    stack: KStack<T, K>,
+    seq: u64,
+}
+
+impl<T, const K: usize> Replica<T, K> {
+    /// Make a Replica with state of primary in `snapshot`.
+    pub fn from_snapshot(snapshot: Snapshot<T>) -> Self {
+        Replica {
+            stack: KStack::from(snapshot.items),
+            seq: snapshot.seq,
+        }
+    }
+
+    /// Get underlying stack, for all the read-only operations.
+    pub fn as_kstack(&self) -> &KStack<T, K> {
+        &self.stack
+    }
+
+    /// Number of primary operations replica has.
+    pub fn seq(&self) -> u64 {
+        self.seq
+    }
+
+    /// Apply operations of `delta` that replica doesn't have yet, so
+    /// applying a delta twice or one older than the snapshot is fine.
+    ///
+    /// # Errors
+    /// Returns [`ReplicationError::Gap`] if operations before `delta` are
+    /// missing, or [`ReplicationError::Diverged`] if replica doesn't match
+    /// primary after applying it.
+    pub fn apply(&mut self, delta: Delta<T>) -> Result<(), ReplicationError> {
+        if delta.base > self.seq {
+            return Err(ReplicationError::Gap {
+                expected: self.seq,
+                found: delta.base,
+            });
+        }
+        if delta.seq() <= self.seq {
+            return Ok(());
+        }
+
+        let applied = (self.seq - delta.base) as usize;
+        self.seq = delta.seq();
+        for op in delta.ops.into_iter().skip(applied) {
+            op.apply(&mut self.stack);
+        }
+        match self.stack.len() as u64 == delta.len {
+            true => Ok(()),
+            false => Err(ReplicationError::Diverged {
+                expected: delta.len,
+                found: self.stack.len() as u64,
+            }),
+        }
+    }
+}
+
+fn read_u64<R: Read>(mut reader: R) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replicate() {
+        let mut primary = Primary::<u32, 2>::default();
+        primary.kpush(&[1, 2, 3]);
+        let old = primary.take_delta();
+        assert_eq!(1, old.seq());
+
+        primary.push(4);
+        let snapshot = primary.snapshot();
+        assert_eq!(2, snapshot.seq);
+        primary.kpop();
+        primary.push(5);
+        assert_eq!(4, primary.seq());
+
+        let mut replica = Replica::from_snapshot(snapshot);
+        replica.apply(old).unwrap();
+        let delta = primary.take_delta();
+        assert_eq!(1, delta.base);
+        replica.apply(delta.clone()).unwrap();
+        replica.apply(delta).unwrap();
+        assert_eq!(4, replica.seq());
+        assert_eq!(primary.as_kstack(), replica.as_kstack());
+        assert!(primary.take_delta().ops.is_empty());
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut primary = Primary::<u32, 2>::new();
+        let mut replica = Replica::<u32, 2>::from_snapshot(primary.snapshot());
+        primary.push(1);
+        primary.take_delta();
+        primary.pop();
+        let error = replica.apply(primary.take_delta()).unwrap_err();
+        assert_eq!(
+            ReplicationError::Gap {
+                expected: 0,
+                found: 1
+            },
+            error
+        );
+        assert_eq!(0, replica.seq());
+
+        let delta = Delta {
+            base: 0,
+            len: 5,
+            ops: vec![Op::Push(1)],
+        };
+        let error = replica.apply(delta).unwrap_err();
+        assert_eq!("replica has 1 element but primary has 5", error.to_string());
+    }
+
+    #[test]
+    fn test_encoding() {
+        let mut primary = Primary::<String, 2>::new();
+        primary.push("a".to_string());
+        let mut bytes = Vec::new();
+        primary.snapshot().write_to(&mut bytes).unwrap();
+        let snapshot = Snapshot::<String>::read_from(&bytes[..]).unwrap();
+        assert_eq!(primary.snapshot(), snapshot);
+
+        primary.kpush(&["b".to_string(), "c".to_string()]);
+        primary.kpop();
+        primary.pop();
+        let delta = primary.take_delta();
+        let mut bytes = Vec::new();
+        delta.write_to(&mut bytes).unwrap();
+        assert_eq!(delta, Delta::read_from(&bytes[..]).unwrap());
+
+        let error = Delta::<String>::read_from(&bytes[..bytes.len() - 1]).unwrap_err();
+        assert_eq!(io::ErrorKind::UnexpectedEof, error.kind());
+    }
+}
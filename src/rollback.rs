@@ -0,0 +1,141 @@
+//! Rollback buffer of recent game states, for netcode-style rollback.
+
+use crate::{CappedKStack, KWindow, OverflowPolicy};
+
+/// Keeps a snapshot of state for each of the most recent `max_frames`
+/// frames, with the K most recent ones available as a window.
+///
+/// It's built on a [`CappedKStack`] with [`OverflowPolicy::Evict`], so
+/// oldest frames are dropped and memory stays constant.
+pub struct FrameHistory<S, const K: usize> {
+    frames: CappedKStack<S, K>,
+}
+
+impl<S, const K: usize> FrameHistory<S, K> {
+    /// Make a new FrameHistory keeping at most `max_frames` frames.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::rollback::FrameHistory;
+    ///
+    ///     let mut history = FrameHistory::<u64, 3>::new(8);
+    /// ```
+    pub fn new(max_frames: usize) -> Self {
+        FrameHistory {
+            frames: CappedKStack::new(max_frames, OverflowPolicy::Evict),
+        }
+    }
+
+    /// Most frames kept.
+    pub fn max_frames(&self) -> usize {
+        self.frames.max_depth()
+    }
+
+    /// Number of frames kept.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Whether no frame is kept.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Record state of a new frame, dropping the oldest one if history is
+    /// full.
+    pub fn push(&mut self, state: S) {
+        // evicting never fails.
+        let _ = self.frames.push(state);
+    }
+
+    /// State of most recent frame.
+    pub fn latest(&self) -> Option<&S> {
+        self.kshow().get(0)
+    }
+
+    /// K most recent frames, most recent first.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::rollback::FrameHistory;
+    ///
+    ///     let mut history = FrameHistory::<u64, 2>::new(8);
+    ///
+    ///     for frame in 0..5 {
+    ///         history.push(frame * 10);
+    ///     }
+    ///
+    ///     assert_eq!(vec![&40, &30], history.kshow().iter().collect::<Vec<_>>());
+    /// ```
+    pub fn kshow(&self) -> KWindow<'_, S, K> {
+        self.frames.as_kstack().kshow_view()
+    }
+
+    /// Drop the `n` most recent frames and return state of the frame
+    /// before them, which becomes the most recent one. returns `None` and
+    /// leaves history untouched if there are not more than `n` frames.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::rollback::FrameHistory;
+    ///
+    ///     let mut history = FrameHistory::<u64, 2>::new(4);
+    ///
+    ///     for frame in 0..6 {
+    ///         history.push(frame);
+    ///     }
+    ///
+    ///     assert_eq!(Some(&3), history.rewind(2));
+    ///     assert_eq!(2, history.len());
+    ///
+    ///     // frame 1 is already evicted.
+    ///     assert_eq!(None, history.rewind(2));
+    /// ```
+    pub fn rewind(&mut self, n: usize) -> Option<&S> {
+        if n >= self.len() {
+            return None;
+        }
+        for _ in 0..n {
+            self.frames.pop();
+        }
+        self.latest()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constant_memory() {
+        let mut history = FrameHistory::<u32, 3>::new(5);
+        for frame in 0..100 {
+            history.push(frame);
+            assert!(history.len() <= 5);
+        }
+        assert_eq!(5, history.max_frames());
+        assert_eq!(Some(&99), history.latest());
+        assert_eq!(
+            vec![&99, &98, &97],
+            history.kshow().iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_rewind_and_resimulate() {
+        let mut history = FrameHistory::<u32, 2>::new(10);
+        assert_eq!(None, history.latest());
+        assert_eq!(None, history.rewind(0));
+
+        for frame in 1..=5 {
+            history.push(frame);
+        }
+        assert_eq!(Some(&5), history.rewind(0));
+        assert_eq!(Some(&2), history.rewind(3));
+        history.push(30);
+
+        assert_eq!(vec![&30, &2], history.kshow().iter().collect::<Vec<_>>());
+        assert_eq!(None, history.rewind(3));
+        assert_eq!(3, history.len());
+    }
+}
@@ -0,0 +1,187 @@
+use num_traits::{Num, ToPrimitive};
+
+use crate::KStack;
+
+/// [`KStack`] that keeps sum of K top element up to date on every
+/// mutation, so [`ksum`](Self::ksum) and [`kmean`](Self::kmean) are O(1).
+/// enabled by `num-traits` feature.
+///
+/// The sum is updated by adding element entering the window and
+/// subtracting the ones leaving it, so with floating point element it can
+/// drift slightly from [`KStack::ksum`].
+pub struct RunningKStack<T, const K: usize> {
+    stack: KStack<T, K>,
+    sum: T,
+}
+
+impl<T: Num + Copy, const K: usize> RunningKStack<T, K> {
+    /// Make a new RunningKStack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::RunningKStack;
+    ///
+    ///     let mut stack = RunningKStack::<i32, 3>::new();
+    /// ```
+    pub fn new() -> Self {
+        RunningKStack {
+            stack: KStack::new(),
+            sum: T::zero(),
+        }
+    }
+
+    /// Get underlying stack, for all the read-only operations.
+    pub fn as_kstack(&self) -> &KStack<T, K> {
+        &self.stack
+    }
+
+    /// Consume and return underlying stack.
+    pub fn into_kstack(self) -> KStack<T, K> {
+        self.stack
+    }
+
+    /// Number of element on stack.
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Whether there is no element on stack.
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    /// Sum of K top element on stack, zero if stack is empty.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::RunningKStack;
+    ///
+    ///     let mut stack = RunningKStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]);
+    ///     assert_eq!(9, stack.ksum());
+    ///
+    ///     stack.pop();
+    ///     assert_eq!(6, stack.ksum());
+    /// ```
+    pub fn ksum(&self) -> T {
+        self.sum
+    }
+
+    /// Mean of K top element on stack, `None` if stack is empty or sum
+    /// can't be represented as `f64`.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::RunningKStack;
+    ///
+    ///     let mut stack = RunningKStack::<i32, 2>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3]);
+    ///
+    ///     assert_eq!(Some(2.5), stack.kmean());
+    /// ```
+    pub fn kmean(&self) -> Option<f64>
+    where
+        T: ToPrimitive,
+    {
+        let count = self.stack.len().min(K);
+        match count {
+            0 => None,
+            _ => Some(self.sum.to_f64()? / count as f64),
+        }
+    }
+
+    /// Remove and get single element on top of stack.
+    pub fn pop(&mut self) -> Option<T> {
+        let item = self.stack.pop()?;
+        self.sum = self.sum - item;
+        // element at depth K - 1 enters the window.
+        if let Some(index) = self.stack.len().checked_sub(K) {
+            self.sum = self.sum + self.stack.0[index];
+        }
+        Some(item)
+    }
+
+    /// Push a single element to stack.
+    pub fn push(&mut self, item: T) {
+        // element at depth K - 1 leaves the window.
+        if let Some(index) = self.stack.len().checked_sub(K) {
+            self.sum = self.sum - self.stack.0[index];
+        }
+        self.sum = self.sum + item;
+        self.stack.push(item);
+    }
+
+    /// Removes and return K top element on stack.
+    pub fn kpop(&mut self) -> [Option<T>; K] {
+        core::array::from_fn(|_| self.pop())
+    }
+
+    /// return K top element on stack.
+    pub fn kshow(&self) -> [Option<T>; K] {
+        self.stack.kshow()
+    }
+
+    /// Push K element to stack.
+    pub fn kpush(&mut self, items: &[T]) {
+        self.kpush_iter(items.iter().copied());
+    }
+
+    /// Push all element of an iterator to stack.
+    pub fn kpush_iter<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        items.into_iter().for_each(|item| self.push(item));
+    }
+}
+
+impl<T: Num + Copy, const K: usize> Default for RunningKStack<T, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Num + Copy, const K: usize> From<KStack<T, K>> for RunningKStack<T, K> {
+    fn from(stack: KStack<T, K>) -> Self {
+        let sum = stack.ksum();
+        RunningKStack { stack, sum }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_ksum() {
+        let mut stack = RunningKStack::<i64, 3>::new();
+        assert_eq!(0, stack.ksum());
+        assert_eq!(None, stack.kmean());
+
+        for i in 1..=10 {
+            stack.push(i * i);
+            assert_eq!(stack.as_kstack().ksum(), stack.ksum());
+        }
+        stack.kpop();
+        assert_eq!(stack.as_kstack().ksum(), stack.ksum());
+        while stack.pop().is_some() {
+            assert_eq!(stack.as_kstack().ksum(), stack.ksum());
+        }
+        assert_eq!(0, stack.ksum());
+    }
+
+    #[test]
+    fn test_from_kstack() {
+        let mut inner = KStack::<f64, 2>::new();
+        inner.kpush(&[1.0, 2.0, 4.0]);
+
+        let mut stack = RunningKStack::from(inner);
+        assert_eq!(6.0, stack.ksum());
+        assert_eq!(Some(3.0), stack.kmean());
+
+        stack.kpush(&[8.0]);
+        assert_eq!(Some(6.0), stack.kmean());
+        assert_eq!([Some(8.0), Some(4.0)], stack.kpop());
+        assert_eq!(Some(1.5), stack.kmean());
+        assert_eq!(2, stack.len());
+    }
+}
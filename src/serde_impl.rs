@@ -0,0 +1,132 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem;
+
+use serde::de::{Deserialize, Deserializer, Error, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use crate::{ArrayKStack, KStack};
+
+/// Upper bound on memory preallocated from an untrusted length hint.
+const MAX_PREALLOCATION: usize = 1024 * 1024;
+
+fn cautious_capacity<T>(hint: Option<usize>) -> usize {
+    let max = MAX_PREALLOCATION / mem::size_of::<T>().max(1);
+    hint.unwrap_or(0).min(max)
+}
+
+/// Serialized as a sequence from bottom to top, K is kept by the type.
+impl<T: Serialize, const K: usize> Serialize for KStack<T, K> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter())
+    }
+}
+
+impl<'de, T: Deserialize<'de>, const K: usize> Deserialize<'de> for KStack<T, K> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct KStackVisitor<T, const K: usize>(PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de>, const K: usize> Visitor<'de> for KStackVisitor<T, K> {
+            type Value = KStack<T, K>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a sequence of stack items")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut items = Vec::with_capacity(cautious_capacity::<T>(seq.size_hint()));
+                while let Some(item) = seq.next_element()? {
+                    items.push(item);
+                }
+                Ok(KStack::with_storage(items))
+            }
+        }
+
+        deserializer.deserialize_seq(KStackVisitor(PhantomData))
+    }
+}
+
+/// Serialized as a sequence from bottom to top, K and CAP are kept by the
+/// type.
+impl<T: Serialize, const K: usize, const CAP: usize> Serialize for ArrayKStack<T, K, CAP> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.as_slice().iter())
+    }
+}
+
+/// Fails if the sequence has more than `CAP` items.
+impl<'de, T: Deserialize<'de>, const K: usize, const CAP: usize> Deserialize<'de>
+    for ArrayKStack<T, K, CAP>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ArrayKStackVisitor<T, const K: usize, const CAP: usize>(PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de>, const K: usize, const CAP: usize> Visitor<'de>
+            for ArrayKStackVisitor<T, K, CAP>
+        {
+            type Value = ArrayKStack<T, K, CAP>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "a sequence of at most {} stack items", CAP)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut stack = ArrayKStack::new();
+                while let Some(item) = seq.next_element()? {
+                    if stack.push(item).is_err() {
+                        return Err(A::Error::invalid_length(CAP + 1, &self));
+                    }
+                }
+                Ok(stack)
+            }
+        }
+
+        deserializer.deserialize_seq(ArrayKStackVisitor(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kstack_round_trip() {
+        let mut stack = KStack::<i32, 3>::new();
+        stack.kpush(&[1, 2, 3, 4]);
+
+        let json = serde_json::to_string(&stack).unwrap();
+        assert_eq!("[1,2,3,4]", json);
+
+        let mut decoded: KStack<i32, 3> = serde_json::from_str(&json).unwrap();
+        assert_eq!([Some(4), Some(3), Some(2)], decoded.kpop());
+        assert_eq!([Some(1), None, None], decoded.kpop());
+    }
+
+    #[test]
+    fn test_kstack_invalid() {
+        assert!(serde_json::from_str::<KStack<i32, 3>>("{}").is_err());
+        assert!(serde_json::from_str::<KStack<i32, 3>>("[1, \"a\"]").is_err());
+    }
+
+    #[test]
+    fn test_array_kstack_round_trip() {
+        let mut stack = ArrayKStack::<i32, 2, 4>::new();
+        stack.kpush(&[1, 2, 3]).unwrap();
+
+        let json = serde_json::to_string(&stack).unwrap();
+        assert_eq!("[1,2,3]", json);
+
+        let mut decoded: ArrayKStack<i32, 2, 4> = serde_json::from_str(&json).unwrap();
+        assert_eq!([Some(3), Some(2)], decoded.kpop());
+        assert_eq!([Some(1), None], decoded.kpop());
+    }
+
+    #[test]
+    fn test_array_kstack_too_long() {
+        match serde_json::from_str::<ArrayKStack<i32, 2, 2>>("[1, 2, 3]") {
+            Err(error) => assert!(error.to_string().contains("at most 2")),
+            Ok(_) => panic!("deserialized more than CAP items"),
+        }
+    }
+}
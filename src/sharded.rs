@@ -0,0 +1,205 @@
+//! Sharded stack for many concurrent producers.
+
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::vec::Vec;
+
+use crate::poison::{lock, unpoisoned};
+use crate::KStack;
+
+type Shard<T, const K: usize> = KStack<(u64, T), K>;
+
+/// Stack spread over several internal stacks, so producers on different
+/// threads mostly lock different shards.
+///
+/// Every element gets a sequence number when pushed, and pops lock all
+/// shards and merge their tops by it. order is LIFO by the time a push
+/// locked its shard, so concurrent pushes may land in either order, while
+/// pushes of one thread are always popped in LIFO order.
+pub struct ShardedKStack<T, const K: usize> {
+    shards: Vec<Mutex<Shard<T, K>>>,
+    next_shard: AtomicUsize,
+    next_seq: AtomicU64,
+}
+
+impl<T, const K: usize> ShardedKStack<T, K> {
+    /// Make a new ShardedKStack with `shards` internal stacks, at least
+    /// one.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::sharded::ShardedKStack;
+    ///
+    ///     let stack = ShardedKStack::<i32, 3>::new(8);
+    /// ```
+    pub fn new(shards: usize) -> Self {
+        ShardedKStack {
+            shards: (0..shards.max(1))
+                .map(|_| Mutex::new(KStack::new()))
+                .collect(),
+            next_shard: AtomicUsize::new(0),
+            next_seq: AtomicU64::new(0),
+        }
+    }
+
+    /// Number of internal stacks.
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Number of element on stack.
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| lock(shard).len()).sum()
+    }
+
+    /// Whether there is no element on stack.
+    pub fn is_empty(&self) -> bool {
+        self.shards.iter().all(|shard| lock(shard).is_empty())
+    }
+
+    /// Push a single element to stack, locking only one shard.
+    pub fn push(&self, item: T) {
+        let shard = self.next_shard.fetch_add(1, Ordering::Relaxed) % self.shards.len();
+        let mut shard = lock(&self.shards[shard]);
+        // taken under shard lock, so each shard is sorted by it.
+        let seq = self.next_seq.fetch_add(1, Ordering::Relaxed);
+        shard.push((seq, item));
+    }
+
+    /// Remove and get single element on top of stack.
+    pub fn pop(&self) -> Option<T> {
+        let mut shards = self.lock_all();
+        pop_newest(&mut shards)
+    }
+
+    /// Removes and return K top element on stack, merged from all shards
+    /// atomically.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::sharded::ShardedKStack;
+    ///
+    ///     let stack = ShardedKStack::<i32, 3>::new(2);
+    ///
+    ///     for i in 1..=4 {
+    ///         stack.push(i);
+    ///     }
+    ///
+    ///     assert_eq!([Some(4), Some(3), Some(2)], stack.kpop());
+    ///     assert_eq!([Some(1), None, None], stack.kpop());
+    /// ```
+    pub fn kpop(&self) -> [Option<T>; K] {
+        let mut shards = self.lock_all();
+        core::array::from_fn(|_| pop_newest(&mut shards))
+    }
+
+    /// return K top element on stack, merged from all shards.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::sharded::ShardedKStack;
+    ///
+    ///     let stack = ShardedKStack::<i32, 2>::new(4);
+    ///
+    ///     stack.push(1);
+    ///     stack.push(2);
+    ///     stack.push(3);
+    ///
+    ///     assert_eq!([Some(3), Some(2)], stack.kshow());
+    ///     assert_eq!(3, stack.len());
+    /// ```
+    pub fn kshow(&self) -> [Option<T>; K]
+    where
+        T: Clone,
+    {
+        let shards = self.lock_all();
+        // number of element already taken from top of each shard.
+        let mut taken = vec![0; shards.len()];
+        core::array::from_fn(|_| {
+            let (shard, item) = shards
+                .iter()
+                .enumerate()
+                .filter_map(|(i, shard)| Some((i, shard.kshow_view().get(taken[i])?)))
+                .max_by_key(|(_, (seq, _))| *seq)?;
+            taken[shard] += 1;
+            Some(item.1.clone())
+        })
+    }
+
+    /// Consume and return element as a single [`KStack`], in the order
+    /// they would be popped.
+    pub fn into_kstack(self) -> KStack<T, K> {
+        let mut items: Vec<_> = self
+            .shards
+            .into_iter()
+            .flat_map(|shard| unpoisoned(shard.into_inner()))
+            .collect();
+        items.sort_unstable_by_key(|(seq, _)| *seq);
+        items.into_iter().map(|(_, item)| item).collect()
+    }
+
+    fn lock_all(&self) -> Vec<MutexGuard<'_, Shard<T, K>>> {
+        // always locked in the same order, so threads can't deadlock.
+        self.shards.iter().map(lock).collect()
+    }
+}
+
+fn pop_newest<T, const K: usize>(shards: &mut [MutexGuard<'_, Shard<T, K>>]) -> Option<T> {
+    let newest = shards
+        .iter_mut()
+        .filter(|shard| !shard.is_empty())
+        .max_by_key(|shard| shard.peek().map(|(seq, _)| *seq))?;
+    newest.pop().map(|(_, item)| item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_single_thread_lifo() {
+        let stack = ShardedKStack::<u32, 3>::new(4);
+        assert!(stack.is_empty());
+        for i in 0..10 {
+            stack.push(i);
+        }
+        assert_eq!(4, stack.shards());
+        assert_eq!(Some(9), stack.pop());
+        assert_eq!([Some(8), Some(7), Some(6)], stack.kshow());
+        assert_eq!([Some(8), Some(7), Some(6)], stack.kpop());
+        assert_eq!(
+            vec![5, 4, 3, 2, 1, 0],
+            stack.into_kstack().into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_many_producers() {
+        let stack = Arc::new(ShardedKStack::<usize, 4>::new(8));
+        let producers: Vec<_> = (0..8)
+            .map(|id| {
+                let stack = Arc::clone(&stack);
+                thread::spawn(move || {
+                    for i in 0..100 {
+                        stack.push(id * 1000 + i);
+                    }
+                })
+            })
+            .collect();
+        for producer in producers {
+            producer.join().unwrap();
+        }
+
+        assert_eq!(800, stack.len());
+        // pushes of each thread come back in LIFO order.
+        let mut last = [usize::MAX; 8];
+        while let Some(item) = stack.pop() {
+            let (id, i) = (item / 1000, item % 1000);
+            assert!(i < last[id]);
+            last[id] = i;
+        }
+        assert!(stack.is_empty());
+    }
+}
@@ -0,0 +1,184 @@
+//! Binary snapshots of a [`KStack`], enabled by `snapshot` feature.
+//!
+//! A snapshot is the magic bytes `KSTK`, a format version byte, K as a
+//! little-endian `u64`, then element from bottom to top encoded by
+//! `bincode`.
+
+use std::fmt;
+use std::io::{self, Read, Write};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::KStack;
+
+const MAGIC: [u8; 4] = *b"KSTK";
+const VERSION: u8 = 1;
+
+/// Error returned by [`KStack::save_to`] and [`KStack::load_from`].
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// Reading or writing failed.
+    Io(io::Error),
+    /// Element couldn't be encoded or decoded.
+    Encoding(bincode::Error),
+    /// Data doesn't start with snapshot magic bytes.
+    BadMagic,
+    /// Snapshot has a format version this crate can't read.
+    UnsupportedVersion(u8),
+    /// Snapshot is of a stack with another window size.
+    WindowMismatch {
+        /// K of the stack being loaded.
+        expected: usize,
+        /// K in the snapshot.
+        found: u64,
+    },
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::Io(error) => write!(f, "snapshot i/o failed: {}", error),
+            SnapshotError::Encoding(error) => write!(f, "snapshot encoding failed: {}", error),
+            SnapshotError::BadMagic => write!(f, "not a kstack snapshot"),
+            SnapshotError::UnsupportedVersion(version) => {
+                write!(f, "unsupported snapshot version {}", version)
+            }
+            SnapshotError::WindowMismatch { expected, found } => write!(
+                f,
+                "snapshot has window size {} but stack has {}",
+                found, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SnapshotError::Io(error) => Some(error),
+            SnapshotError::Encoding(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SnapshotError {
+    fn from(error: io::Error) -> Self {
+        SnapshotError::Io(error)
+    }
+}
+
+impl From<bincode::Error> for SnapshotError {
+    fn from(error: bincode::Error) -> Self {
+        SnapshotError::Encoding(error)
+    }
+}
+
+/// Snapshots, enabled by `snapshot` feature.
+impl<T, const K: usize> KStack<T, K> {
+    /// Write a snapshot of stack to `writer`, see [`snapshot`](self) for
+    /// the format.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<String, 2>::new();
+    ///     let mut snapshot = Vec::new();
+    ///
+    ///     stack.kpush(&["a".to_string(), "b".to_string()]);
+    ///     stack.save_to(&mut snapshot).unwrap();
+    ///
+    ///     let loaded = KStack::<String, 2>::load_from(&snapshot[..]).unwrap();
+    ///     assert_eq!(stack, loaded);
+    /// ```
+    pub fn save_to<W: Write>(&self, mut writer: W) -> Result<(), SnapshotError>
+    where
+        T: Serialize,
+    {
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&[VERSION])?;
+        writer.write_all(&(K as u64).to_le_bytes())?;
+        bincode::serialize_into(writer, self)?;
+        Ok(())
+    }
+
+    /// Read a stack from a snapshot written by [`save_to`](Self::save_to).
+    ///
+    /// # Errors
+    /// Returns [`SnapshotError::WindowMismatch`] if snapshot is of a stack
+    /// with another K, or another [`SnapshotError`] if it can't be read.
+    pub fn load_from<R: Read>(mut reader: R) -> Result<Self, SnapshotError>
+    where
+        T: DeserializeOwned,
+    {
+        let mut header = [0; 13];
+        reader.read_exact(&mut header)?;
+        if header[..4] != MAGIC {
+            return Err(SnapshotError::BadMagic);
+        }
+        if header[4] != VERSION {
+            return Err(SnapshotError::UnsupportedVersion(header[4]));
+        }
+        let mut k = [0; 8];
+        k.copy_from_slice(&header[5..]);
+        let found = u64::from_le_bytes(k);
+        if found != K as u64 {
+            return Err(SnapshotError::WindowMismatch { expected: K, found });
+        }
+        Ok(bincode::deserialize_from(reader)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot<const K: usize>(stack: &KStack<u32, K>) -> Vec<u8> {
+        let mut snapshot = Vec::new();
+        stack.save_to(&mut snapshot).unwrap();
+        snapshot
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let empty = KStack::<u32, 3>::new();
+        assert_eq!(empty, KStack::load_from(&snapshot(&empty)[..]).unwrap());
+
+        let stack = KStack::<u32, 3>::from(vec![1, 2, 3, 4]);
+        let bytes = snapshot(&stack);
+        assert_eq!(b"KSTK\x01\x03\0\0\0\0\0\0\0", &bytes[..13]);
+        assert_eq!(stack, KStack::load_from(&bytes[..]).unwrap());
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut bytes = snapshot(&KStack::<u32, 3>::from(vec![1]));
+
+        let error = KStack::<u32, 2>::load_from(&bytes[..]).unwrap_err();
+        assert!(matches!(
+            error,
+            SnapshotError::WindowMismatch {
+                expected: 2,
+                found: 3
+            }
+        ));
+        assert_eq!(
+            "snapshot has window size 3 but stack has 2",
+            error.to_string()
+        );
+
+        let error = KStack::<u32, 3>::load_from(&bytes[..bytes.len() - 1]).unwrap_err();
+        assert!(matches!(error, SnapshotError::Encoding(_)));
+        let error = KStack::<u32, 3>::load_from(&bytes[..5]).unwrap_err();
+        assert!(matches!(error, SnapshotError::Io(_)));
+
+        bytes[4] = 9;
+        let error = KStack::<u32, 3>::load_from(&bytes[..]).unwrap_err();
+        assert!(matches!(error, SnapshotError::UnsupportedVersion(9)));
+        bytes[0] = b'X';
+        let error = KStack::<u32, 3>::load_from(&bytes[..]).unwrap_err();
+        assert!(matches!(error, SnapshotError::BadMagic));
+    }
+}
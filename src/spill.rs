@@ -0,0 +1,291 @@
+//! Two-tier stack that spills old element to disk, enabled by `spill`
+//! feature.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use bytemuck::Pod;
+
+use crate::KStack;
+
+/// Makes temp file names unique inside a process.
+static NEXT_FILE: AtomicUsize = AtomicUsize::new(0);
+
+/// Stack keeping its top element in memory and older ones in a temp file.
+///
+/// When more than `threshold` element are in memory, the oldest are
+/// written to the file until only half of them are left, and they are
+/// read back when pops get close to them. at least K element are kept in
+/// memory, so [`kshow_ref`](Self::kshow_ref) never touches the file.
+///
+/// The temp file is removed when stack is dropped.
+pub struct SpillKStack<T, const K: usize> {
+    hot: KStack<T, K>,
+    file: File,
+    path: PathBuf,
+    spilled: usize,
+    threshold: usize,
+}
+
+impl<T: Pod, const K: usize> SpillKStack<T, K> {
+    /// Make a new SpillKStack keeping at most `threshold` element in
+    /// memory, spilling to a file in [`std::env::temp_dir`]. threshold is
+    /// raised to 2 * K if it's smaller.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::spill::SpillKStack;
+    ///
+    ///     let mut stack = SpillKStack::<u64, 3>::new(1024)?;
+    ///     # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn new(threshold: usize) -> io::Result<Self> {
+        let name = format!(
+            "kstack-spill-{}-{}",
+            std::process::id(),
+            NEXT_FILE.fetch_add(1, Ordering::Relaxed)
+        );
+        Self::with_path(threshold, std::env::temp_dir().join(name))
+    }
+
+    /// Like [`new`](Self::new), but spilling to file at `path`, which is
+    /// replaced if it exists.
+    pub fn with_path<P: AsRef<Path>>(threshold: usize, path: P) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)?;
+        Ok(SpillKStack {
+            hot: KStack::new(),
+            file,
+            path,
+            spilled: 0,
+            threshold: threshold.max(2 * K).max(2),
+        })
+    }
+
+    /// Number of element on stack, in memory and on disk.
+    pub fn len(&self) -> usize {
+        self.hot.len() + self.spilled
+    }
+
+    /// Whether there is no element on stack.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of element in the file.
+    pub fn spilled(&self) -> usize {
+        self.spilled
+    }
+
+    /// Most element kept in memory.
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Push a single element to stack, spilling old element if there are
+    /// too many in memory.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::spill::SpillKStack;
+    ///
+    ///     let mut stack = SpillKStack::<u64, 2>::new(4)?;
+    ///
+    ///     for i in 0..5 {
+    ///         stack.push(i)?;
+    ///     }
+    ///
+    ///     assert_eq!(5, stack.len());
+    ///     assert_eq!(3, stack.spilled());
+    ///     # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn push(&mut self, item: T) -> io::Result<()> {
+        self.hot.push(item);
+        if self.hot.len() > self.threshold {
+            self.spill()?;
+        }
+        Ok(())
+    }
+
+    /// Remove and get single element on top of stack, reading old element
+    /// back from the file if needed.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::spill::SpillKStack;
+    ///
+    ///     let mut stack = SpillKStack::<u64, 2>::new(4)?;
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4, 5, 6])?;
+    ///
+    ///     let mut popped = Vec::new();
+    ///     while let Some(item) = stack.pop()? {
+    ///         popped.push(item);
+    ///     }
+    ///     assert_eq!(vec![6, 5, 4, 3, 2, 1], popped);
+    ///     # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn pop(&mut self) -> io::Result<Option<T>> {
+        self.refill(1)?;
+        Ok(self.hot.pop())
+    }
+
+    /// Push K element to stack.
+    pub fn kpush(&mut self, items: &[T]) -> io::Result<()> {
+        items.iter().try_for_each(|&item| self.push(item))
+    }
+
+    /// Removes and return K top element on stack. nothing is popped if
+    /// reading the file fails.
+    pub fn kpop(&mut self) -> io::Result<[Option<T>; K]> {
+        self.refill(K)?;
+        Ok(self.hot.kpop())
+    }
+
+    /// return references to K top element on stack, they are always in
+    /// memory.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::spill::SpillKStack;
+    ///
+    ///     let mut stack = SpillKStack::<u64, 2>::new(4)?;
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4, 5, 6])?;
+    ///     stack.kpop()?;
+    ///     stack.kpop()?;
+    ///
+    ///     assert_eq!([Some(&2), Some(&1)], stack.kshow_ref());
+    ///     # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn kshow_ref(&self) -> [Option<&T>; K] {
+        self.hot.kshow_ref()
+    }
+
+    /// Write oldest element in memory to the file, keeping half of
+    /// threshold in memory.
+    fn spill(&mut self) -> io::Result<()> {
+        let count = self.hot.len() - self.threshold / 2;
+        let bytes = bytemuck::cast_slice(&self.hot.0[..count]);
+        self.file
+            .seek(SeekFrom::Start(Self::offset(self.spilled)))?;
+        self.file.write_all(bytes)?;
+        self.hot.0.drain(..count);
+        self.spilled += count;
+        Ok(())
+    }
+
+    /// Read element back from the file before popping `count`, so at
+    /// least K are still in memory after it. reading happens first so a
+    /// failed read doesn't lose popped element.
+    fn refill(&mut self, count: usize) -> io::Result<()> {
+        if self.spilled > 0 && self.hot.len() < K + count {
+            self.unspill()?;
+        }
+        Ok(())
+    }
+
+    /// Read up to half of threshold newest element in the file back to
+    /// memory, below the ones already there.
+    fn unspill(&mut self) -> io::Result<()> {
+        let count = self.spilled.min(self.threshold / 2);
+        let start = self.spilled - count;
+        let mut items = vec![T::zeroed(); count];
+        self.file.seek(SeekFrom::Start(Self::offset(start)))?;
+        self.file
+            .read_exact(bytemuck::cast_slice_mut(&mut items[..]))?;
+        items.append(&mut self.hot.0);
+        self.hot.0 = items;
+        self.spilled = start;
+        Ok(())
+    }
+
+    fn offset(index: usize) -> u64 {
+        (index * std::mem::size_of::<T>()) as u64
+    }
+}
+
+impl<T, const K: usize> Drop for SpillKStack<T, K> {
+    fn drop(&mut self) {
+        // nothing useful can be done if it fails, the file is only
+        // left behind in temp directory.
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut stack = SpillKStack::<u32, 3>::new(8).unwrap();
+        for i in 0..1000 {
+            stack.push(i).unwrap();
+            assert!(stack.hot.len() <= 8);
+            assert_eq!(Some(&i), stack.kshow_ref()[0]);
+        }
+        assert_eq!(1000, stack.len());
+        assert!(stack.spilled() >= 992);
+
+        for i in (0..1000).rev() {
+            assert!(stack.hot.len() >= 3.min(stack.len()));
+            assert_eq!(Some(i), stack.pop().unwrap());
+        }
+        assert_eq!(None, stack.pop().unwrap());
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn test_interleaved() {
+        let mut stack = SpillKStack::<u64, 2>::new(0).unwrap();
+        assert_eq!(4, stack.threshold());
+        let mut model = Vec::new();
+        for i in 0..200u64 {
+            if i % 3 == 2 {
+                let expected = [model.pop(), model.pop()];
+                assert_eq!(expected, stack.kpop().unwrap());
+            } else {
+                stack.kpush(&[i, i * 10]).unwrap();
+                model.extend([i, i * 10]);
+            }
+            let expected: Vec<_> = model.iter().rev().take(2).collect();
+            let window: Vec<_> = stack.kshow_ref().iter().flatten().copied().collect();
+            assert_eq!(expected, window);
+            assert_eq!(model.len(), stack.len());
+        }
+    }
+
+    #[test]
+    fn test_failed_read_keeps_element() {
+        let mut stack = SpillKStack::<u32, 2>::new(4).unwrap();
+        stack.kpush(&[1, 2, 3, 4, 5, 6]).unwrap();
+        stack.file.set_len(0).unwrap();
+
+        while stack.hot.len() > 2 {
+            stack.pop().unwrap();
+        }
+        let len = stack.len();
+        let window = stack.kshow_ref().map(Option::<&u32>::copied);
+        assert!(stack.kpop().is_err());
+        assert!(stack.pop().is_err());
+        assert_eq!(len, stack.len());
+        assert_eq!(window, stack.kshow_ref().map(Option::<&u32>::copied));
+    }
+
+    #[test]
+    fn test_file_removed_on_drop() {
+        let stack = SpillKStack::<u8, 2>::new(4).unwrap();
+        let path = stack.path.clone();
+        assert!(path.exists());
+        drop(stack);
+        assert!(!path.exists());
+    }
+}
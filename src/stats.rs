@@ -0,0 +1,153 @@
+use crate::{KStack, Storage};
+
+/// Counters of a [`KStack`], returned by [`KStack::stats`] and enabled by
+/// `stats` feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct KStackStats {
+    /// Number of element pushed.
+    pub pushes: usize,
+    /// Number of element popped or otherwise removed.
+    pub pops: usize,
+    /// Number of window pops, like [`KStack::kpop`] or
+    /// [`KStack::kpop_n`].
+    pub kpops: usize,
+    /// Number of element on stack.
+    pub depth: usize,
+    /// Largest number of element stack had.
+    pub max_depth: usize,
+}
+
+impl KStackStats {
+    pub(crate) const fn new(depth: usize) -> Self {
+        KStackStats {
+            pushes: 0,
+            pops: 0,
+            kpops: 0,
+            depth,
+            max_depth: depth,
+        }
+    }
+
+    pub(crate) fn pushed(&mut self, count: usize, depth: usize) {
+        self.pushes += count;
+        self.max_depth = self.max_depth.max(depth);
+    }
+
+    pub(crate) fn popped(&mut self, count: usize) {
+        self.pops += count;
+    }
+}
+
+/// Counters, enabled by `stats` feature.
+impl<T, const K: usize, S: Storage<T>> KStack<T, K, S> {
+    /// Get counters of stack since it was made or
+    /// [`reset_stats`](Self::reset_stats) was called.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3]);
+    ///     stack.kpop();
+    ///     stack.push(4);
+    ///
+    ///     let stats = stack.stats();
+    ///     assert_eq!(4, stats.pushes);
+    ///     assert_eq!(2, stats.pops);
+    ///     assert_eq!(1, stats.kpops);
+    ///     assert_eq!(2, stats.depth);
+    ///     assert_eq!(3, stats.max_depth);
+    /// ```
+    pub fn stats(&self) -> KStackStats {
+        KStackStats {
+            depth: self.len(),
+            ..self.2
+        }
+    }
+
+    /// Reset counters, max depth becomes current depth.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3]);
+    ///     stack.pop();
+    ///     stack.reset_stats();
+    ///
+    ///     assert_eq!(0, stack.stats().pushes);
+    ///     assert_eq!(2, stack.stats().max_depth);
+    /// ```
+    pub fn reset_stats(&mut self) {
+        self.2 = KStackStats::new(self.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(
+        pushes: usize,
+        pops: usize,
+        kpops: usize,
+        depth: usize,
+        max_depth: usize,
+    ) -> KStackStats {
+        KStackStats {
+            pushes,
+            pops,
+            kpops,
+            depth,
+            max_depth,
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut stack = KStack::<i32, 2>::from(vec![1, 2]);
+        assert_eq!(stats(0, 0, 0, 2, 2), stack.stats());
+
+        stack.kpush_iter(3..=7);
+        stack.kdup().unwrap();
+        assert_eq!(stats(7, 0, 0, 9, 9), stack.stats());
+
+        stack.kdrop().unwrap();
+        stack.pop();
+        stack.kpop_n(2);
+        stack.kpop_exact();
+        stack.apply_binop(|a, b| a + b).unwrap();
+        assert_eq!(stats(8, 9, 2, 1, 9), stack.stats());
+
+        stack.drain_top(1).next();
+        stack.push(1);
+        stack.clear();
+        assert_eq!(stats(9, 11, 2, 0, 9), stack.stats());
+    }
+
+    #[test]
+    fn test_moves() {
+        let mut stack = KStack::<i32, 2>::from(vec![1, 2, 3]);
+        let mut other = KStack::<i32, 2>::from(vec![4, 5]);
+        stack.append(&mut other);
+        assert_eq!(stats(0, 2, 0, 0, 2), other.stats());
+        assert_eq!(stats(2, 0, 0, 5, 5), stack.stats());
+
+        let (window, rest) = stack.split_window();
+        assert_eq!(stats(0, 0, 0, 2, 2), window.stats());
+        assert_eq!(stats(2, 2, 0, 3, 5), rest.stats());
+    }
+
+    #[test]
+    fn test_transaction() {
+        let mut stack = KStack::<i32, 2>::from(vec![1, 2]);
+        let mut transaction = stack.checkpoint();
+        transaction.kpop();
+        transaction.rollback();
+        assert_eq!(stats(2, 2, 0, 2, 2), stack.stats());
+    }
+}
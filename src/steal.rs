@@ -0,0 +1,207 @@
+//! Work-stealing stack for task schedulers.
+
+use std::sync::{Arc, Mutex};
+
+use crate::poison::lock;
+use crate::KDeque;
+
+/// Owner side of a work-stealing stack, it pushes and pops at the top.
+///
+/// Other threads take work from the bottom through [`Stealer`]s, K element
+/// at a time, so a thief gets the oldest and usually largest tasks in one
+/// go and the owner keeps the hot ones on top.
+pub struct Worker<T, const K: usize>(Arc<Mutex<KDeque<T, K>>>);
+
+/// Thief side of a work-stealing stack, made by [`Worker::stealer`].
+///
+/// It's cheap to clone and can be sent to other threads.
+pub struct Stealer<T, const K: usize>(Arc<Mutex<KDeque<T, K>>>);
+
+impl<T, const K: usize> Worker<T, K> {
+    /// Make a new Worker.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::steal::Worker;
+    ///
+    ///     let worker = Worker::<i32, 3>::new();
+    /// ```
+    pub fn new() -> Self {
+        Worker(Arc::new(Mutex::new(KDeque::new())))
+    }
+
+    /// Make a new [`Stealer`] taking element of this worker.
+    pub fn stealer(&self) -> Stealer<T, K> {
+        Stealer(Arc::clone(&self.0))
+    }
+
+    /// Number of element on stack.
+    pub fn len(&self) -> usize {
+        lock(&self.0).len()
+    }
+
+    /// Whether there is no element on stack.
+    pub fn is_empty(&self) -> bool {
+        lock(&self.0).is_empty()
+    }
+
+    /// Push a single element to top of stack.
+    pub fn push(&self, item: T) {
+        lock(&self.0).push_back(item);
+    }
+
+    /// Push K element to top of stack.
+    pub fn kpush(&self, items: &[T])
+    where
+        T: Clone,
+    {
+        lock(&self.0).kpush_back(items);
+    }
+
+    /// Remove and get single element on top of stack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::steal::Worker;
+    ///
+    ///     let worker = Worker::<i32, 3>::new();
+    ///
+    ///     worker.push(1);
+    ///     worker.push(2);
+    ///
+    ///     assert_eq!(Some(2), worker.pop());
+    /// ```
+    pub fn pop(&self) -> Option<T> {
+        lock(&self.0).pop_back()
+    }
+
+    /// Removes and return K top element on stack.
+    pub fn kpop(&self) -> [Option<T>; K] {
+        lock(&self.0).kpop_back()
+    }
+}
+
+impl<T, const K: usize> Default for Worker<T, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const K: usize> Stealer<T, K> {
+    /// Whether there is nothing to steal.
+    pub fn is_empty(&self) -> bool {
+        lock(&self.0).is_empty()
+    }
+
+    /// Remove and get single element at bottom of stack.
+    pub fn steal(&self) -> Option<T> {
+        lock(&self.0).pop_front()
+    }
+
+    /// Removes and return K bottom element of stack, oldest first. they
+    /// are taken under a single lock, so a batch never interleaves with
+    /// other thieves.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::steal::Worker;
+    ///
+    ///     let worker = Worker::<i32, 2>::new();
+    ///     let stealer = worker.stealer();
+    ///
+    ///     worker.kpush(&[1, 2, 3]);
+    ///
+    ///     assert_eq!([Some(1), Some(2)], stealer.steal_k());
+    ///     assert_eq!(Some(3), worker.pop());
+    /// ```
+    pub fn steal_k(&self) -> [Option<T>; K] {
+        lock(&self.0).kpop_front()
+    }
+
+    /// Steal K bottom element of stack and push them to top of `dest`,
+    /// oldest last so it's popped last. return number of element moved.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::steal::Worker;
+    ///
+    ///     let victim = Worker::<i32, 2>::new();
+    ///     let thief = Worker::<i32, 2>::new();
+    ///
+    ///     victim.kpush(&[1, 2, 3]);
+    ///
+    ///     assert_eq!(2, victim.stealer().steal_k_into(&thief));
+    ///     assert_eq!([Some(2), Some(1)], thief.kpop());
+    /// ```
+    pub fn steal_k_into(&self, dest: &Worker<T, K>) -> usize {
+        if Arc::ptr_eq(&self.0, &dest.0) {
+            return 0;
+        }
+        let stolen = self.steal_k();
+        let mut dest = lock(&dest.0);
+        let mut count = 0;
+        for item in IntoIterator::into_iter(stolen).flatten() {
+            dest.push_back(item);
+            count += 1;
+        }
+        count
+    }
+}
+
+impl<T, const K: usize> Clone for Stealer<T, K> {
+    fn clone(&self) -> Self {
+        Stealer(Arc::clone(&self.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    #[test]
+    fn test_owner_and_thief_ends() {
+        let worker = Worker::<u32, 2>::new();
+        let stealer = worker.stealer();
+        worker.kpush(&[1, 2, 3, 4, 5]);
+
+        assert_eq!(Some(1), stealer.steal());
+        assert_eq!([Some(5), Some(4)], worker.kpop());
+        assert_eq!([Some(2), Some(3)], stealer.steal_k());
+        assert!(stealer.is_empty());
+        assert_eq!([None, None], stealer.clone().steal_k());
+        assert_eq!(0, stealer.steal_k_into(&worker));
+    }
+
+    #[test]
+    fn test_every_item_taken_once() {
+        let worker = Worker::<usize, 4>::new();
+        for i in 0..1000 {
+            worker.push(i);
+        }
+        let total = Arc::new(AtomicUsize::new(0));
+
+        let thieves: Vec<_> = (0..4)
+            .map(|_| {
+                let stealer = worker.stealer();
+                let total = Arc::clone(&total);
+                thread::spawn(move || {
+                    let own = Worker::<usize, 4>::new();
+                    while stealer.steal_k_into(&own) > 0 {
+                        while let Some(item) = own.pop() {
+                            total.fetch_add(item, Ordering::Relaxed);
+                        }
+                    }
+                })
+            })
+            .collect();
+        while let Some(item) = worker.pop() {
+            total.fetch_add(item, Ordering::Relaxed);
+        }
+        for thief in thieves {
+            thief.join().unwrap();
+        }
+        assert_eq!((0..1000).sum::<usize>(), total.load(Ordering::Relaxed));
+    }
+}
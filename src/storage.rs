@@ -0,0 +1,552 @@
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::{mem, ptr};
+
+pub(crate) mod sealed {
+    pub trait Sealed {}
+}
+
+/// Backing storage of a [`KStack`](crate::KStack), holding element from
+/// bottom to top.
+///
+/// All the window logic of [`KStack`](crate::KStack) is written against
+/// this trait, so backends can be swapped without changing it. it's
+/// sealed, backends are provided by this crate, [`Vec`] is the default.
+pub trait Storage<T>: sealed::Sealed {
+    /// element from bottom to top.
+    fn as_slice(&self) -> &[T];
+
+    /// mutable element from bottom to top.
+    fn as_mut_slice(&mut self) -> &mut [T];
+
+    /// Push a single element on top.
+    fn push(&mut self, item: T);
+
+    /// Remove and get the top element.
+    fn pop(&mut self) -> Option<T>;
+
+    /// Keep only the `len` bottom element.
+    fn truncate(&mut self, len: usize);
+
+    /// Set number of element without dropping or initializing any.
+    ///
+    /// # Safety
+    /// `len` must not be more than current length, and element past it
+    /// must have been moved out through [`as_mut_slice`](Self::as_mut_slice)
+    /// with no change to storage since then.
+    unsafe fn set_len(&mut self, len: usize);
+
+    /// Number of element storage can hold without reallocating.
+    fn capacity(&self) -> usize;
+
+    /// Make room for at least `additional` more element.
+    fn reserve(&mut self, additional: usize);
+
+    /// Number of element.
+    fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    /// Whether there is no element.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Push all element of an iterator, in iteration order.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        items.into_iter().for_each(|item| self.push(item));
+    }
+}
+
+impl<T> sealed::Sealed for Vec<T> {}
+
+impl<T> Storage<T> for Vec<T> {
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+
+    fn push(&mut self, item: T) {
+        Vec::push(self, item);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        Vec::pop(self)
+    }
+
+    fn truncate(&mut self, len: usize) {
+        Vec::truncate(self, len);
+    }
+
+    unsafe fn set_len(&mut self, len: usize) {
+        Vec::set_len(self, len);
+    }
+
+    fn capacity(&self) -> usize {
+        Vec::capacity(self)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        Vec::reserve(self, additional);
+    }
+
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn extend<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        Extend::extend(self, items);
+    }
+}
+
+/// [`Storage`] sharing its buffer behind an [`Arc`] between clones, and
+/// copying it only when a shared buffer is changed.
+///
+/// Cloning a stack on it is O(1), which makes it cheap to keep snapshots.
+/// popping from a shared buffer only copies element that stay.
+pub struct CowStorage<T>(Arc<Vec<T>>);
+
+impl<T> CowStorage<T> {
+    /// Whether buffer is shared with a clone, so the next change copies
+    /// it.
+    pub fn is_shared(&self) -> bool {
+        Arc::strong_count(&self.0) > 1
+    }
+}
+
+impl<T> Clone for CowStorage<T> {
+    fn clone(&self) -> Self {
+        CowStorage(Arc::clone(&self.0))
+    }
+}
+
+impl<T> Default for CowStorage<T> {
+    fn default() -> Self {
+        CowStorage(Arc::new(Vec::new()))
+    }
+}
+
+impl<T> From<Vec<T>> for CowStorage<T> {
+    fn from(items: Vec<T>) -> Self {
+        CowStorage(Arc::new(items))
+    }
+}
+
+impl<T> sealed::Sealed for CowStorage<T> {}
+
+impl<T: Clone> Storage<T> for CowStorage<T> {
+    fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        Arc::make_mut(&mut self.0).as_mut_slice()
+    }
+
+    fn push(&mut self, item: T) {
+        Arc::make_mut(&mut self.0).push(item);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        if let Some(items) = Arc::get_mut(&mut self.0) {
+            return items.pop();
+        }
+        let item = self.0.last()?.clone();
+        self.truncate(self.0.len() - 1);
+        Some(item)
+    }
+
+    fn truncate(&mut self, len: usize) {
+        if let Some(items) = Arc::get_mut(&mut self.0) {
+            items.truncate(len);
+        } else if len < self.0.len() {
+            self.0 = Arc::new(self.0[..len].to_vec());
+        }
+    }
+
+    unsafe fn set_len(&mut self, len: usize) {
+        // `as_mut_slice` made buffer unique.
+        match Arc::get_mut(&mut self.0) {
+            Some(items) => items.set_len(len),
+            None => unreachable!("set_len on a shared buffer"),
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        Arc::make_mut(&mut self.0).reserve(additional);
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn extend<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        Extend::extend(Arc::make_mut(&mut self.0), items);
+    }
+}
+
+/// [`KStack`](crate::KStack) with O(1) copy-on-write [`Clone`].
+///
+/// # Example
+/// ```
+///     use kstack::CowKStack;
+///
+///     let mut stack = CowKStack::<i32, 2>::default();
+///     stack.kpush(&[1, 2, 3]);
+///
+///     let snapshot = stack.clone();
+///     assert!(stack.storage().is_shared());
+///
+///     stack.pop();
+///     assert!(!stack.storage().is_shared());
+///     assert_eq!([Some(2), Some(1)], stack.kshow());
+///     assert_eq!([Some(3), Some(2)], snapshot.kshow());
+/// ```
+pub type CowKStack<T, const K: usize> = crate::KStack<T, K, CowStorage<T>>;
+
+/// [`Storage`] that drops its bottom element in amortized O(1), used by
+/// [`CappedKStack`](crate::CappedKStack) to evict like a ring buffer.
+///
+/// Evicted element leave a gap at the front of buffer, which is closed
+/// once it's as large as the element left, so element stay contiguous
+/// and buffer holds at most twice as many as are on stack.
+pub struct RingStorage<T> {
+    buf: Vec<T>,
+    /// Number of element at front of `buf` that are already dropped.
+    head: usize,
+}
+
+impl<T> RingStorage<T> {
+    /// Drop `count` bottom element, or all of them if there are less.
+    pub(crate) fn evict_front(&mut self, count: usize) -> usize {
+        let count = count.min(self.len());
+        let start = self.head;
+        self.head += count;
+        // SAFETY: element in `start..head` were live and are now outside
+        // of live range, so each is dropped once. if a drop panics the
+        // rest are leaked instead.
+        unsafe {
+            let evicted = ptr::slice_from_raw_parts_mut(self.buf.as_mut_ptr().add(start), count);
+            ptr::drop_in_place(evicted);
+        }
+        if self.head >= self.len() {
+            self.compact();
+        }
+        count
+    }
+
+    /// Return live element as a [`Vec`].
+    pub(crate) fn into_vec(mut self) -> Vec<T> {
+        self.compact();
+        mem::take(&mut self.buf)
+    }
+
+    /// Move live element to front of buffer, closing the gap.
+    fn compact(&mut self) {
+        let live = self.len();
+        // SAFETY: live element are moved once to the front, over dropped
+        // ones, then buffer forgets the rest. nothing in between can
+        // panic.
+        unsafe {
+            let ptr = self.buf.as_mut_ptr();
+            ptr::copy(ptr.add(self.head), ptr, live);
+            self.buf.set_len(live);
+        }
+        self.head = 0;
+    }
+}
+
+impl<T: Clone> Clone for RingStorage<T> {
+    fn clone(&self) -> Self {
+        RingStorage::from(self.as_slice().to_vec())
+    }
+}
+
+impl<T> Default for RingStorage<T> {
+    fn default() -> Self {
+        RingStorage::from(Vec::new())
+    }
+}
+
+impl<T> From<Vec<T>> for RingStorage<T> {
+    fn from(items: Vec<T>) -> Self {
+        RingStorage {
+            buf: items,
+            head: 0,
+        }
+    }
+}
+
+impl<T> Drop for RingStorage<T> {
+    fn drop(&mut self) {
+        let live = self.len();
+        // SAFETY: only live element are dropped, buffer forgets all of
+        // them first so it only frees memory.
+        unsafe {
+            let items = ptr::slice_from_raw_parts_mut(self.buf.as_mut_ptr().add(self.head), live);
+            self.buf.set_len(0);
+            ptr::drop_in_place(items);
+        }
+    }
+}
+
+impl<T> sealed::Sealed for RingStorage<T> {}
+
+impl<T> Storage<T> for RingStorage<T> {
+    fn as_slice(&self) -> &[T] {
+        &self.buf[self.head..]
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.buf[self.head..]
+    }
+
+    fn push(&mut self, item: T) {
+        self.buf.push(item);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.buf.pop()
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.buf.truncate(self.head + len);
+    }
+
+    unsafe fn set_len(&mut self, len: usize) {
+        self.buf.set_len(self.head + len);
+    }
+
+    fn capacity(&self) -> usize {
+        self.buf.capacity() - self.head
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.buf.reserve(additional);
+    }
+
+    fn len(&self) -> usize {
+        self.buf.len() - self.head
+    }
+
+    fn extend<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        Extend::extend(&mut self.buf, items);
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array> sealed::Sealed for smallvec::SmallVec<A> {}
+
+/// Keeps up to `A::size()` element inline and only allocates on the heap
+/// beyond that, enabled by `smallvec` feature.
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array> Storage<A::Item> for smallvec::SmallVec<A> {
+    fn as_slice(&self) -> &[A::Item] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [A::Item] {
+        self
+    }
+
+    fn push(&mut self, item: A::Item) {
+        smallvec::SmallVec::push(self, item);
+    }
+
+    fn pop(&mut self) -> Option<A::Item> {
+        smallvec::SmallVec::pop(self)
+    }
+
+    fn truncate(&mut self, len: usize) {
+        smallvec::SmallVec::truncate(self, len);
+    }
+
+    unsafe fn set_len(&mut self, len: usize) {
+        smallvec::SmallVec::set_len(self, len);
+    }
+
+    fn capacity(&self) -> usize {
+        smallvec::SmallVec::capacity(self)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        smallvec::SmallVec::reserve(self, additional);
+    }
+
+    fn len(&self) -> usize {
+        smallvec::SmallVec::len(self)
+    }
+
+    fn extend<I: IntoIterator<Item = A::Item>>(&mut self, items: I) {
+        Extend::extend(self, items);
+    }
+}
+
+/// [`KStack`](crate::KStack) keeping up to N element inline, without a
+/// heap allocation, enabled by `smallvec` feature.
+///
+/// # Example
+/// ```
+///     use kstack::SmallKStack;
+///
+///     let mut stack = SmallKStack::<i32, 2, 8>::default();
+///
+///     stack.kpush(&[1, 2, 3]);
+///
+///     assert_eq!(8, stack.capacity());
+///     assert_eq!([Some(3), Some(2)], stack.kpop());
+/// ```
+#[cfg(feature = "smallvec")]
+pub type SmallKStack<T, const K: usize, const N: usize> =
+    crate::KStack<T, K, smallvec::SmallVec<[T; N]>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::KStack;
+
+    /// Storage using only the required methods.
+    #[derive(Default)]
+    struct Minimal(Vec<i32>);
+
+    impl sealed::Sealed for Minimal {}
+
+    impl Storage<i32> for Minimal {
+        fn as_slice(&self) -> &[i32] {
+            &self.0
+        }
+
+        fn as_mut_slice(&mut self) -> &mut [i32] {
+            &mut self.0
+        }
+
+        fn push(&mut self, item: i32) {
+            self.0.push(item);
+        }
+
+        fn pop(&mut self) -> Option<i32> {
+            self.0.pop()
+        }
+
+        fn truncate(&mut self, len: usize) {
+            self.0.truncate(len);
+        }
+
+        unsafe fn set_len(&mut self, len: usize) {
+            self.0.set_len(len);
+        }
+
+        fn capacity(&self) -> usize {
+            self.0.capacity()
+        }
+
+        fn reserve(&mut self, additional: usize) {
+            self.0.reserve(additional);
+        }
+    }
+
+    #[test]
+    fn test_window_logic_on_other_storage() {
+        let mut stack: KStack<i32, 2, Minimal> = (1..=5).collect();
+        assert_eq!(5, stack.len());
+        assert_eq!([Some(5), Some(4)], stack.kshow());
+
+        stack.kswap().unwrap();
+        stack.kdup().unwrap();
+        assert_eq!(&[2, 3], stack.kshow_slice());
+        assert_eq!(Some([3, 2]), stack.kpop_exact());
+        assert_eq!(vec![3, 2, 5, 4, 1], stack.kpop_n(10));
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn test_cow_clone() {
+        let mut stack: crate::CowKStack<String, 2> =
+            ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+        let snapshot = stack.clone();
+        assert!(Arc::ptr_eq(&stack.0 .0, &snapshot.0 .0));
+
+        assert_eq!(Some("c".to_string()), stack.pop());
+        assert!(!stack.storage().is_shared());
+        assert_eq!(2, stack.len());
+        assert_eq!(3, snapshot.len());
+
+        let mut copy = snapshot.clone();
+        copy.kmap(|item| item.push('!'));
+        assert_eq!(&["b!", "c!"], copy.kshow_slice());
+        assert_eq!(&["b", "c"], snapshot.kshow_slice());
+
+        let mut unique = copy;
+        unique.push("d".to_string());
+        assert!(!unique.storage().is_shared());
+        assert_eq!(4, unique.len());
+    }
+
+    #[cfg(feature = "smallvec")]
+    #[test]
+    fn test_smallvec_spill() {
+        let mut stack = crate::SmallKStack::<i32, 2, 4>::default();
+        stack.kpush(&[1, 2, 3, 4]);
+        assert!(!stack.0.spilled());
+
+        stack.push(5);
+        assert!(stack.0.spilled());
+        assert_eq!(Some([5, 4]), stack.kpop_exact());
+        assert_eq!(vec![3, 2, 1], stack.kpop_n(10));
+    }
+
+    #[test]
+    fn test_ring_evict() {
+        use std::rc::Rc;
+
+        let item = Rc::new(());
+        let mut storage = RingStorage::default();
+        for _ in 0..10 {
+            Storage::push(&mut storage, Rc::clone(&item));
+        }
+
+        assert_eq!(3, storage.evict_front(3));
+        assert_eq!(7, storage.len());
+        assert_eq!(8, Rc::strong_count(&item));
+        assert_eq!(7, storage.evict_front(100));
+        assert!(storage.is_empty());
+        assert_eq!(1, Rc::strong_count(&item));
+
+        for _ in 0..4 {
+            Storage::push(&mut storage, Rc::clone(&item));
+        }
+        storage.evict_front(1);
+        drop(storage.clone());
+        assert_eq!(4, Rc::strong_count(&item));
+        drop(storage);
+        assert_eq!(1, Rc::strong_count(&item));
+    }
+
+    #[test]
+    fn test_ring_window_logic() {
+        let mut stack = KStack::<i32, 2, RingStorage<i32>>::default();
+        for i in 0..100 {
+            stack.push(i);
+            if stack.len() > 5 {
+                stack.storage_mut().evict_front(1);
+            }
+            // gap never grows beyond element on stack.
+            assert!(stack.storage().buf.len() <= 2 * 5);
+        }
+        assert_eq!(&[95, 96, 97, 98, 99], stack.as_slice());
+        assert_eq!([Some(99), Some(98)], stack.kpop());
+        assert_eq!(Some(97), stack.pop());
+        stack.truncate_to_depth(1);
+        assert_eq!(vec![95], stack.into_inner().into_vec());
+    }
+}
@@ -0,0 +1,319 @@
+//! Thread-safe stack.
+
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::time::Duration;
+
+use crate::poison::{self, unpoisoned};
+use crate::KStack;
+
+/// [`KStack`] that can be shared between threads.
+///
+/// Every operation, including the window ones, holds a lock for its whole
+/// duration, so a `kpush` or `kpop` never interleaves with operations of
+/// other threads. use [`with`](Self::with) to run several operations
+/// atomically.
+///
+/// [`kpop_blocking`](Self::kpop_blocking) and
+/// [`kpop_timeout`](Self::kpop_timeout) wait for a full window, pushes
+/// wake them once there are K element.
+///
+/// If a thread panics while holding the lock, for example inside `with`,
+/// stack may be half changed, so later operations panic too, like a
+/// poisoned [`Mutex`].
+pub struct SyncKStack<T, const K: usize>(Mutex<KStack<T, K>>, Condvar);
+
+impl<T, const K: usize> SyncKStack<T, K> {
+    /// Make a new SyncKStack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::sync::SyncKStack;
+    ///
+    ///     let stack = SyncKStack::<i32, 3>::new();
+    /// ```
+    pub fn new() -> Self {
+        SyncKStack::from(KStack::new())
+    }
+
+    /// Consume and return underlying stack.
+    pub fn into_inner(self) -> KStack<T, K> {
+        unpoisoned(self.0.into_inner())
+    }
+
+    /// Run `f` with exclusive access to underlying stack, so all of its
+    /// operations are atomic with respect to other threads. they are not
+    /// undone if `f` panics, stack is poisoned instead.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::sync::SyncKStack;
+    ///
+    ///     let stack = SyncKStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2]);
+    ///     let sum = stack.with(|stack| {
+    ///         let [a, b, _] = stack.kpop();
+    ///         let sum = a.unwrap() + b.unwrap();
+    ///         stack.push(sum);
+    ///         sum
+    ///     });
+    ///
+    ///     assert_eq!(3, sum);
+    ///     assert_eq!(Some(3), stack.pop());
+    /// ```
+    pub fn with<R>(&self, f: impl FnOnce(&mut KStack<T, K>) -> R) -> R {
+        let mut stack = self.lock();
+        let result = f(&mut stack);
+        self.notify(&stack);
+        result
+    }
+
+    /// Number of element on stack.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether there is no element on stack.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Remove and get single element on top of stack.
+    pub fn pop(&self) -> Option<T> {
+        self.lock().pop()
+    }
+
+    /// Push a single element to stack.
+    pub fn push(&self, item: T) {
+        let mut stack = self.lock();
+        stack.push(item);
+        self.notify(&stack);
+    }
+
+    /// Removes and return K top element on stack atomically.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::sync::SyncKStack;
+    ///
+    ///     let stack = SyncKStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]);
+    ///
+    ///     assert_eq!([Some(4), Some(3), Some(2)], stack.kpop());
+    ///     assert_eq!([Some(1), None, None], stack.kpop());
+    /// ```
+    pub fn kpop(&self) -> [Option<T>; K] {
+        self.lock().kpop()
+    }
+
+    /// Removes and return K top element on stack atomically, only if
+    /// there are at least K element.
+    pub fn kpop_exact(&self) -> Option<[T; K]> {
+        self.lock().kpop_exact()
+    }
+
+    /// return K top element on stack.
+    pub fn kshow(&self) -> [Option<T>; K]
+    where
+        T: Clone,
+    {
+        self.lock().kshow()
+    }
+
+    /// Push K element to stack atomically.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::sync::SyncKStack;
+    ///
+    ///     let stack = SyncKStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[4, 5, 6]);
+    ///
+    ///     assert_eq!([Some(6), Some(5), Some(4)], stack.kshow());
+    /// ```
+    pub fn kpush(&self, items: &[T])
+    where
+        T: Clone,
+    {
+        let mut stack = self.lock();
+        stack.kpush(items);
+        self.notify(&stack);
+    }
+
+    /// Wait until there are at least K element, then removes and return
+    /// K top element on stack atomically.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::sync::SyncKStack;
+    ///     use std::sync::Arc;
+    ///     use std::thread;
+    ///
+    ///     let stack = Arc::new(SyncKStack::<i32, 2>::new());
+    ///     let producer = {
+    ///         let stack = Arc::clone(&stack);
+    ///         thread::spawn(move || stack.kpush(&[1, 2]))
+    ///     };
+    ///
+    ///     assert_eq!([2, 1], stack.kpop_blocking());
+    ///     producer.join().unwrap();
+    /// ```
+    pub fn kpop_blocking(&self) -> [T; K] {
+        let stack = self.1.wait_while(self.lock(), |stack| stack.len() < K);
+        let mut stack = unpoisoned(stack);
+        // waited until there is a full window.
+        stack.kpop_exact().unwrap()
+    }
+
+    /// Like [`kpop_blocking`](Self::kpop_blocking), but waits at most
+    /// `timeout` and returns `None` if there is no full window by then.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::sync::SyncKStack;
+    ///     use std::time::Duration;
+    ///
+    ///     let stack = SyncKStack::<i32, 2>::new();
+    ///
+    ///     stack.push(1);
+    ///     assert_eq!(None, stack.kpop_timeout(Duration::from_millis(10)));
+    ///
+    ///     stack.push(2);
+    ///     assert_eq!(Some([2, 1]), stack.kpop_timeout(Duration::from_millis(10)));
+    /// ```
+    pub fn kpop_timeout(&self, timeout: Duration) -> Option<[T; K]> {
+        let result = self
+            .1
+            .wait_timeout_while(self.lock(), timeout, |stack| stack.len() < K);
+        let (mut stack, _) = unpoisoned(result);
+        stack.kpop_exact()
+    }
+
+    fn notify(&self, stack: &KStack<T, K>) {
+        if stack.len() >= K {
+            self.1.notify_all();
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, KStack<T, K>> {
+        poison::lock(&self.0)
+    }
+}
+
+impl<T, const K: usize> Default for SyncKStack<T, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const K: usize> From<KStack<T, K>> for SyncKStack<T, K> {
+    fn from(stack: KStack<T, K>) -> Self {
+        SyncKStack(Mutex::new(stack), Condvar::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    fn assert_send_sync<S: Send + Sync>() {}
+
+    #[test]
+    fn test_send_sync() {
+        assert_send_sync::<SyncKStack<String, 3>>();
+    }
+
+    #[test]
+    fn test_panic_in_with_poisons() {
+        let stack = Arc::new(SyncKStack::<i32, 2>::new());
+        stack.kpush(&[1, 2]);
+        let result = {
+            let stack = Arc::clone(&stack);
+            thread::spawn(move || {
+                stack.with(|stack| {
+                    stack.kpop();
+                    panic!("result never pushed");
+                })
+            })
+            .join()
+        };
+        assert!(result.is_err());
+
+        let result = thread::spawn(move || stack.len()).join();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_kpush_atomic() {
+        let stack = Arc::new(SyncKStack::<usize, 4>::new());
+        let threads: Vec<_> = (0..8)
+            .map(|id| {
+                let stack = Arc::clone(&stack);
+                thread::spawn(move || {
+                    for _ in 0..200 {
+                        stack.kpush(&[id; 4]);
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        let mut stack = Arc::try_unwrap(stack).ok().unwrap().into_inner();
+        assert_eq!(8 * 200 * 4, stack.len());
+        while let Some(window) = stack.kpop_exact() {
+            assert!(window.iter().all(|&item| item == window[0]));
+        }
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn test_kpop_atomic() {
+        let stack = Arc::new(SyncKStack::<usize, 4>::new());
+        for id in 0..400 {
+            stack.kpush(&[id; 4]);
+        }
+
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                let stack = Arc::clone(&stack);
+                thread::spawn(move || {
+                    let mut windows = 0;
+                    while let Some(window) = stack.kpop_exact() {
+                        assert!(window.iter().all(|&item| item == window[0]));
+                        windows += 1;
+                    }
+                    windows
+                })
+            })
+            .collect();
+        let windows: usize = threads.into_iter().map(|t| t.join().unwrap()).sum();
+
+        assert_eq!(400, windows);
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn test_kpop_blocking() {
+        let stack = Arc::new(SyncKStack::<usize, 3>::new());
+        let consumers: Vec<_> = (0..4)
+            .map(|_| {
+                let stack = Arc::clone(&stack);
+                thread::spawn(move || (0..25).map(|_| stack.kpop_blocking().len()).sum::<usize>())
+            })
+            .collect();
+        for i in 0..300 {
+            stack.push(i);
+        }
+
+        let popped: usize = consumers.into_iter().map(|t| t.join().unwrap()).sum();
+        assert_eq!(300, popped);
+        assert!(stack.is_empty());
+        assert_eq!(None, stack.kpop_timeout(Duration::from_millis(1)));
+    }
+}
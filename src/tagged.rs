@@ -0,0 +1,149 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+
+use crate::{KStack, Storage};
+
+/// [`KStack`] marked with a `Tag` type, so stacks with the same element
+/// but different roles, like operand and return stacks of a VM, are
+/// different types and can't be mixed up.
+///
+/// It derefs to the underlying [`KStack`] for all stack operations. the
+/// tag is only a marker and doesn't affect `Send` or `Sync`.
+///
+/// # Example
+/// ```compile_fail
+///     use kstack::TaggedKStack;
+///
+///     struct Operands;
+///     struct Returns;
+///
+///     fn call(returns: &mut TaggedKStack<usize, 1, Returns>, pc: usize) {
+///         returns.push(pc);
+///     }
+///
+///     let mut operands = TaggedKStack::<usize, 1, Operands>::new();
+///     call(&mut operands, 7);
+/// ```
+pub struct TaggedKStack<T, const K: usize, Tag, S = Vec<T>> {
+    stack: KStack<T, K, S>,
+    tag: PhantomData<fn() -> Tag>,
+}
+
+impl<T, const K: usize, Tag> TaggedKStack<T, K, Tag> {
+    /// Make a new TaggedKStack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::TaggedKStack;
+    ///
+    ///     struct Operands;
+    ///
+    ///     let mut stack = TaggedKStack::<i32, 2, Operands>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3]);
+    ///     assert_eq!([Some(3), Some(2)], stack.kshow());
+    /// ```
+    pub fn new() -> Self {
+        Self::from_kstack(KStack::new())
+    }
+}
+
+impl<T, const K: usize, Tag, S: Storage<T>> TaggedKStack<T, K, Tag, S> {
+    /// Tag an existing stack.
+    pub fn from_kstack(stack: KStack<T, K, S>) -> Self {
+        TaggedKStack {
+            stack,
+            tag: PhantomData,
+        }
+    }
+
+    /// Consume and return underlying stack.
+    pub fn into_kstack(self) -> KStack<T, K, S> {
+        self.stack
+    }
+}
+
+impl<T, const K: usize, Tag, S> Deref for TaggedKStack<T, K, Tag, S> {
+    type Target = KStack<T, K, S>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.stack
+    }
+}
+
+impl<T, const K: usize, Tag, S> DerefMut for TaggedKStack<T, K, Tag, S> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.stack
+    }
+}
+
+impl<T, const K: usize, Tag, S: Clone> Clone for TaggedKStack<T, K, Tag, S> {
+    fn clone(&self) -> Self {
+        TaggedKStack {
+            stack: self.stack.clone(),
+            tag: PhantomData,
+        }
+    }
+}
+
+impl<T, const K: usize, Tag, S: Storage<T> + Default> Default for TaggedKStack<T, K, Tag, S> {
+    fn default() -> Self {
+        Self::from_kstack(KStack::default())
+    }
+}
+
+impl<T: fmt::Debug, const K: usize, Tag, S: Storage<T>> fmt::Debug for TaggedKStack<T, K, Tag, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.stack.fmt(f)
+    }
+}
+
+impl<T: PartialEq, const K: usize, Tag, S: Storage<T>> PartialEq for TaggedKStack<T, K, Tag, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.stack == other.stack
+    }
+}
+
+impl<T: Eq, const K: usize, Tag, S: Storage<T>> Eq for TaggedKStack<T, K, Tag, S> {}
+
+impl<T, const K: usize, Tag, S: Storage<T>> From<KStack<T, K, S>> for TaggedKStack<T, K, Tag, S> {
+    fn from(stack: KStack<T, K, S>) -> Self {
+        Self::from_kstack(stack)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    struct Operands;
+    struct Returns;
+
+    fn assert_send<T: Send>(_: &T) {}
+
+    #[test]
+    fn test_tagged() {
+        let mut operands = TaggedKStack::<u32, 2, Operands>::default();
+        let mut returns = TaggedKStack::<u32, 2, Returns>::from(KStack::from(vec![10]));
+        operands.push(1);
+        operands.push(2);
+        returns.push(operands.pop().unwrap());
+
+        assert_eq!("[1]", format!("{:?}", operands));
+        assert_eq!(&[10, 2], returns.kshow_slice());
+        assert_eq!(returns.clone(), returns);
+        assert_eq!(
+            vec![2, 10],
+            returns.into_kstack().into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_tag_does_not_affect_send() {
+        let stack = TaggedKStack::<u32, 2, Rc<()>>::new();
+        assert_send(&stack);
+    }
+}
@@ -0,0 +1,158 @@
+//! Tail of a log, for status panes and the like.
+
+use alloc::string::String;
+
+use crate::{CappedKStack, OverflowPolicy};
+
+/// Keeps the most recent `max_lines` lines of a log, with the K most recent
+/// ones available to render.
+///
+/// It's built on a [`CappedKStack`] with [`OverflowPolicy::Evict`], so
+/// oldest lines are dropped and memory stays bounded.
+pub struct LogTail<const K: usize> {
+    lines: CappedKStack<String, K>,
+}
+
+impl<const K: usize> LogTail<K> {
+    /// Make a new LogTail keeping at most `max_lines` lines.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::tail::LogTail;
+    ///
+    ///     let mut tail = LogTail::<3>::new(100);
+    /// ```
+    pub fn new(max_lines: usize) -> Self {
+        LogTail {
+            lines: CappedKStack::new(max_lines, OverflowPolicy::Evict),
+        }
+    }
+
+    /// Most lines kept.
+    pub fn max_lines(&self) -> usize {
+        self.lines.max_depth()
+    }
+
+    /// Number of lines kept.
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Whether no line is kept.
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Add a line, dropping the oldest one if tail is full. `line` is split
+    /// like [`str::lines`] if it has several lines, so a trailing newline
+    /// doesn't add an empty line.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::tail::LogTail;
+    ///
+    ///     let mut tail = LogTail::<2>::new(100);
+    ///
+    ///     tail.push("booting");
+    ///     tail.push("ready\nlistening");
+    ///
+    ///     assert_eq!(["ready", "listening"], tail.kshow());
+    ///     assert_eq!(3, tail.len());
+    /// ```
+    pub fn push(&mut self, line: &str) {
+        for line in line.lines() {
+            // evicting never fails.
+            let _ = self.lines.push(String::from(line));
+        }
+    }
+
+    /// K most recent lines, oldest first like they are shown.
+    pub fn kshow(&self) -> &[String] {
+        self.lines.kshow_slice()
+    }
+
+    /// All kept lines, oldest first.
+    pub fn lines(&self) -> &[String] {
+        self.lines.as_kstack().as_slice()
+    }
+
+    /// K most recent lines joined by `\n`, oldest first.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::tail::LogTail;
+    ///
+    ///     let mut tail = LogTail::<2>::new(100);
+    ///
+    ///     tail.push("a");
+    ///     tail.push("b");
+    ///     tail.push("c");
+    ///
+    ///     assert_eq!("b\nc", tail.render());
+    /// ```
+    pub fn render(&self) -> String {
+        self.kshow().join("\n")
+    }
+
+    /// Add each line of `reader`, keeping only the most recent ones as they
+    /// are read, and return how many were read.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::tail::LogTail;
+    ///
+    ///     let mut tail = LogTail::<2>::new(3);
+    ///
+    ///     tail.push_lines("1\n2\n3\n4\n5\n".as_bytes()).unwrap();
+    ///
+    ///     assert_eq!(["3", "4", "5"], tail.lines());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn push_lines<R: std::io::BufRead>(&mut self, reader: R) -> std::io::Result<usize> {
+        self.lines.push_lines(reader)
+    }
+
+    /// Forget all lines.
+    pub fn clear(&mut self) {
+        while self.lines.pop().is_some() {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tail() {
+        let mut tail = LogTail::<2>::new(3);
+        assert_eq!(3, tail.max_lines());
+        assert_eq!("", tail.render());
+
+        tail.push("one\r\ntwo");
+        assert_eq!("one\ntwo", tail.render());
+
+        tail.push("three");
+        tail.push("four");
+        assert_eq!(["two", "three", "four"], tail.lines());
+        assert_eq!("three\nfour", tail.render());
+
+        tail.clear();
+        assert!(tail.is_empty());
+
+        tail.push("ready\n");
+        assert_eq!(["ready"], tail.lines());
+        tail.push("a\r\n\r\nb\r\n");
+        assert_eq!(["a", "", "b"], tail.lines());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_push_lines() {
+        let mut tail = LogTail::<1>::new(2);
+        tail.push("old");
+        assert_eq!(3, tail.push_lines(&b"a\nb\r\nc"[..]).unwrap());
+        assert_eq!(["b", "c"], tail.lines());
+        assert_eq!(1, tail.push_lines(&b"\n"[..]).unwrap());
+        assert_eq!([""], tail.kshow());
+    }
+}
@@ -0,0 +1,203 @@
+//! Stack with a timestamp per element, enabled by `std` feature.
+
+use std::time::{Duration, Instant};
+
+use crate::KStack;
+
+/// [`KStack`] that records when each element was pushed, for windows
+/// bounded by time as well as by count.
+///
+/// Timestamps given to [`push_at`](Self::push_at) must not be older than
+/// the one on top, so element stay sorted by time.
+pub struct TimedKStack<T, const K: usize> {
+    stack: KStack<(Instant, T), K>,
+}
+
+impl<T, const K: usize> TimedKStack<T, K> {
+    /// Make a new TimedKStack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::TimedKStack;
+    ///
+    ///     let mut stack = TimedKStack::<i32, 3>::new();
+    /// ```
+    pub fn new() -> Self {
+        TimedKStack {
+            stack: KStack::new(),
+        }
+    }
+
+    /// Number of element on stack.
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Whether there is no element on stack.
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    /// Get underlying stack of timestamps and element, for all the
+    /// read-only operations.
+    pub fn as_kstack(&self) -> &KStack<(Instant, T), K> {
+        &self.stack
+    }
+
+    /// Push a single element to stack, pushed now.
+    pub fn push(&mut self, item: T) {
+        self.push_at(item, Instant::now());
+    }
+
+    /// Push a single element to stack, pushed at `timestamp`.
+    ///
+    /// # Panics
+    /// Panics if `timestamp` is older than timestamp of top element.
+    pub fn push_at(&mut self, item: T, timestamp: Instant) {
+        assert!(
+            self.stack.peek().map_or(true, |&(top, _)| top <= timestamp),
+            "timestamp is older than top element"
+        );
+        self.stack.push((timestamp, item));
+    }
+
+    /// Remove and get single element on top of stack with its timestamp.
+    pub fn pop(&mut self) -> Option<(Instant, T)> {
+        self.stack.pop()
+    }
+
+    /// return references to K top element on stack.
+    pub fn kshow_ref(&self) -> [Option<&T>; K] {
+        let window = self.stack.kshow_ref();
+        window.map(|entry| entry.map(|(_, item)| item))
+    }
+
+    /// Element pushed in the last `duration`, top first, not limited to K.
+    pub fn kshow_since(&self, duration: Duration) -> impl Iterator<Item = &T> + '_ {
+        self.entries_from(cutoff(duration))
+    }
+
+    /// Element pushed at or after `timestamp`, top first, not limited to
+    /// K.
+    ///
+    /// # Example
+    /// ```
+    ///     use std::time::{Duration, Instant};
+    ///     use kstack::TimedKStack;
+    ///
+    ///     let mut stack = TimedKStack::<&str, 2>::new();
+    ///     let start = Instant::now();
+    ///
+    ///     stack.push_at("boot", start);
+    ///     stack.push_at("warn", start + Duration::from_secs(5));
+    ///     stack.push_at("stop", start + Duration::from_secs(9));
+    ///
+    ///     let recent: Vec<_> = stack.kshow_newer_than(start + Duration::from_secs(5)).collect();
+    ///     assert_eq!(vec![&"stop", &"warn"], recent);
+    /// ```
+    pub fn kshow_newer_than(&self, timestamp: Instant) -> impl Iterator<Item = &T> + '_ {
+        self.entries_from(Some(timestamp))
+    }
+
+    /// Remove element pushed more than `duration` ago and return how many
+    /// were removed.
+    pub fn evict_older_than(&mut self, duration: Duration) -> usize {
+        match cutoff(duration) {
+            Some(timestamp) => self.evict_before(timestamp),
+            None => 0,
+        }
+    }
+
+    /// Remove element pushed before `timestamp` and return how many were
+    /// removed.
+    ///
+    /// # Example
+    /// ```
+    ///     use std::time::{Duration, Instant};
+    ///     use kstack::TimedKStack;
+    ///
+    ///     let mut stack = TimedKStack::<i32, 2>::new();
+    ///     let start = Instant::now();
+    ///
+    ///     for i in 0..5 {
+    ///         stack.push_at(i, start + Duration::from_secs(i as u64));
+    ///     }
+    ///
+    ///     assert_eq!(3, stack.evict_before(start + Duration::from_secs(3)));
+    ///     assert_eq!([Some(&4), Some(&3)], stack.kshow_ref());
+    ///     assert_eq!(2, stack.len());
+    /// ```
+    pub fn evict_before(&mut self, timestamp: Instant) -> usize {
+        let len = self.stack.len();
+        self.stack.retain(|&(pushed, _)| pushed >= timestamp);
+        len - self.stack.len()
+    }
+
+    /// Element pushed at or after `timestamp`, or all of them if it's
+    /// `None`, top first.
+    fn entries_from(&self, timestamp: Option<Instant>) -> impl Iterator<Item = &T> + '_ {
+        let entries = self.stack.as_slice();
+        let start = match timestamp {
+            Some(timestamp) => entries.partition_point(|&(pushed, _)| pushed < timestamp),
+            None => 0,
+        };
+        entries[start..].iter().rev().map(|(_, item)| item)
+    }
+}
+
+impl<T, const K: usize> Default for TimedKStack<T, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Instant `duration` ago, `None` if it's before any instant.
+fn cutoff(duration: Duration) -> Option<Instant> {
+    Instant::now().checked_sub(duration)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_windows() {
+        let mut stack = TimedKStack::<u32, 2>::default();
+        let base = Instant::now();
+        for i in 0..4 {
+            stack.push_at(i, base + Duration::from_secs(30 * u64::from(i)));
+        }
+
+        let recent: Vec<_> = stack
+            .kshow_newer_than(base + Duration::from_secs(50))
+            .collect();
+        assert_eq!(vec![&3, &2], recent);
+        let all: Vec<_> = stack.kshow_since(Duration::from_secs(u64::MAX)).collect();
+        assert_eq!(vec![&3, &2, &1, &0], all);
+
+        assert_eq!(2, stack.evict_before(base + Duration::from_secs(45)));
+        assert_eq!(0, stack.evict_before(base + Duration::from_secs(45)));
+        assert_eq!(0, stack.evict_older_than(Duration::from_secs(u64::MAX)));
+        assert_eq!(2, stack.len());
+        assert_eq!([Some(&3), Some(&2)], stack.kshow_ref());
+
+        let (pushed, item) = stack.pop().unwrap();
+        assert_eq!(3, item);
+        assert_eq!(base + Duration::from_secs(90), pushed);
+
+        let mut stack = TimedKStack::<u32, 2>::new();
+        stack.push(4);
+        let recent: Vec<_> = stack.kshow_since(Duration::from_secs(3600)).collect();
+        assert_eq!(vec![&4], recent);
+        assert!(stack.pop().unwrap().0 >= base);
+    }
+
+    #[test]
+    #[should_panic(expected = "timestamp is older than top element")]
+    fn test_push_at_older() {
+        let mut stack = TimedKStack::<u32, 2>::new();
+        let now = Instant::now();
+        stack.push_at(1, now + Duration::from_secs(1));
+        stack.push_at(2, now);
+    }
+}
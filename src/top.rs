@@ -0,0 +1,151 @@
+use alloc::vec::Vec;
+
+/// Keeps the K largest element pushed so far, sorted largest first.
+///
+/// Wrap element in [`core::cmp::Reverse`] to keep the K smallest instead.
+/// pushes are O(K), element equal to a kept one rank after it.
+pub struct KTop<T, const K: usize>(Vec<T>);
+
+impl<T: Ord, const K: usize> KTop<T, K> {
+    /// Make a new empty KTop.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KTop;
+    ///
+    ///     let mut top = KTop::<u32, 3>::new();
+    /// ```
+    pub fn new() -> Self {
+        KTop(Vec::with_capacity(K))
+    }
+
+    /// Number of element kept, at most K.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether no element is kept.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Push an element, keeping it if it's among the K largest so far.
+    /// return the element that is no longer kept, `item` itself if it's
+    /// not large enough.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KTop;
+    ///
+    ///     let mut top = KTop::<u32, 2>::new();
+    ///
+    ///     assert_eq!(None, top.push(5));
+    ///     assert_eq!(None, top.push(1));
+    ///     assert_eq!(Some(1), top.push(7));
+    ///     assert_eq!(Some(3), top.push(3));
+    ///
+    ///     assert_eq!(&[7, 5], top.kshow());
+    /// ```
+    pub fn push(&mut self, item: T) -> Option<T> {
+        if K == 0 {
+            return Some(item);
+        }
+        if self.0.len() == K && self.0.last().map_or(false, |min| item <= *min) {
+            return Some(item);
+        }
+
+        let index = self.0.partition_point(|kept| *kept >= item);
+        self.0.insert(index, item);
+        match self.0.len() > K {
+            true => self.0.pop(),
+            false => None,
+        }
+    }
+
+    /// Push all element of an iterator.
+    pub fn kpush_iter<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        for item in items {
+            self.push(item);
+        }
+    }
+
+    /// Kept element, largest first.
+    pub fn kshow(&self) -> &[T] {
+        &self.0
+    }
+
+    /// Largest element kept.
+    pub fn max(&self) -> Option<&T> {
+        self.0.first()
+    }
+
+    /// Smallest element kept, that a new element must be larger than to be
+    /// kept once there are K.
+    pub fn min(&self) -> Option<&T> {
+        self.0.last()
+    }
+
+    /// Consume and return kept element, largest first.
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        self.0
+    }
+
+    /// Forget all element.
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+impl<T: Ord, const K: usize> Default for KTop<T, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord, const K: usize> Extend<T> for KTop<T, K> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.kpush_iter(iter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cmp::Reverse;
+
+    #[test]
+    fn test_against_sort() {
+        let mut top = KTop::<u32, 4>::default();
+        let mut all = Vec::new();
+        let mut seed = 7u32;
+        for _ in 0..500 {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            let item = seed / 7 % 40;
+            all.push(item);
+            top.push(item);
+
+            all.sort_unstable_by(|a, b| b.cmp(a));
+            assert_eq!(&all[..all.len().min(4)], top.kshow());
+        }
+        assert_eq!(Some(&39), top.max());
+        assert_eq!(top.kshow().to_vec(), top.into_sorted_vec());
+    }
+
+    #[test]
+    fn test_smallest() {
+        let mut top = KTop::<Reverse<i32>, 2>::new();
+        top.extend([5, -1, 3, 8].iter().map(|&x| Reverse(x)));
+        assert_eq!(&[Reverse(-1), Reverse(3)], top.kshow());
+        assert_eq!(Some(&Reverse(3)), top.min());
+
+        top.clear();
+        assert!(top.is_empty());
+    }
+
+    #[test]
+    fn test_zero() {
+        let mut top = KTop::<i32, 0>::new();
+        assert_eq!(Some(1), top.push(1));
+        assert_eq!(0, top.len());
+    }
+}
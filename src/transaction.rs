@@ -0,0 +1,270 @@
+use alloc::vec::Vec;
+
+use crate::{KStack, KWindow};
+
+/// What's needed to undo changes made since a checkpoint.
+struct Log<T> {
+    /// Length of stack at checkpoint.
+    base: usize,
+    /// Smallest length of stack since checkpoint, element below it are
+    /// untouched.
+    low: usize,
+    /// Element that were on stack at checkpoint and got popped, from top
+    /// to bottom.
+    saved: Vec<T>,
+}
+
+/// Transaction over a [`KStack`], returned by [`KStack::checkpoint`].
+///
+/// Changes made through the transaction are undone by
+/// [`rollback`](Self::rollback) or by dropping it, and kept by
+/// [`commit`](Self::commit). only element that were on stack at
+/// checkpoint and then popped are cloned, not the whole stack.
+///
+/// Transactions nest with [`checkpoint`](Self::checkpoint), committing an
+/// inner transaction moves its changes to the outer one.
+pub struct Transaction<'a, T, const K: usize> {
+    stack: &'a mut KStack<T, K>,
+    log: Log<T>,
+    parent: Option<&'a mut Log<T>>,
+    done: bool,
+}
+
+impl<'a, T, const K: usize> Transaction<'a, T, K> {
+    pub(crate) fn new(stack: &'a mut KStack<T, K>) -> Self {
+        Self::with_parent(stack, None)
+    }
+
+    fn with_parent(stack: &'a mut KStack<T, K>, parent: Option<&'a mut Log<T>>) -> Self {
+        let base = stack.len();
+        Transaction {
+            stack,
+            log: Log {
+                base,
+                low: base,
+                saved: Vec::new(),
+            },
+            parent,
+            done: false,
+        }
+    }
+
+    /// Start a nested transaction.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     let mut outer = stack.checkpoint();
+    ///     outer.push(1);
+    ///
+    ///     let mut inner = outer.checkpoint();
+    ///     inner.push(2);
+    ///     inner.rollback();
+    ///
+    ///     outer.push(3);
+    ///     outer.commit();
+    ///
+    ///     assert_eq!(&[1, 3], stack.kshow_slice());
+    /// ```
+    pub fn checkpoint(&mut self) -> Transaction<'_, T, K> {
+        Transaction::with_parent(self.stack, Some(&mut self.log))
+    }
+
+    /// Number of element on stack.
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Whether there is no element on stack.
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    /// Read-only access to stack.
+    pub fn as_kstack(&self) -> &KStack<T, K> {
+        self.stack
+    }
+
+    /// return a borrowed view of K top element on stack.
+    pub fn kshow_view(&self) -> KWindow<'_, T, K> {
+        self.stack.kshow_view()
+    }
+
+    /// return K top element on stack.
+    pub fn kshow(&self) -> [Option<T>; K]
+    where
+        T: Clone,
+    {
+        self.stack.kshow()
+    }
+
+    /// Push a single element to stack.
+    pub fn push(&mut self, item: T) {
+        self.stack.push(item);
+    }
+
+    /// Push K element to stack.
+    pub fn kpush(&mut self, items: &[T])
+    where
+        T: Clone,
+    {
+        self.stack.kpush(items);
+    }
+
+    /// Remove and get single element on top of stack.
+    pub fn pop(&mut self) -> Option<T>
+    where
+        T: Clone,
+    {
+        let item = self.stack.pop()?;
+        let len = self.stack.len();
+        if len < self.log.low {
+            self.log.low = len;
+            self.log.saved.push(item.clone());
+        }
+        Some(item)
+    }
+
+    /// Removes and return K top element on stack.
+    pub fn kpop(&mut self) -> [Option<T>; K]
+    where
+        T: Clone,
+    {
+        core::array::from_fn(|_| self.pop())
+    }
+
+    /// Keep changes made since checkpoint.
+    pub fn commit(mut self) {
+        self.done = true;
+        let log = &mut self.log;
+        if let Some(parent) = self.parent.as_mut() {
+            // element below the parent low point were untouched before
+            // this transaction, so parent needs them to roll back. they
+            // were popped last, so they are at the end of saved element.
+            if log.low < parent.low {
+                let start = log.saved.len() - (parent.low - log.low);
+                parent.saved.extend(log.saved.drain(start..));
+                parent.low = log.low;
+            }
+        }
+    }
+
+    /// Undo changes made since checkpoint, same as dropping the
+    /// transaction.
+    pub fn rollback(self) {}
+
+    fn restore(&mut self) {
+        self.stack.truncate_to_depth(self.log.low);
+        self.stack.kpush_iter(self.log.saved.drain(..).rev());
+        debug_assert_eq!(self.log.base, self.stack.len());
+    }
+}
+
+impl<T, const K: usize> Drop for Transaction<'_, T, K> {
+    fn drop(&mut self) {
+        if !self.done {
+            self.restore();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::KStack;
+
+    fn stack(items: &[i32]) -> KStack<i32, 2> {
+        items.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_rollback() {
+        let mut stack = stack(&[1, 2, 3]);
+
+        let mut transaction = stack.checkpoint();
+        transaction.push(4);
+        assert_eq!([Some(4), Some(3)], transaction.kpop());
+        assert_eq!([Some(2), Some(1)], transaction.kpop());
+        transaction.kpush(&[7, 8, 9]);
+        assert_eq!(Some(9), transaction.pop());
+        transaction.rollback();
+
+        assert_eq!(vec![3, 2, 1], stack.kpop_n(5));
+    }
+
+    #[test]
+    fn test_commit() {
+        let mut stack = stack(&[1, 2, 3]);
+
+        let mut transaction = stack.checkpoint();
+        transaction.pop();
+        transaction.push(5);
+        transaction.commit();
+
+        assert_eq!(vec![5, 2, 1], stack.kpop_n(5));
+    }
+
+    #[test]
+    fn test_drop_rolls_back() {
+        let mut stack = stack(&[1, 2]);
+        {
+            let mut transaction = stack.checkpoint();
+            transaction.kpop();
+            assert!(transaction.is_empty());
+        }
+        assert_eq!(&[1, 2], stack.kshow_slice());
+    }
+
+    #[test]
+    fn test_nested_commit_then_outer_rollback() {
+        let mut stack = stack(&[1, 2, 3, 4]);
+
+        let mut outer = stack.checkpoint();
+        outer.pop();
+        outer.push(10);
+
+        let mut inner = outer.checkpoint();
+        assert_eq!([Some(10), Some(3)], inner.kpop());
+        assert_eq!(Some(2), inner.pop());
+        inner.commit();
+
+        assert_eq!(&[1], outer.as_kstack().kshow_slice());
+        outer.rollback();
+
+        assert_eq!(vec![4, 3, 2, 1], stack.kpop_n(5));
+    }
+
+    #[test]
+    fn test_nested_rollback() {
+        let mut stack = stack(&[1, 2, 3]);
+
+        let mut outer = stack.checkpoint();
+        outer.pop();
+
+        let mut inner = outer.checkpoint();
+        inner.kpop();
+        inner.push(7);
+        drop(inner);
+
+        assert_eq!(&[1, 2], outer.as_kstack().kshow_slice());
+        outer.push(8);
+        outer.commit();
+
+        assert_eq!(vec![8, 2, 1], stack.kpop_n(5));
+    }
+
+    #[test]
+    fn test_nested_commit_both() {
+        let mut stack = stack(&[1, 2, 3]);
+
+        let mut outer = stack.checkpoint();
+        let mut inner = outer.checkpoint();
+        inner.kpop();
+        inner.commit();
+        outer.commit();
+
+        assert_eq!(vec![1], stack.kpop_n(5));
+    }
+}
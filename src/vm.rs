@@ -0,0 +1,290 @@
+//! Tiny Forth-like virtual machine running on a [`KStack`].
+//!
+//! Window instructions like [`KDup`](Instruction::KDup) and
+//! [`KSwap`](Instruction::KSwap) work on K element at once, so the same
+//! program behaves like `DUP` or `2DUP` depending on K.
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Add, Mul, Sub};
+
+use crate::{KStack, KStackError};
+
+/// Instruction of a [`Vm`] program.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Instruction<T> {
+    /// Push a value.
+    Push(T),
+    /// Drop top element.
+    Pop,
+    /// Drop K top element, see [`KStack::kdrop`].
+    KPop,
+    /// Duplicate K top element, see [`KStack::kdup`].
+    KDup,
+    /// Swap K top element with the K below them, see [`KStack::kswap`].
+    KSwap,
+    /// Copy the K element below the top K over them, see
+    /// [`KStack::kover`].
+    KOver,
+    /// Pop two element and push their sum.
+    Add,
+    /// Pop two element and push their difference.
+    Sub,
+    /// Pop two element and push their product.
+    Mul,
+    /// Continue at given instruction.
+    Jmp(usize),
+    /// Pop top element and continue at given instruction if it's zero,
+    /// `T::default()`.
+    Jz(usize),
+    /// Stop the program.
+    Halt,
+}
+
+/// Error returned when a [`Vm`] program fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VmError {
+    /// Instruction at `pc` didn't have enough element on stack.
+    Stack {
+        /// Index of failing instruction.
+        pc: usize,
+        /// Error of stack operation.
+        error: KStackError,
+    },
+    /// Jump at `pc` to an instruction past end of program.
+    InvalidJump {
+        /// Index of failing instruction.
+        pc: usize,
+        /// Target of the jump.
+        target: usize,
+    },
+}
+
+impl fmt::Display for VmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VmError::Stack { pc, error } => write!(f, "instruction {}: {}", pc, error),
+            VmError::InvalidJump { pc, target } => {
+                write!(f, "instruction {}: invalid jump to {}", pc, target)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VmError {}
+
+/// Virtual machine executing a program of [`Instruction`]s against a
+/// [`KStack`].
+///
+/// the program stops at [`Halt`](Instruction::Halt) or by running past its
+/// last instruction.
+pub struct Vm<T, const K: usize> {
+    program: Vec<Instruction<T>>,
+    pc: usize,
+    stack: KStack<T, K>,
+}
+
+impl<T, const K: usize> Vm<T, K>
+where
+    T: Clone + PartialEq + Default + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    /// Make a new Vm for program, with an empty stack.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::vm::{Instruction::*, Vm};
+    ///
+    ///     let mut vm = Vm::<i64, 1>::new(vec![Push(2), Push(3), Add]);
+    /// ```
+    pub fn new(program: Vec<Instruction<T>>) -> Self {
+        Vm {
+            program,
+            pc: 0,
+            stack: KStack::new(),
+        }
+    }
+
+    /// Index of next instruction to execute.
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    /// Stack of the program.
+    pub fn stack(&self) -> &KStack<T, K> {
+        &self.stack
+    }
+
+    /// Consume vm and return its stack.
+    pub fn into_kstack(self) -> KStack<T, K> {
+        self.stack
+    }
+
+    /// Whether the program stopped.
+    pub fn is_halted(&self) -> bool {
+        self.pc >= self.program.len()
+    }
+
+    /// Execute next instruction, return `false` if program already
+    /// stopped. on error pc stays at the failing instruction.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::vm::{Instruction::*, Vm};
+    ///
+    ///     let mut vm = Vm::<i64, 2>::new(vec![Push(1), Push(2), KDup]);
+    ///
+    ///     assert_eq!(Ok(true), vm.step());
+    ///     assert_eq!(Ok(true), vm.step());
+    ///     assert_eq!(Ok(true), vm.step());
+    ///     assert_eq!(Ok(false), vm.step());
+    ///
+    ///     assert_eq!(&[1, 2], vm.stack().kshow_slice());
+    ///     assert_eq!(4, vm.stack().len());
+    /// ```
+    pub fn step(&mut self) -> Result<bool, VmError> {
+        let pc = self.pc;
+        let instruction = match self.program.get(pc) {
+            Some(instruction) => instruction,
+            None => return Ok(false),
+        };
+        if let &Instruction::Jmp(target) | &Instruction::Jz(target) = instruction {
+            if target > self.program.len() {
+                return Err(VmError::InvalidJump { pc, target });
+            }
+        }
+
+        let stack = &mut self.stack;
+        let mut next = pc + 1;
+        let result = match instruction {
+            Instruction::Push(value) => {
+                stack.push(value.clone());
+                Ok(())
+            }
+            Instruction::Pop => stack.try_pop().map(drop),
+            Instruction::KPop => stack.kdrop(),
+            Instruction::KDup => stack.kdup(),
+            Instruction::KSwap => stack.kswap(),
+            Instruction::KOver => stack.kover(),
+            Instruction::Add => stack.apply_binop(|a, b| a + b),
+            Instruction::Sub => stack.apply_binop(|a, b| a - b),
+            Instruction::Mul => stack.apply_binop(|a, b| a * b),
+            &Instruction::Jmp(target) => {
+                next = target;
+                Ok(())
+            }
+            &Instruction::Jz(target) => stack.try_pop().map(|value| {
+                if value == T::default() {
+                    next = target;
+                }
+            }),
+            Instruction::Halt => {
+                next = self.program.len();
+                Ok(())
+            }
+        };
+
+        result.map_err(|error| VmError::Stack { pc, error })?;
+        self.pc = next;
+        Ok(true)
+    }
+
+    /// Run program until it stops.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::vm::{Instruction::*, Vm};
+    ///
+    ///     // sum of 4 + 3 + 2 + 1, keeping sum and counter on stack.
+    ///     let mut vm = Vm::<i64, 1>::new(vec![
+    ///         Push(0), Push(4),
+    ///         KDup, Jz(11),
+    ///         KSwap, KOver, Add, KSwap,
+    ///         Push(1), Sub,
+    ///         Jmp(2),
+    ///         Pop,
+    ///     ]);
+    ///     vm.run().unwrap();
+    ///
+    ///     assert_eq!([Some(10)], vm.stack().kshow());
+    /// ```
+    pub fn run(&mut self) -> Result<(), VmError> {
+        while self.step()? {}
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Instruction::*;
+    use super::*;
+
+    #[test]
+    fn test_window_instructions() {
+        let mut vm = Vm::<i32, 2>::new(vec![Push(1), Push(2), Push(3), Push(4), KSwap, KOver]);
+        vm.run().unwrap();
+        assert_eq!(vec![4, 3, 2, 1, 4, 3], vm.into_kstack().kpop_n(10));
+
+        let mut vm = Vm::<i32, 2>::new(vec![Push(1), Push(2), Push(3), KPop]);
+        vm.run().unwrap();
+        assert_eq!(vec![1], vm.into_kstack().kpop_n(10));
+    }
+
+    #[test]
+    fn test_halt() {
+        let mut vm = Vm::<i32, 1>::new(vec![Push(1), Halt, Push(2)]);
+        vm.run().unwrap();
+        assert!(vm.is_halted());
+        assert_eq!(3, vm.pc());
+        assert_eq!(1, vm.stack().len());
+        assert_eq!(Ok(false), vm.step());
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut vm = Vm::<i32, 1>::new(vec![Push(1), Add]);
+        assert_eq!(
+            Err(VmError::Stack {
+                pc: 1,
+                error: KStackError::Underflow {
+                    requested: 2,
+                    available: 1
+                }
+            }),
+            vm.run()
+        );
+        assert_eq!(1, vm.pc());
+        assert_eq!(1, vm.stack().len());
+
+        assert_eq!(Ok(()), Vm::<i32, 1>::new(vec![Jmp(2), Halt]).run());
+        let mut vm = Vm::<i32, 1>::new(vec![Jmp(3), Halt]);
+        assert_eq!(Err(VmError::InvalidJump { pc: 0, target: 3 }), vm.step());
+        assert_eq!(0, vm.pc());
+    }
+
+    #[test]
+    fn test_jz() {
+        let program = vec![Jz(3), Push(10), Halt, Push(20)];
+
+        let mut vm = Vm::<i32, 1>::new(program.clone());
+        vm.stack.push(0);
+        vm.run().unwrap();
+        assert_eq!([Some(20)], vm.stack().kshow());
+
+        let mut vm = Vm::<i32, 1>::new(program);
+        vm.stack.push(7);
+        vm.run().unwrap();
+        assert_eq!([Some(10)], vm.stack().kshow());
+    }
+
+    #[test]
+    fn test_jz_invalid_jump() {
+        let mut vm = Vm::<i32, 1>::new(vec![Jz(5)]);
+        vm.stack.push(0);
+        for _ in 0..2 {
+            assert_eq!(Err(VmError::InvalidJump { pc: 0, target: 5 }), vm.step());
+            assert_eq!(0, vm.pc());
+            assert_eq!([Some(0)], vm.stack().kshow());
+        }
+    }
+}
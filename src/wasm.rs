@@ -0,0 +1,141 @@
+//! JavaScript bindings, enabled by `wasm` feature.
+//!
+//! [`NumberKStack`] holds JS numbers and returns windows as
+//! `Float64Array`, [`StringKStack`] holds strings and returns windows as
+//! arrays. window size is given to the constructor at runtime, and a
+//! window size of 0 throws an `Error`. windows are top first, like
+//! [`KStack::kpop`](crate::KStack::kpop).
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use wasm_bindgen::prelude::{wasm_bindgen, JsError};
+
+use crate::dynamic::DynKStack;
+
+/// Stack of JS numbers.
+#[wasm_bindgen]
+pub struct NumberKStack {
+    stack: DynKStack<f64>,
+}
+
+#[wasm_bindgen]
+impl NumberKStack {
+    /// Make a new stack with window size `k`, throws if `k` is 0.
+    #[wasm_bindgen(constructor)]
+    pub fn new(k: usize) -> Result<NumberKStack, JsError> {
+        match DynKStack::new(k) {
+            Some(stack) => Ok(NumberKStack { stack }),
+            None => Err(JsError::new("window size must be at least 1")),
+        }
+    }
+
+    /// Number of element on stack.
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Push a single element to stack.
+    pub fn push(&mut self, item: f64) {
+        self.stack.push(item);
+    }
+
+    /// Remove and get single element on top of stack.
+    pub fn pop(&mut self) -> Option<f64> {
+        self.stack.pop()
+    }
+
+    /// Push element in order, so the last one ends up on top.
+    pub fn kpush(&mut self, items: &[f64]) {
+        self.stack.kpush(items.iter().copied());
+    }
+
+    /// Removes and return window, top first.
+    pub fn kpop(&mut self) -> Vec<f64> {
+        self.stack.kpop(usize::MAX)
+    }
+
+    /// return window, top first.
+    pub fn kshow(&self) -> Vec<f64> {
+        self.stack.kshow(usize::MAX).copied().collect()
+    }
+}
+
+/// Stack of strings.
+#[wasm_bindgen]
+pub struct StringKStack {
+    stack: DynKStack<String>,
+}
+
+#[wasm_bindgen]
+impl StringKStack {
+    /// Make a new stack with window size `k`, throws if `k` is 0.
+    #[wasm_bindgen(constructor)]
+    pub fn new(k: usize) -> Result<StringKStack, JsError> {
+        match DynKStack::new(k) {
+            Some(stack) => Ok(StringKStack { stack }),
+            None => Err(JsError::new("window size must be at least 1")),
+        }
+    }
+
+    /// Number of element on stack.
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Push a single element to stack.
+    pub fn push(&mut self, item: String) {
+        self.stack.push(item);
+    }
+
+    /// Remove and get single element on top of stack.
+    pub fn pop(&mut self) -> Option<String> {
+        self.stack.pop()
+    }
+
+    /// Push element in order, so the last one ends up on top.
+    pub fn kpush(&mut self, items: Vec<String>) {
+        self.stack.kpush(items);
+    }
+
+    /// Removes and return window, top first.
+    pub fn kpop(&mut self) -> Vec<String> {
+        self.stack.kpop(usize::MAX)
+    }
+
+    /// return window, top first.
+    pub fn kshow(&self) -> Vec<String> {
+        self.stack.kshow(usize::MAX).cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_number() {
+        let mut stack = NumberKStack::new(2).unwrap();
+        stack.kpush(&[1.0, 2.0, 3.0]);
+        stack.push(4.0);
+        assert_eq!(4, stack.length());
+        assert_eq!(vec![4.0, 3.0], stack.kshow());
+        assert_eq!(Some(4.0), stack.pop());
+        assert_eq!(vec![3.0, 2.0], stack.kpop());
+        assert_eq!(vec![1.0], stack.kpop());
+        assert_eq!(None, stack.pop());
+    }
+
+    #[test]
+    fn test_string() {
+        let mut stack = StringKStack::new(2).unwrap();
+        stack.kpush(vec!["a".into(), "b".into()]);
+        stack.push("c".into());
+        assert_eq!(3, stack.length());
+        assert_eq!(vec!["c", "b"], stack.kshow());
+        assert_eq!(Some("c".into()), stack.pop());
+        assert_eq!(vec!["b", "a"], stack.kpop());
+        assert!(stack.kpop().is_empty());
+    }
+}
@@ -0,0 +1,145 @@
+use alloc::collections::VecDeque;
+
+use crate::StackObserver;
+
+/// Depth threshold crossed, reported by [`Watermarks::poll`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WatermarkEvent {
+    /// Stack grew to high watermark.
+    High,
+    /// Stack shrank to low watermark after reaching high watermark.
+    Low,
+}
+
+/// [`StackObserver`] that tracks depth of an
+/// [`ObservedKStack`](crate::ObservedKStack) and records when it crosses
+/// low and high watermarks, for backpressure.
+///
+/// [`WatermarkEvent::High`] is recorded when depth grows to high
+/// watermark, then nothing until depth shrinks to low watermark and
+/// [`WatermarkEvent::Low`] is recorded, so events alternate. events are
+/// kept until polled.
+pub struct Watermarks {
+    low: usize,
+    high: usize,
+    depth: usize,
+    high_reached: bool,
+    events: VecDeque<WatermarkEvent>,
+}
+
+impl Watermarks {
+    /// Make new Watermarks for an empty stack.
+    ///
+    /// # Panics
+    /// Panics if `low` is not less than `high`.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::{ObservedKStack, WatermarkEvent, Watermarks};
+    ///
+    ///     let mut stack = ObservedKStack::<i32, 2, _>::new(Watermarks::new(1, 3));
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]);
+    ///     assert_eq!(Some(WatermarkEvent::High), stack.observer_mut().poll());
+    ///
+    ///     stack.kpop();
+    ///     assert_eq!(None, stack.observer_mut().poll());
+    ///
+    ///     stack.pop();
+    ///     assert_eq!(Some(WatermarkEvent::Low), stack.observer_mut().poll());
+    /// ```
+    pub fn new(low: usize, high: usize) -> Self {
+        assert!(
+            low < high,
+            "low watermark {} is not less than high watermark {}",
+            low,
+            high
+        );
+        Watermarks {
+            low,
+            high,
+            depth: 0,
+            high_reached: false,
+            events: VecDeque::new(),
+        }
+    }
+
+    /// Low watermark.
+    pub fn low(&self) -> usize {
+        self.low
+    }
+
+    /// High watermark.
+    pub fn high(&self) -> usize {
+        self.high
+    }
+
+    /// Depth of observed stack.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Whether stack reached high watermark and didn't shrink to low
+    /// watermark since, for example to tell if a producer should wait.
+    pub fn is_high(&self) -> bool {
+        self.high_reached
+    }
+
+    /// Remove and get oldest recorded event.
+    pub fn poll(&mut self) -> Option<WatermarkEvent> {
+        self.events.pop_front()
+    }
+}
+
+impl<T> StackObserver<T> for Watermarks {
+    fn on_push(&mut self, _: &T) {
+        self.depth += 1;
+        if !self.high_reached && self.depth >= self.high {
+            self.high_reached = true;
+            self.events.push_back(WatermarkEvent::High);
+        }
+    }
+
+    fn on_pop(&mut self, _: &T) {
+        self.depth = self.depth.saturating_sub(1);
+        if self.high_reached && self.depth <= self.low {
+            self.high_reached = false;
+            self.events.push_back(WatermarkEvent::Low);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ObservedKStack;
+
+    #[test]
+    fn test_watermarks() {
+        let mut stack = ObservedKStack::<u8, 2, _>::new(Watermarks::new(0, 2));
+        stack.push(1);
+        assert!(!stack.observer().is_high());
+
+        stack.push(2);
+        stack.pop();
+        stack.push(3);
+        stack.kpop();
+        stack.push(4);
+        stack.push(5);
+        assert!(stack.observer().is_high());
+        assert_eq!(2, stack.observer().depth());
+
+        let watermarks = stack.observer_mut();
+        assert_eq!(Some(WatermarkEvent::High), watermarks.poll());
+        assert_eq!(Some(WatermarkEvent::Low), watermarks.poll());
+        assert_eq!(Some(WatermarkEvent::High), watermarks.poll());
+        assert_eq!(None, watermarks.poll());
+        assert_eq!((0, 2), (watermarks.low(), watermarks.high()));
+    }
+
+    #[test]
+    #[should_panic(expected = "low watermark 2 is not less than high watermark 2")]
+    fn test_new_panic() {
+        Watermarks::new(2, 2);
+    }
+}
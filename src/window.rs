@@ -0,0 +1,233 @@
+use core::mem::MaybeUninit;
+use core::{fmt, ptr, slice};
+
+use crate::iter::Iter;
+
+/// Borrowed view of K top element on stack, returned by
+/// [`KStack::kshow_view`](crate::KStack::kshow_view).
+///
+/// Element are indexed from the top, so index 0 is the top of stack.
+#[derive(Debug)]
+pub struct KWindow<'a, T, const K: usize>(pub(crate) &'a [T]);
+
+impl<'a, T, const K: usize> KWindow<'a, T, K> {
+    /// Number of element in window, which is K unless stack has less than
+    /// K element.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether window is empty, which means stack is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether window has all the K element.
+    pub fn is_full(&self) -> bool {
+        self.0.len() == K
+    }
+
+    /// Get element `index` position below the top.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3, 4]);
+    ///     let window = stack.kshow_view();
+    ///
+    ///     assert_eq!(Some(&4), window.get(0));
+    ///     assert_eq!(Some(&2), window.get(2));
+    ///     assert_eq!(None, window.get(3));
+    /// ```
+    pub fn get(&self, index: usize) -> Option<&'a T> {
+        let len = self.0.len();
+        match index < len {
+            true => Some(&self.0[len - index - 1]),
+            false => None,
+        }
+    }
+
+    /// Returns an iterator over window element, from top to bottom.
+    pub fn iter(&self) -> Iter<'a, T> {
+        Iter(self.0.iter().rev())
+    }
+
+    /// Whether window contains an element equal to `item`.
+    pub fn contains(&self, item: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.0.contains(item)
+    }
+
+    /// Window element in stack order, so the top element is the last one.
+    pub fn as_slice(&self) -> &'a [T] {
+        self.0
+    }
+
+    /// Copy window element to an array, in the same order as
+    /// [`KStack::kshow`](crate::KStack::kshow).
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 3>::new();
+    ///
+    ///     stack.kpush(&[1, 2]);
+    ///
+    ///     assert_eq!([Some(2), Some(1), None], stack.kshow_view().to_array());
+    /// ```
+    pub fn to_array(&self) -> [Option<T>; K]
+    where
+        T: Clone,
+    {
+        let mut items = self.iter();
+        core::array::from_fn(|_| items.next().cloned())
+    }
+}
+
+impl<T, const K: usize> Clone for KWindow<'_, T, K> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, const K: usize> Copy for KWindow<'_, T, K> {}
+
+impl<'a, T, const K: usize> IntoIterator for KWindow<'a, T, K> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Up to K element popped by [`KStack::kpop_raw`](crate::KStack::kpop_raw),
+/// held inline without an [`Option`] per element.
+///
+/// Element are top first, like [`KStack::kpop`](crate::KStack::kpop), and
+/// only the popped ones are initialized, so `T` needs no default value.
+pub struct RawKWindow<T, const K: usize> {
+    items: [MaybeUninit<T>; K],
+    len: usize,
+}
+
+impl<T, const K: usize> RawKWindow<T, K> {
+    pub(crate) fn new() -> Self {
+        RawKWindow {
+            // SAFETY: an array of `MaybeUninit` doesn't need initialization.
+            items: unsafe { MaybeUninit::<[MaybeUninit<T>; K]>::uninit().assume_init() },
+            len: 0,
+        }
+    }
+
+    /// Buffer to pop into, the first `len` items must be initialized
+    /// before it's returned.
+    pub(crate) fn buffer(&mut self) -> &mut [MaybeUninit<T>; K] {
+        &mut self.items
+    }
+
+    /// # Safety
+    /// The first `len` items must be initialized.
+    pub(crate) unsafe fn set_len(&mut self, len: usize) {
+        self.len = len;
+    }
+
+    /// Number of element popped, which is K unless stack had less than K
+    /// element.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether nothing was popped.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Whether all the K element were popped.
+    pub fn is_full(&self) -> bool {
+        self.len == K
+    }
+
+    /// Popped element, top first.
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: first `len` items are always initialized.
+        unsafe { slice::from_raw_parts(self.items.as_ptr() as *const T, self.len) }
+    }
+
+    /// Popped element, top first.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: first `len` items are always initialized.
+        unsafe { slice::from_raw_parts_mut(self.items.as_mut_ptr() as *mut T, self.len) }
+    }
+
+    /// Convert to an array if all the K element were popped, or give
+    /// window back otherwise.
+    ///
+    /// # Example
+    /// ```
+    ///     use kstack::KStack;
+    ///
+    ///     let mut stack = KStack::<i32, 2>::new();
+    ///
+    ///     stack.kpush(&[1, 2, 3]);
+    ///
+    ///     assert_eq!(Some([3, 2]), stack.kpop_raw().into_array().ok());
+    ///     assert_eq!(&[1], stack.kpop_raw().into_array().unwrap_err().as_slice());
+    /// ```
+    pub fn into_array(self) -> Result<[T; K], Self> {
+        if !self.is_full() {
+            return Err(self);
+        }
+        let window = core::mem::ManuallyDrop::new(self);
+        // SAFETY: all K items are initialized, and `window` is never
+        // dropped so they are moved out once.
+        Ok(unsafe { (window.items.as_ptr() as *const [T; K]).read() })
+    }
+}
+
+impl<T, const K: usize> Drop for RawKWindow<T, K> {
+    fn drop(&mut self) {
+        let items = self.as_mut_slice() as *mut [T];
+        self.len = 0;
+        // SAFETY: first `len` items were initialized and are dropped once.
+        unsafe { ptr::drop_in_place(items) };
+    }
+}
+
+impl<T: fmt::Debug, const K: usize> fmt::Debug for RawKWindow<T, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::KStack;
+
+    #[test]
+    fn test_window() {
+        let mut stack = KStack::<i32, 3>::new();
+        assert!(stack.kshow_view().is_empty());
+
+        stack.kpush(&[1, 2]);
+        let window = stack.kshow_view();
+        assert_eq!(2, window.len());
+        assert!(!window.is_full());
+        assert!(window.contains(&1));
+        assert!(!window.contains(&3));
+
+        stack.kpush(&[3, 4]);
+        let window = stack.kshow_view();
+        assert!(window.is_full());
+        assert_eq!(vec![&4, &3, &2], window.iter().collect::<Vec<_>>());
+        assert_eq!(&[2, 3, 4], window.as_slice());
+        assert_eq!(stack.kshow(), window.to_array());
+        assert!(!window.contains(&1));
+    }
+}